@@ -0,0 +1,90 @@
+"""Gathers criterion's results into one machine readable report, and compares two reports
+
+    cargo bench
+    python3 benches/collect.py report > bench-$(git rev-parse --short HEAD).json
+    python3 benches/collect.py compare bench-old.json bench-new.json
+"""
+
+import json
+import os
+import subprocess
+import sys
+
+CRITERION_DIR = os.path.join("target", "criterion")
+
+
+def git_commit():
+    try:
+        return subprocess.check_output(["git", "rev-parse", "HEAD"], text=True).strip()
+    except (OSError, subprocess.CalledProcessError):
+        return None
+
+
+def load_benchmarks(root):
+    benchmarks = {}
+
+    for directory, _, files in os.walk(root):
+        # every benchmark keeps its latest run in a `new` directory
+        if os.path.basename(directory) != "new" or "estimates.json" not in files:
+            continue
+
+        with open(os.path.join(directory, "benchmark.json")) as file:
+            info = json.load(file)
+        with open(os.path.join(directory, "estimates.json")) as file:
+            estimates = json.load(file)
+
+        benchmarks[info["full_id"]] = {
+            "group": info["group_id"],
+            "function": info["function_id"],
+            "parameter": info["value_str"],
+            "throughput": info["throughput"],
+            "mean_ns": estimates["mean"]["point_estimate"],
+            "median_ns": estimates["median"]["point_estimate"],
+            "std_dev_ns": estimates["std_dev"]["point_estimate"],
+        }
+
+    return benchmarks
+
+
+def report():
+    result = {
+        "commit": git_commit(),
+        "benchmarks": load_benchmarks(CRITERION_DIR),
+    }
+    json.dump(result, sys.stdout, indent=4, sort_keys=True)
+    print()
+
+
+def compare(old_path, new_path):
+    with open(old_path) as file:
+        old = json.load(file)
+    with open(new_path) as file:
+        new = json.load(file)
+
+    print(f"{old['commit']} -> {new['commit']}")
+    print(f"{'benchmark':<40} {'old [ms]':>12} {'new [ms]':>12} {'change':>9}")
+
+    for name in sorted(set(old["benchmarks"]) | set(new["benchmarks"])):
+        before = old["benchmarks"].get(name)
+        after = new["benchmarks"].get(name)
+
+        if before is None or after is None:
+            missing = "new" if before is None else "removed"
+            print(f"{name:<40} {missing:>12}")
+            continue
+
+        before = before["median_ns"] / 1e6
+        after = after["median_ns"] / 1e6
+        change = (after - before) / before * 100
+
+        print(f"{name:<40} {before:>12.4f} {after:>12.4f} {change:>+8.1f}%")
+
+
+if __name__ == "__main__":
+    if len(sys.argv) == 2 and sys.argv[1] == "report":
+        report()
+    elif len(sys.argv) == 4 and sys.argv[1] == "compare":
+        compare(sys.argv[2], sys.argv[3])
+    else:
+        print(__doc__)
+        sys.exit(1)
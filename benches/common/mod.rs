@@ -0,0 +1,53 @@
+//! Setup shared between the benchmarks, every benchmark runs the
+//! `inputs/constant_temperature.json` case at a range of grid sizes
+
+#![allow(dead_code)]
+
+use finite_difference::input::{self, Conditions, Input};
+use finite_difference::prelude::SolverInfo;
+use finite_difference::setup::BoundaryConditions;
+use finite_difference::SolverParams;
+
+use finite_difference::conditions::Condition;
+
+pub const SIZES: [usize; 3] = [32, 64, 128];
+
+pub type Boundaries = BoundaryConditions<
+    Condition<f64>,
+    Condition<f64>,
+    Condition<f64>,
+    Condition<f64>,
+    Condition<f64>,
+    Condition<f64>,
+>;
+
+pub fn input() -> Input<f64> {
+    let raw = input::read("inputs/constant_temperature.json").unwrap();
+    serde_json::from_value(raw).unwrap()
+}
+
+pub fn conditions() -> Conditions<f64> {
+    input().conditions
+}
+
+pub fn boundaries() -> Boundaries {
+    conditions().make_boundaries()
+}
+
+/// solver parameters for a grid of `divisions` nodes along each side
+pub fn params(divisions: usize) -> SolverParams<f64> {
+    SolverParams {
+        divisions,
+        ..input().solver_params
+    }
+}
+
+pub fn solver_info(divisions: usize) -> SolverInfo<f64> {
+    let input = input();
+
+    SolverInfo::builder()
+        .k(input.thermal_conductivity)
+        .q_dot(input.energy_generation)
+        .del(params(divisions).del())
+        .build()
+}
@@ -0,0 +1,63 @@
+//! JSON serialization of a `SaveFile` holding one recorded step
+
+mod common;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use finite_difference::dump::{ErrorData, ErrorType, SaveFile, SimulationResult, StepData};
+use finite_difference::prelude::Precision;
+
+fn dump(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dump");
+
+    for divisions in common::SIZES {
+        let nodes = divisions.pow(3);
+
+        let simulation = SimulationResult {
+            step_data: vec![StepData {
+                step: 0,
+                data: (0..nodes).map(|i| 273. + i as f64 / nodes as f64).collect(),
+            }],
+            error_decay: ErrorData {
+                error_type: ErrorType::L2Norm,
+                data: vec![1.; 100],
+            },
+            size: divisions,
+            shape: None,
+            num_steps: 0,
+            probes: Vec::new(),
+            final_error: None,
+            wall_time: None,
+            stopped: false,
+        };
+
+        let save = SaveFile {
+            conditions: common::conditions(),
+            simulation,
+            solver_params: common::params(divisions),
+            precision: Precision::Double,
+            thermal_conductivity: None,
+            energy_generation: None,
+            samples: None,
+            heat_flux: None,
+            geometry: None,
+            cylinder: None,
+            orthotropic: None,
+            mirrored: None,
+        };
+
+        let mut buffer = Vec::with_capacity(nodes * 20);
+
+        group.throughput(Throughput::Elements(nodes as u64));
+        group.bench_with_input(BenchmarkId::new("json", divisions), &divisions, |b, _| {
+            b.iter(|| {
+                buffer.clear();
+                serde_json::to_writer(&mut buffer, &save).unwrap();
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, dump);
+criterion_main!(benches);
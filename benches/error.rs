@@ -0,0 +1,46 @@
+//! A solver step reducing each error norm over the difference between the two steps
+//!
+//! the norms are fused into the sweep, so each is timed through `Coefficients::sweep` on the
+//! same step. A change to a norm shows up as a difference between them, a change to the
+//! stencils moves all three alike
+
+mod common;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use finite_difference::error::{InfinityNorm, L1Norm, L2Norm};
+use finite_difference::prelude::Matrix;
+use finite_difference::stencil::{Coefficients, Sweep};
+
+fn error(c: &mut Criterion) {
+    let bcs = common::boundaries();
+
+    let mut group = c.benchmark_group("error");
+
+    for divisions in common::SIZES {
+        let s = common::solver_info(divisions);
+        let coefficients = Coefficients::new(&bcs, &s, divisions, Sweep::default()).unwrap();
+
+        let shape = (divisions, divisions, divisions);
+        let previous = Matrix::from_shape_fn(shape, |(x, y, z)| 273. + (x + y + z) as f64);
+        let mut current = previous.clone();
+
+        group.throughput(Throughput::Elements(divisions.pow(3) as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("infinity", divisions),
+            &divisions,
+            |b, _| b.iter(|| coefficients.sweep(&previous, &mut current, &InfinityNorm)),
+        );
+        group.bench_with_input(BenchmarkId::new("l1", divisions), &divisions, |b, _| {
+            b.iter(|| coefficients.sweep(&previous, &mut current, &L1Norm))
+        });
+        group.bench_with_input(BenchmarkId::new("l2", divisions), &divisions, |b, _| {
+            b.iter(|| coefficients.sweep(&previous, &mut current, &L2Norm))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, error);
+criterion_main!(benches);
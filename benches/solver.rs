@@ -0,0 +1,51 @@
+//! Full solves of `inputs/constant_temperature.json` to convergence
+//!
+//! converging to the input file's `error_epsilon` takes minutes at 128^3, so these solves stop
+//! once no node changes by more than `EPSILON` kelvin in a step. The 128^3 case still takes
+//! several minutes, `cargo bench --bench solver -- 'solve/(32|64)'` skips it
+
+mod common;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, SamplingMode};
+use finite_difference::error::InfinityNorm;
+use finite_difference::solver;
+use finite_difference::SolverParams;
+
+const EPSILON: f64 = 0.01;
+
+fn solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solve");
+    group.sample_size(10);
+    group.sampling_mode(SamplingMode::Flat);
+
+    for divisions in common::SIZES {
+        let params = SolverParams {
+            error_epsilon: EPSILON,
+            data_steps: usize::MAX,
+            ..common::params(divisions)
+        };
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(divisions),
+            &divisions,
+            |b, _| {
+                b.iter(|| {
+                    solver::solver(
+                        common::solver_info(divisions),
+                        params,
+                        common::boundaries(),
+                        InfinityNorm,
+                        &[],
+                        (),
+                    )
+                    .unwrap()
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, solve);
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+//! Compares the slab and tiled interior sweeps on a single step at several grid sizes
+//!
+//! `cargo bench --bench sweep`
+
+mod common;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use finite_difference::error::L2Norm;
+use finite_difference::prelude::Matrix;
+use finite_difference::stencil::{Coefficients, Sweep, DEFAULT_TILE};
+
+fn sweeps() -> Vec<(String, Sweep)> {
+    vec![
+        ("slab".to_string(), Sweep::Slab),
+        (
+            "tiled_default".to_string(),
+            Sweep::Tiled { tile: DEFAULT_TILE },
+        ),
+        (
+            "tiled_4x4x1024".to_string(),
+            Sweep::Tiled { tile: [4, 4, 1024] },
+        ),
+        (
+            "tiled_1x64x1024".to_string(),
+            Sweep::Tiled {
+                tile: [1, 64, 1024],
+            },
+        ),
+        (
+            "tiled_32x32x32".to_string(),
+            Sweep::Tiled { tile: [32, 32, 32] },
+        ),
+    ]
+}
+
+fn sweep(c: &mut Criterion) {
+    let bcs = common::boundaries();
+
+    let mut group = c.benchmark_group("sweep");
+
+    for divisions in common::SIZES {
+        let s = common::solver_info(divisions);
+
+        let previous: Matrix<f64> = Matrix::from_elem((divisions, divisions, divisions), 273.);
+        let mut current = previous.clone();
+
+        group.throughput(Throughput::Elements(divisions.pow(3) as u64));
+
+        for (name, sweep) in sweeps() {
+            let coefficients = Coefficients::new(&bcs, &s, divisions, sweep).unwrap();
+
+            group.bench_with_input(BenchmarkId::new(name, divisions), &divisions, |b, _| {
+                b.iter(|| coefficients.sweep(&previous, &mut current, &L2Norm))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, sweep);
+criterion_main!(benches);
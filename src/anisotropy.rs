@@ -0,0 +1,146 @@
+//! Conductivity that differs along each axis, such as the in-plane and through-thickness
+//! conductivity of a laminate or a circuit board
+//!
+//! the stencils weight the conduction along each axis by its conductivity relative to the
+//! input's `thermal_conductivity`, which the boundary conditions' terms are divided by as
+//! before. A material frame turned away from the cube's axes gives a full tensor, whose cross
+//! terms would couple each node to its diagonal neighbours, which the six point stencils do
+//! not reach. Only turns that leave the cross terms negligible, such as quarter turns, can be
+//! solved
+
+use crate::prelude::*;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Largest cross term, relative to the largest diagonal term, that the solver leaves out
+pub const CROSS_TERMS: f64 = 1e-3;
+
+/// A material frame the solver cannot solve
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Error {
+    /// turned so that the largest cross term is this share of the largest diagonal term
+    CrossTerms(f64),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::CrossTerms(share) => write!(
+                f,
+                "the rotated conductivity has cross terms of {share:.3} of its diagonal, which \
+                 the stencils cannot solve, turn the material by quarter turns only"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Conductivities along the axes of a material, which may be turned relative to the cube
+///
+/// ```json
+/// "orthotropic": {"k": [20.0, 0.3, 20.0], "rotation": [0.0, 0.0, 30.0]}
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Orthotropic<T> {
+    /// along the material's own x, y and z axes `[W/(mK)]`
+    pub k: [T; 3],
+    /// angles the material's axes are turned through about the cube's x, y and then z axes
+    /// `[deg]`, aligned with the cube when not given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<[T; 3]>,
+}
+
+impl<T: Float> Orthotropic<T> {
+    /// Material axes in the cube's frame, as the columns of a rotation matrix
+    fn frame(&self) -> [[T; 3]; 3] {
+        let [x, y, z] = self
+            .rotation
+            .unwrap_or([T::zero(); 3])
+            .map(|angle| angle.to_radians());
+
+        let about_x = [
+            [T::one(), T::zero(), T::zero()],
+            [T::zero(), x.cos(), -x.sin()],
+            [T::zero(), x.sin(), x.cos()],
+        ];
+        let about_y = [
+            [y.cos(), T::zero(), y.sin()],
+            [T::zero(), T::one(), T::zero()],
+            [-y.sin(), T::zero(), y.cos()],
+        ];
+        let about_z = [
+            [z.cos(), -z.sin(), T::zero()],
+            [z.sin(), z.cos(), T::zero()],
+            [T::zero(), T::zero(), T::one()],
+        ];
+
+        multiply(about_z, multiply(about_y, about_x))
+    }
+
+    /// Conductivity tensor in the cube's frame `[W/(mK)]`
+    pub fn tensor(&self) -> [[T; 3]; 3] {
+        let frame = self.frame();
+
+        [0, 1, 2].map(|row| {
+            [0, 1, 2].map(|column| {
+                (0..3).fold(T::zero(), |sum, axis| {
+                    sum + frame[row][axis] * self.k[axis] * frame[column][axis]
+                })
+            })
+        })
+    }
+
+    /// Conductivity along each of the cube's axes, the diagonal of the tensor `[W/(mK)]`
+    pub fn along_axes(&self) -> [T; 3] {
+        let tensor = self.tensor();
+        [0, 1, 2].map(|axis| tensor[axis][axis])
+    }
+
+    /// Conductivity along each of the cube's axes relative to `k`, for `SolverInfo::anisotropy`
+    pub fn anisotropy(&self, k: T) -> [T; 3] {
+        self.along_axes().map(|along| along / k)
+    }
+
+    /// Largest cross term of the tensor relative to its largest diagonal term
+    pub fn cross_terms(&self) -> T {
+        let tensor = self.tensor();
+        let diagonal = self
+            .along_axes()
+            .into_iter()
+            .fold(T::zero(), |max, along| max.max(along));
+
+        [(0, 1), (0, 2), (1, 2)]
+            .into_iter()
+            .fold(T::zero(), |max, (row, column)| {
+                max.max(tensor[row][column].abs())
+            })
+            / diagonal
+    }
+
+    /// Fails when the cross terms are too large to leave out, see `CROSS_TERMS`
+    pub fn check(&self) -> Result<(), Error> {
+        let cross_terms = self.cross_terms();
+
+        match cross_terms > lit(CROSS_TERMS) {
+            true => Err(Error::CrossTerms(cross_terms.to_f64().unwrap())),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Tensor of a conductivity `k` along each of the cube's axes, without cross terms `[W/(mK)]`
+pub fn diagonal<T: Float>(k: [T; 3]) -> [[T; 3]; 3] {
+    [0, 1, 2].map(|row| [0, 1, 2].map(|column| if row == column { k[row] } else { T::zero() }))
+}
+
+fn multiply<T: Float>(a: [[T; 3]; 3], b: [[T; 3]; 3]) -> [[T; 3]; 3] {
+    [0, 1, 2].map(|row| {
+        [0, 1, 2].map(|column| {
+            (0..3).fold(T::zero(), |sum, inner| {
+                sum + a[row][inner] * b[inner][column]
+            })
+        })
+    })
+}
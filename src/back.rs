@@ -1,25 +1,27 @@
 use crate::prelude::*;
 
-pub struct BackSurface<V: BoundaryCondition> {
+pub struct BackSurface<V> {
     pub back_boundary: V,
 }
 
-impl<V> CalculateTemperature for BackSurface<V>
+impl<T, V> CalculateTemperature<T> for BackSurface<V>
 where
-    V: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.back_boundary);
 
+        let [ax, ay, az] = s.anisotropy;
         let area = s.del2();
 
-        let m = (info.j_front + info.j_back + info.i_front + info.i_back) / 2.
-            + info.k_front
-            + self.back_boundary.lhs_constant(&info, &s, area);
+        let m = (ay * (info.j_front + info.j_back) + ax * (info.i_front + info.i_back)) / lit(2.)
+            + az * info.k_front
+            + self.back_boundary.lhs_constant(&info, s, area);
 
-        let numerator = m + (s.q_dot * s.del2() / (2.0 * s.k));
+        let numerator = m + (s.q_dot * s.del2() / (lit::<T>(2.0) * s.k));
 
-        let div = 3. + self.back_boundary.rhs_constant(&info, s, area);
+        let div = ax + ay + az + self.back_boundary.rhs_constant(&info, s, area);
 
         numerator / div
     }
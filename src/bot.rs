@@ -1,25 +1,27 @@
 use crate::prelude::*;
 
-pub struct BottomSurface<V: BoundaryCondition> {
+pub struct BottomSurface<V> {
     pub bot_boundary: V,
 }
 
-impl<V> CalculateTemperature for BottomSurface<V>
+impl<T, V> CalculateTemperature<T> for BottomSurface<V>
 where
-    V: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.bot_boundary);
 
+        let [ax, ay, az] = s.anisotropy;
         let area = s.del2();
 
-        let m = (info.i_front + info.i_back + info.k_front + info.k_back) / 2.
-            + info.j_front
-            + self.bot_boundary.lhs_constant(&info, &s, area);
+        let m = (ax * (info.i_front + info.i_back) + az * (info.k_front + info.k_back)) / lit(2.)
+            + ay * info.j_front
+            + self.bot_boundary.lhs_constant(&info, s, area);
 
-        let numerator = m + (s.q_dot * s.del2() / (2.0 * s.k));
+        let numerator = m + (s.q_dot * s.del2() / (lit::<T>(2.0) * s.k));
 
-        let div = 3. + self.bot_boundary.rhs_constant(&info, s, area);
+        let div = ax + ay + az + self.bot_boundary.rhs_constant(&info, s, area);
 
         numerator / div
     }
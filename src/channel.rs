@@ -0,0 +1,340 @@
+//! Faces cooled by a coolant that heats up as it flows along them, as in a cold plate
+//!
+//! the coolant is a single stream along one axis of the face, at one temperature across its
+//! width. It is marched from the inlet one row of face nodes at a time, each row exchanging
+//! heat with the stream over its share of the face at the row's mean temperature, exactly
+//! for a surface at that temperature. The temperature of the coolant beside each row is the
+//! one that convects the heat the stream picks up there, so what leaves the face is what the
+//! stream carries away. The stencils take it as `t_inf` of every node of the row, and the
+//! cube is solved again with the coolant marched along the last solve until it settles
+
+use crate::conditions::{BoundaryCondition, Condition, Convection};
+use crate::dump::SimulationResult;
+use crate::input::Conditions;
+use crate::prelude::*;
+use crate::probe::Axis;
+use crate::stencil::Coefficients;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Largest change of the coolant temperature beside any node between two solves that ends
+/// the iteration `[K]`
+pub const TOLERANCE: f64 = 1e-3;
+
+/// Most solves made while settling the coolant temperatures
+pub const PASSES: usize = 20;
+
+/// The faces of the cube as `(axis, high)`, in the order of the report
+const FACES: [(usize, bool); 6] = [
+    (0, true),
+    (0, false),
+    (1, true),
+    (1, false),
+    (2, true),
+    (2, false),
+];
+
+/// Convection to a coolant stream flowing along the face
+///
+/// ```json
+/// {"type": "Channel", "mass_flow": 0.02, "cp": 4180.0, "t_inlet": 293.0, "h": 2500.0,
+///  "along": "x"}
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct Channel<T> {
+    /// `[kg/s]`
+    pub mass_flow: T,
+    /// specific heat capacity of the coolant `[J/(kg K)]`
+    pub cp: T,
+    /// `[K]`
+    pub t_inlet: T,
+    /// between the face and the coolant `[W/(m^2 K)]`
+    pub h: T,
+    /// axis the coolant flows along, one of the two in the plane of the face
+    pub along: Axis,
+    /// flowing from the high end of `along` to the low end
+    #[serde(default)]
+    pub reversed: bool,
+}
+
+/// A channel that cannot be marched
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// the channel flows along the normal of its face
+    AlongNormal(Axis),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::AlongNormal(axis) => write!(
+                f,
+                "a coolant channel flows along {}, the normal of its face",
+                ["x", "y", "z"][axis.index()]
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The coolant marched along a face
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stream<T> {
+    /// beside each row of face nodes, by index along the channel `[K]`
+    pub fluid: Vec<T>,
+    /// leaving the far end of the face `[K]`
+    pub outlet: T,
+}
+
+/// The stencils convect to the coolant at its inlet temperature, which `Coolant::constants`
+/// replaces by the marched temperature beside each node
+impl<T: Float> BoundaryCondition<T> for Channel<T> {
+    fn lhs_constant(&self, _: &Information<T>, s: &SolverInfo<T>, area: T) -> T {
+        area * self.h * self.t_inlet / (s.k * s.del)
+    }
+    fn rhs_constant(&self, _: &Information<T>, s: &SolverInfo<T>, area: T) -> T {
+        area * self.h / (s.k * s.del)
+    }
+}
+
+impl<T: Float> Channel<T> {
+    /// Fails when the channel cannot flow along the face normal to `axis`
+    pub fn check(&self, axis: usize) -> Result<(), Error> {
+        match self.along.index() == axis {
+            true => Err(Error::AlongNormal(self.along)),
+            false => Ok(()),
+        }
+    }
+
+    /// Marches the coolant along the face normal to `axis`, on its `high` side, past the
+    /// temperatures `temps` of nodes `del` apart
+    pub fn march(
+        &self,
+        temps: &Matrix<T>,
+        del: T,
+        axis: usize,
+        high: bool,
+    ) -> Result<Stream<T>, Error> {
+        self.check(axis)?;
+
+        let shape = temps.shape();
+        let along = self.along.index();
+        let across = 3 - axis - along;
+        let layer = if high { shape[axis] - 1 } else { 0 };
+
+        // half a cell at either end of an axis, as the boundary stencils have
+        let share = |index: usize, n: usize| {
+            if n > 1 && (index == 0 || index == n - 1) {
+                lit::<T>(0.5) * del
+            } else {
+                del
+            }
+        };
+
+        let capacity = self.mass_flow * self.cp;
+        let mut fluid = vec![self.t_inlet; shape[along]];
+        let mut t = self.t_inlet;
+
+        let rows: Vec<usize> = match self.reversed {
+            false => (0..shape[along]).collect(),
+            true => (0..shape[along]).rev().collect(),
+        };
+
+        for row in rows {
+            let (total, area) =
+                (0..shape[across]).fold((T::zero(), T::zero()), |(total, area), i| {
+                    let mut node = [layer; 3];
+                    node[along] = row;
+                    node[across] = i;
+
+                    let w = share(row, shape[along]) * share(i, shape[across]);
+                    (total + w * temps[node], area + w)
+                });
+            let surface = total / area;
+
+            // transfer units of the row, over which the stream approaches the surface
+            // exponentially
+            let units = self.h * area / capacity;
+            let outlet = surface - (surface - t) * (-units).exp();
+
+            if units > T::zero() {
+                fluid[row] = surface - (outlet - t) / units;
+            }
+            t = outlet;
+        }
+
+        Ok(Stream { fluid, outlet: t })
+    }
+
+    /// Heat the coolant carries away from the face `[W]`
+    pub fn heat_flow(&self, stream: &Stream<T>) -> T {
+        self.mass_flow * self.cp * (stream.outlet - self.t_inlet)
+    }
+}
+
+impl<T: Float> fmt::Display for Channel<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.reversed { "-" } else { "+" };
+        let along = ["x", "y", "z"][self.along.index()];
+
+        write!(
+            f,
+            "channel along {sign}{along} m_dot = {}, cp = {}, h = {}, t_inlet = {}",
+            self.mass_flow, self.cp, self.h, self.t_inlet
+        )
+    }
+}
+
+/// The channel of each face of `conditions`, in the order of `FACES`
+fn channels<T: Copy>(conditions: &Conditions<T>) -> [Option<Channel<T>>; 6] {
+    [
+        conditions.right_boundary,
+        conditions.left_boundary,
+        conditions.top_boundary,
+        conditions.bot_boundary,
+        conditions.front_boundary,
+        conditions.back_boundary,
+    ]
+    .map(|condition| match condition {
+        Condition::Channel(channel) => Some(channel),
+        _ => None,
+    })
+}
+
+/// The coolant temperature beside the nodes of every channel of the cube
+#[derive(Clone, Debug, PartialEq)]
+pub struct Coolant<T> {
+    /// by face, right, left, top, bottom, front and back, `None` without a channel
+    pub faces: [Option<Vec<T>>; 6],
+}
+
+impl<T: Float> Coolant<T> {
+    /// Every channel of `conditions` at its inlet temperature along a cube of `divisions`
+    pub fn inlet(conditions: &Conditions<T>, divisions: usize) -> Result<Self, Error> {
+        let channels = channels(conditions);
+
+        for (channel, (axis, _)) in channels.iter().zip(FACES) {
+            if let Some(channel) = channel {
+                channel.check(axis)?;
+            }
+        }
+
+        Ok(Coolant {
+            faces: channels.map(|channel| channel.map(|channel| vec![channel.t_inlet; divisions])),
+        })
+    }
+
+    /// Every channel of `conditions` marched past `temps`
+    pub fn march(conditions: &Conditions<T>, temps: &Matrix<T>, del: T) -> Result<Self, Error> {
+        let mut faces: [Option<Vec<T>>; 6] = Default::default();
+
+        for ((channel, (axis, high)), fluid) in
+            channels(conditions).iter().zip(FACES).zip(&mut faces)
+        {
+            if let Some(channel) = channel {
+                *fluid = Some(channel.march(temps, del, axis, high)?.fluid);
+            }
+        }
+
+        Ok(Coolant { faces })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.faces.iter().all(Option::is_none)
+    }
+
+    /// Largest difference between the coolant temperatures of `self` and `other` `[K]`
+    pub fn change(&self, other: &Self) -> T {
+        self.faces
+            .iter()
+            .zip(&other.faces)
+            .filter_map(|(a, b)| a.as_ref().zip(b.as_ref()))
+            .flat_map(|(a, b)| a.iter().zip(b))
+            .fold(T::zero(), |largest, (a, b)| largest.max((*a - *b).abs()))
+    }
+
+    /// `coefficients` of the cube with the constants of the channel faces of `conditions`
+    /// convecting to the coolant beside each node
+    pub fn constants(
+        &self,
+        coefficients: Coefficients<T>,
+        conditions: &Conditions<T>,
+        s: &SolverInfo<T>,
+        divisions: usize,
+    ) -> Coefficients<T> {
+        if self.is_empty() {
+            return coefficients;
+        }
+
+        coefficients.with_boundary_constants(divisions, |node| {
+            let mut local = *conditions;
+            let faces = [
+                &mut local.right_boundary,
+                &mut local.left_boundary,
+                &mut local.top_boundary,
+                &mut local.bot_boundary,
+                &mut local.front_boundary,
+                &mut local.back_boundary,
+            ];
+
+            for (condition, fluid) in faces.into_iter().zip(&self.faces) {
+                if let (Condition::Channel(channel), Some(fluid)) = (*condition, fluid) {
+                    *condition = Condition::Convection(Convection {
+                        h: channel.h,
+                        t_inf: fluid[node[channel.along.index()]],
+                    });
+                }
+            }
+
+            (local.make_boundaries(), s.clone())
+        })
+    }
+}
+
+/// Solves with `solve` until the coolant of every channel of `conditions` has settled,
+/// starting from and leaving in `coolant` the temperatures the result was solved with
+///
+/// a single solve when there are no channels, or once a solve is stopped. Each solve after
+/// the first is given the temperatures of the one before to start from. The nodes are `del`
+/// apart. Fails when a channel flows along the normal of its face
+pub fn settle<T: Float>(
+    conditions: &Conditions<T>,
+    del: T,
+    coolant: &mut Coolant<T>,
+    mut solve: impl FnMut(&Coolant<T>, Option<&Matrix<T>>) -> SimulationResult<T>,
+) -> Result<SimulationResult<T>, Error> {
+    let mut previous = None;
+    let mut pass = 1;
+
+    loop {
+        let result = solve(coolant, previous.as_ref());
+
+        if coolant.is_empty() || result.stopped {
+            return Ok(result);
+        }
+
+        let temps = result.temperatures();
+        let marched = Coolant::march(conditions, &temps, del)?;
+        let change = marched.change(coolant);
+
+        tracing::info!(pass, change = change.to_f64(), "coolant temperatures");
+
+        if change < lit(TOLERANCE) {
+            return Ok(result);
+        }
+        if pass == PASSES {
+            tracing::warn!(
+                passes = PASSES,
+                change = change.to_f64(),
+                "the coolant temperatures did not settle"
+            );
+            return Ok(result);
+        }
+
+        *coolant = marched;
+        previous = Some(temps);
+        pass += 1;
+    }
+}
@@ -1,54 +1,177 @@
 use crate::prelude::*;
 
-#[derive(Copy, Clone, serde::Serialize)]
-pub(crate) struct Convection {
-    pub(crate) h: T,
-    pub(crate) t_inf: T,
+#[derive(Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Convection<T> {
+    pub h: T,
+    pub t_inf: T,
 }
-impl BoundaryCondition for Convection {
-    fn lhs_constant(&self, _: &Information, s: &SolverInfo, area: T) -> T {
+impl<T: Float> BoundaryCondition<T> for Convection<T> {
+    fn lhs_constant(&self, _: &Information<T>, s: &SolverInfo<T>, area: T) -> T {
         area * self.h * self.t_inf / (s.k * s.del)
     }
-    fn rhs_constant(&self, _: &Information, s: &SolverInfo, area: T) -> T {
+    fn rhs_constant(&self, _: &Information<T>, s: &SolverInfo<T>, area: T) -> T {
         area * self.h / (s.k * s.del)
     }
 }
 
-pub trait BoundaryCondition {
-    fn lhs_constant(&self, info: &Information, s: &SolverInfo, area: T) -> T;
-    fn rhs_constant(&self, info: &Information, s: &SolverInfo, area: T) -> T;
+pub trait BoundaryCondition<T: Float> {
+    fn lhs_constant(&self, info: &Information<T>, s: &SolverInfo<T>, area: T) -> T;
+    fn rhs_constant(&self, info: &Information<T>, s: &SolverInfo<T>, area: T) -> T;
     fn constant_temperature(&self) -> Option<T> {
         None
     }
+    /// Whether the face is one of a pair of opposite faces that are the same plane, which the
+    /// cube's stencils treat as interior
+    fn is_periodic(&self) -> bool {
+        false
+    }
 }
 
-#[derive(Copy, Clone, serde::Serialize)]
-pub(crate) struct HeatFlux {
-    pub(crate) heat_flux: T,
+#[derive(Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct HeatFlux<T> {
+    pub heat_flux: T,
 }
-impl BoundaryCondition for HeatFlux {
-    fn lhs_constant(&self, _: &Information, s: &SolverInfo, area: T) -> T {
+impl<T: Float> BoundaryCondition<T> for HeatFlux<T> {
+    fn lhs_constant(&self, _: &Information<T>, s: &SolverInfo<T>, area: T) -> T {
         area * self.heat_flux / (s.k * s.del)
     }
-    fn rhs_constant(&self, _: &Information, _: &SolverInfo, _area: T) -> T {
-        0.
+    fn rhs_constant(&self, _: &Information<T>, _: &SolverInfo<T>, _area: T) -> T {
+        T::zero()
     }
 }
 
-#[derive(Copy, Clone, serde::Serialize)]
-pub(crate) struct Temperature {
-    #[allow(dead_code)]
-    pub(crate) temperature: T,
+#[derive(Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Temperature<T> {
+    pub temperature: T,
 }
 
-impl BoundaryCondition for Temperature {
-    fn lhs_constant(&self, _: &Information, _: &SolverInfo, _: T) -> T {
+impl<T: Float> BoundaryCondition<T> for Temperature<T> {
+    fn lhs_constant(&self, _: &Information<T>, _: &SolverInfo<T>, _: T) -> T {
         unimplemented!()
     }
-    fn rhs_constant(&self, _: &Information, _: &SolverInfo, _area: T) -> T {
+    fn rhs_constant(&self, _: &Information<T>, _: &SolverInfo<T>, _area: T) -> T {
         unimplemented!()
     }
     fn constant_temperature(&self) -> Option<T> {
         Some(self.temperature)
     }
 }
+
+/// A plane the part is mirrored across, so no heat crosses it
+#[derive(Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Symmetry;
+
+impl<T: Float> BoundaryCondition<T> for Symmetry {
+    fn lhs_constant(&self, _: &Information<T>, _: &SolverInfo<T>, _: T) -> T {
+        T::zero()
+    }
+    fn rhs_constant(&self, _: &Information<T>, _: &SolverInfo<T>, _area: T) -> T {
+        T::zero()
+    }
+}
+
+/// A face whose nodes are those of the opposite face, which has to be periodic as well, so
+/// heat leaving through one enters through the other
+///
+/// the nodes of a pair of periodic faces are solved as interior nodes, so no stencil takes
+/// its constants, which add nothing
+#[derive(Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Periodic;
+
+impl<T: Float> BoundaryCondition<T> for Periodic {
+    fn lhs_constant(&self, _: &Information<T>, _: &SolverInfo<T>, _: T) -> T {
+        T::zero()
+    }
+    fn rhs_constant(&self, _: &Information<T>, _: &SolverInfo<T>, _area: T) -> T {
+        T::zero()
+    }
+    fn is_periodic(&self) -> bool {
+        true
+    }
+}
+
+/// Any of the boundary conditions above, chosen at run time from the input file
+///
+/// serialized with the name of the condition in a `type` field, e.g.
+/// `{"type": "Convection", "h": 10.0, "t_inf": 298.0}` or `{"type": "Symmetry"}`. Results
+/// saved before the field was added hold the bare fields of a convection, heat flux or
+/// temperature condition, which are read by the fields they have
+#[derive(Copy, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(remote = "Self", tag = "type", bound(deserialize = "T: Float"))]
+pub enum Condition<T> {
+    Convection(Convection<T>),
+    HeatFlux(HeatFlux<T>),
+    Temperature(Temperature<T>),
+    Symmetry(Symmetry),
+    Periodic(Periodic),
+    Correlated(crate::correlation::Correlated<T>),
+    Channel(crate::channel::Channel<T>),
+}
+
+impl<T: serde::Serialize> serde::Serialize for Condition<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Condition::serialize(self, serializer)
+    }
+}
+
+impl<'de, T: Float> serde::Deserialize<'de> for Condition<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+
+        if value.get("type").is_some() {
+            return Condition::deserialize(value).map_err(D::Error::custom);
+        }
+
+        Convection::deserialize(&value)
+            .map(Condition::Convection)
+            .or_else(|_| HeatFlux::deserialize(&value).map(Condition::HeatFlux))
+            .or_else(|_| Temperature::deserialize(&value).map(Condition::Temperature))
+            .map_err(|_| {
+                D::Error::custom(
+                    "a condition without a `type` is not the convection, heat flux or \
+                     temperature of older results",
+                )
+            })
+    }
+}
+
+impl<T: Float> BoundaryCondition<T> for Condition<T> {
+    fn lhs_constant(&self, info: &Information<T>, s: &SolverInfo<T>, area: T) -> T {
+        match self {
+            Condition::Convection(c) => c.lhs_constant(info, s, area),
+            Condition::HeatFlux(c) => c.lhs_constant(info, s, area),
+            Condition::Temperature(c) => c.lhs_constant(info, s, area),
+            Condition::Symmetry(c) => c.lhs_constant(info, s, area),
+            Condition::Periodic(c) => c.lhs_constant(info, s, area),
+            Condition::Correlated(c) => c.lhs_constant(info, s, area),
+            Condition::Channel(c) => c.lhs_constant(info, s, area),
+        }
+    }
+    fn rhs_constant(&self, info: &Information<T>, s: &SolverInfo<T>, area: T) -> T {
+        match self {
+            Condition::Convection(c) => c.rhs_constant(info, s, area),
+            Condition::HeatFlux(c) => c.rhs_constant(info, s, area),
+            Condition::Temperature(c) => c.rhs_constant(info, s, area),
+            Condition::Symmetry(c) => c.rhs_constant(info, s, area),
+            Condition::Periodic(c) => c.rhs_constant(info, s, area),
+            Condition::Correlated(c) => c.rhs_constant(info, s, area),
+            Condition::Channel(c) => c.rhs_constant(info, s, area),
+        }
+    }
+    fn constant_temperature(&self) -> Option<T> {
+        match self {
+            Condition::Convection(c) => c.constant_temperature(),
+            Condition::HeatFlux(c) => c.constant_temperature(),
+            Condition::Temperature(c) => c.constant_temperature(),
+            Condition::Symmetry(c) => c.constant_temperature(),
+            Condition::Periodic(c) => c.constant_temperature(),
+            Condition::Correlated(c) => c.constant_temperature(),
+            Condition::Channel(c) => c.constant_temperature(),
+        }
+    }
+    fn is_periodic(&self) -> bool {
+        matches!(self, Condition::Periodic(_))
+    }
+}
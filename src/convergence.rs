@@ -0,0 +1,250 @@
+//! Grid convergence studies
+//!
+//! the same setup is solved at several resolutions and the final temperatures are interpolated
+//! onto common probe points. From the three finest grids the observed order of accuracy, a
+//! Richardson extrapolated value and the grid convergence index (GCI) of the finest grid are
+//! estimated, following Celik et al. (2008) "Procedure for Estimation and Reporting of
+//! Uncertainty Due to Discretization in CFD Applications"
+
+use crate::prelude::*;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Safety factor of the GCI when the order is observed from three grids
+pub const THREE_GRID_SAFETY_FACTOR: f64 = 1.25;
+/// Safety factor of the GCI when the formal order has to be assumed from two grids
+pub const TWO_GRID_SAFETY_FACTOR: f64 = 3.;
+/// Formal order of the scheme, assumed when there are only two grids
+pub const FORMAL_ORDER: f64 = 2.;
+
+/// Resolutions and probe points of a study, the `convergence` field of an input file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Study<T> {
+    /// nodes along each side of every grid, in any order
+    #[serde(deserialize_with = "grids")]
+    pub divisions: Vec<usize>,
+    /// positions `[m]`, the centre of the cube when empty
+    #[serde(default = "Vec::new")]
+    pub probes: Vec<[T; 3]>,
+}
+
+/// Reads the grids of a study, rejecting those too coarse to have a spacing between their nodes
+fn grids<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<usize>, D::Error> {
+    Vec::<usize>::deserialize(deserializer)?
+        .into_iter()
+        .map(crate::spaced)
+        .collect()
+}
+
+/// Temperatures at every probe from the solution on a single grid
+#[derive(Serialize, Clone, Debug)]
+pub struct Grid<T> {
+    pub divisions: usize,
+    pub del: T,
+    pub values: Vec<T>,
+}
+
+/// What the sequence of grids says about a single value
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct Estimate<T> {
+    /// observed order of accuracy, `None` with fewer than three grids or when it can't be
+    /// determined from the changes between them
+    pub order: Option<T>,
+    /// Richardson extrapolation to zero spacing
+    pub extrapolated: Option<T>,
+    /// grid convergence index of the finest grid, as a fraction of its value
+    pub gci: Option<T>,
+}
+
+// derived `Default` would require `T: Default`
+impl<T> Default for Estimate<T> {
+    fn default() -> Self {
+        Estimate {
+            order: None,
+            extrapolated: None,
+            gci: None,
+        }
+    }
+}
+
+/// Estimates the converged value from values on grids ordered from coarsest to finest
+///
+/// the three finest grids are used when there are at least three, otherwise the formal
+/// order is assumed with the more conservative two grid safety factor
+pub fn estimate<T: Float>(del: &[T], values: &[T]) -> Estimate<T> {
+    assert_eq!(del.len(), values.len(), "one value is needed per grid");
+
+    let n = values.len();
+
+    if n < 2 {
+        return Estimate::default();
+    }
+
+    let (phi_1, phi_2) = (values[n - 1], values[n - 2]);
+    let r_21 = del[n - 2] / del[n - 1];
+
+    let (order, safety_factor) = if n >= 3 {
+        let phi_3 = values[n - 3];
+        let r_32 = del[n - 3] / del[n - 2];
+
+        match observed_order(phi_1, phi_2, phi_3, r_21, r_32) {
+            Some(order) => (Some(order), lit::<T>(THREE_GRID_SAFETY_FACTOR)),
+            None => return exact_or_default(phi_1, phi_2),
+        }
+    } else {
+        (None, lit::<T>(TWO_GRID_SAFETY_FACTOR))
+    };
+
+    let p = order.unwrap_or(lit(FORMAL_ORDER));
+    let r_p = r_21.powf(p);
+
+    let extrapolated = (r_p * phi_1 - phi_2) / (r_p - T::one());
+    let relative = ((phi_1 - phi_2) / phi_1).abs();
+    let gci = safety_factor * relative / (r_p - T::one());
+
+    Estimate {
+        order,
+        extrapolated: Some(extrapolated),
+        gci: Some(gci),
+    }
+}
+
+/// Solves `p = |ln|e_32 / e_21| + q(p)| / ln(r_21)` by fixed point iteration, with
+/// `q(p) = ln((r_21^p - s) / (r_32^p - s))` correcting for unequal refinement ratios
+fn observed_order<T: Float>(phi_1: T, phi_2: T, phi_3: T, r_21: T, r_32: T) -> Option<T> {
+    let e_21 = phi_2 - phi_1;
+    let e_32 = phi_3 - phi_2;
+
+    if e_21.is_zero() || e_32.is_zero() {
+        return None;
+    }
+
+    // negative for oscillatory convergence
+    let s = (e_32 / e_21).signum();
+    let ratio = (e_32 / e_21).abs().ln();
+
+    let mut p = (ratio / r_21.ln()).abs();
+
+    for _ in 0..100 {
+        let q = ((r_21.powf(p) - s) / (r_32.powf(p) - s)).ln();
+        let next = ((ratio + q) / r_21.ln()).abs();
+
+        if !next.is_finite() {
+            return None;
+        }
+
+        let converged = (next - p).abs() < lit(1e-10);
+        p = next;
+
+        if converged {
+            break;
+        }
+    }
+
+    Some(p)
+}
+
+/// Values that haven't changed between the two finest grids are taken as converged
+fn exact_or_default<T: Float>(phi_1: T, phi_2: T) -> Estimate<T> {
+    if phi_1 == phi_2 {
+        Estimate {
+            order: None,
+            extrapolated: Some(phi_1),
+            gci: Some(T::zero()),
+        }
+    } else {
+        Estimate::default()
+    }
+}
+
+/// Summary of a study at a single probe
+#[derive(Serialize, Clone, Debug)]
+pub struct ProbeSummary<T> {
+    pub point: [T; 3],
+    /// value on each grid, from coarsest to finest
+    pub values: Vec<T>,
+    #[serde(flatten)]
+    pub estimate: Estimate<T>,
+}
+
+/// Summary of a whole study, serialized next to the results of each grid
+#[derive(Serialize, Clone, Debug)]
+pub struct Summary<T> {
+    /// from coarsest to finest
+    pub divisions: Vec<usize>,
+    pub del: Vec<T>,
+    pub probes: Vec<ProbeSummary<T>>,
+}
+
+impl<T: Float> Summary<T> {
+    pub fn new(probes: &[[T; 3]], mut grids: Vec<Grid<T>>) -> Self {
+        grids.sort_by_key(|grid| grid.divisions);
+
+        let del: Vec<T> = grids.iter().map(|grid| grid.del).collect();
+
+        let probes = probes
+            .iter()
+            .enumerate()
+            .map(|(index, &point)| {
+                let values: Vec<T> = grids.iter().map(|grid| grid.values[index]).collect();
+
+                ProbeSummary {
+                    point,
+                    estimate: estimate(&del, &values),
+                    values,
+                }
+            })
+            .collect();
+
+        Summary {
+            divisions: grids.iter().map(|grid| grid.divisions).collect(),
+            del,
+            probes,
+        }
+    }
+}
+
+impl<T: Float> fmt::Display for Summary<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let optional = |value: Option<T>, precision: usize| match value {
+            Some(value) => format!("{:.*}", precision, value),
+            None => "-".to_string(),
+        };
+
+        write!(f, "{:<30}", "probe [m]")?;
+        for divisions in &self.divisions {
+            write!(f, " {:>12}", divisions)?;
+        }
+        writeln!(
+            f,
+            " {:>8} {:>12} {:>10}",
+            "order", "extrapolated", "GCI [%]"
+        )?;
+
+        for probe in &self.probes {
+            let [x, y, z] = probe.point;
+            write!(f, "{:<30}", format!("({:.4}, {:.4}, {:.4})", x, y, z))?;
+
+            for value in &probe.values {
+                write!(f, " {:>12.4}", value)?;
+            }
+
+            let estimate = &probe.estimate;
+            let gci = match estimate.gci {
+                Some(gci) => format!("{:.3e}", gci.to_f64().unwrap() * 100.),
+                None => "-".to_string(),
+            };
+
+            writeln!(
+                f,
+                " {:>8} {:>12} {:>10}",
+                optional(estimate.order, 3),
+                optional(estimate.extrapolated, 4),
+                gci
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,34 +1,36 @@
 use crate::prelude::*;
 
 pub struct RightTopBack<A, B, C> {
-    pub(crate) right_condition: A,
-    pub(crate) top_condition: B,
-    pub(crate) back_condition: C,
+    pub right_condition: A,
+    pub top_condition: B,
+    pub back_condition: C,
 }
 
-impl<A, B, C> CalculateTemperature for RightTopBack<A, B, C>
+impl<T, A, B, C> CalculateTemperature<T> for RightTopBack<A, B, C>
 where
-    A: BoundaryCondition,
-    B: BoundaryCondition,
-    C: BoundaryCondition,
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(
             self.right_condition,
             self.top_condition,
             self.back_condition
         );
 
-        let area = s.del2() / 4.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(4.);
 
-        let m = ((info.i_back + info.j_back + info.k_front) / 4.)
+        let m = ((ax * info.i_back + ay * info.j_back + az * info.k_front) / lit(4.))
             + self.right_condition.lhs_constant(&info, s, area)
             + self.top_condition.lhs_constant(&info, s, area)
             + self.back_condition.lhs_constant(&info, s, area);
 
-        let q = s.q_dot * s.del2() / 8.;
+        let q = s.q_dot * s.del2() / (s.k * lit(8.));
 
-        let div = 3. / 4.
+        let div = (ax + ay + az) / lit(4.)
             + self.right_condition.rhs_constant(&info, s, area)
             + self.top_condition.rhs_constant(&info, s, area)
             + self.back_condition.rhs_constant(&info, s, area);
@@ -38,34 +40,36 @@ where
 }
 
 pub struct RightTopFront<A, B, C> {
-    pub(crate) right_condition: A,
-    pub(crate) top_condition: B,
-    pub(crate) front_condition: C,
+    pub right_condition: A,
+    pub top_condition: B,
+    pub front_condition: C,
 }
 
-impl<A, B, C> CalculateTemperature for RightTopFront<A, B, C>
+impl<T, A, B, C> CalculateTemperature<T> for RightTopFront<A, B, C>
 where
-    A: BoundaryCondition,
-    B: BoundaryCondition,
-    C: BoundaryCondition,
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(
             self.right_condition,
             self.top_condition,
             self.front_condition
         );
 
-        let area = s.del2() / 4.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(4.);
 
-        let m = ((info.i_back + info.j_back + info.k_back) / 4.)
+        let m = ((ax * info.i_back + ay * info.j_back + az * info.k_back) / lit(4.))
             + self.right_condition.lhs_constant(&info, s, area)
             + self.top_condition.lhs_constant(&info, s, area)
             + self.front_condition.lhs_constant(&info, s, area);
 
-        let q = s.q_dot * s.del2() / 8.;
+        let q = s.q_dot * s.del2() / (s.k * lit(8.));
 
-        let div = 3. / 4.
+        let div = (ax + ay + az) / lit(4.)
             + self.right_condition.rhs_constant(&info, s, area)
             + self.top_condition.rhs_constant(&info, s, area)
             + self.front_condition.rhs_constant(&info, s, area);
@@ -75,34 +79,36 @@ where
 }
 
 pub struct RightBottomBack<A, B, C> {
-    pub(crate) right_condition: A,
-    pub(crate) bot_condition: B,
-    pub(crate) back_condition: C,
+    pub right_condition: A,
+    pub bot_condition: B,
+    pub back_condition: C,
 }
 
-impl<A, B, C> CalculateTemperature for RightBottomBack<A, B, C>
+impl<T, A, B, C> CalculateTemperature<T> for RightBottomBack<A, B, C>
 where
-    A: BoundaryCondition,
-    B: BoundaryCondition,
-    C: BoundaryCondition,
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(
             self.right_condition,
             self.bot_condition,
             self.back_condition
         );
 
-        let area = s.del2() / 4.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(4.);
 
-        let m = ((info.i_back + info.j_front + info.k_front) / 4.)
+        let m = ((ax * info.i_back + ay * info.j_front + az * info.k_front) / lit(4.))
             + self.right_condition.lhs_constant(&info, s, area)
             + self.bot_condition.lhs_constant(&info, s, area)
             + self.back_condition.lhs_constant(&info, s, area);
 
-        let q = s.q_dot * s.del2() / 8.;
+        let q = s.q_dot * s.del2() / (s.k * lit(8.));
 
-        let div = 3. / 4.
+        let div = (ax + ay + az) / lit(4.)
             + self.right_condition.rhs_constant(&info, s, area)
             + self.bot_condition.rhs_constant(&info, s, area)
             + self.back_condition.rhs_constant(&info, s, area);
@@ -112,34 +118,36 @@ where
 }
 
 pub struct RightBottomFront<A, B, C> {
-    pub(crate) right_condition: A,
-    pub(crate) bot_condition: B,
-    pub(crate) front_condition: C,
+    pub right_condition: A,
+    pub bot_condition: B,
+    pub front_condition: C,
 }
 
-impl<A, B, C> CalculateTemperature for RightBottomFront<A, B, C>
+impl<T, A, B, C> CalculateTemperature<T> for RightBottomFront<A, B, C>
 where
-    A: BoundaryCondition,
-    B: BoundaryCondition,
-    C: BoundaryCondition,
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(
             self.right_condition,
             self.bot_condition,
             self.front_condition
         );
 
-        let area = s.del2() / 4.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(4.);
 
-        let m = ((info.i_back + info.j_front + info.k_back) / 4.)
+        let m = ((ax * info.i_back + ay * info.j_front + az * info.k_back) / lit(4.))
             + self.right_condition.lhs_constant(&info, s, area)
             + self.bot_condition.lhs_constant(&info, s, area)
             + self.front_condition.lhs_constant(&info, s, area);
 
-        let q = s.q_dot * s.del2() / 8.;
+        let q = s.q_dot * s.del2() / (s.k * lit(8.));
 
-        let div = 3. / 4.
+        let div = (ax + ay + az) / lit(4.)
             + self.right_condition.rhs_constant(&info, s, area)
             + self.bot_condition.rhs_constant(&info, s, area)
             + self.front_condition.rhs_constant(&info, s, area);
@@ -148,29 +156,31 @@ where
     }
 }
 pub struct LeftTopBack<A, B, C> {
-    pub(crate) left_condition: A,
-    pub(crate) top_condition: B,
-    pub(crate) back_condition: C,
+    pub left_condition: A,
+    pub top_condition: B,
+    pub back_condition: C,
 }
 
-impl<A, B, C> CalculateTemperature for LeftTopBack<A, B, C>
+impl<T, A, B, C> CalculateTemperature<T> for LeftTopBack<A, B, C>
 where
-    A: BoundaryCondition,
-    B: BoundaryCondition,
-    C: BoundaryCondition,
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.left_condition, self.top_condition, self.back_condition);
-        let area = s.del2() / 4.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(4.);
 
-        let m = ((info.i_front + info.j_back + info.k_front) / 4.)
+        let m = ((ax * info.i_front + ay * info.j_back + az * info.k_front) / lit(4.))
             + self.left_condition.lhs_constant(&info, s, area)
             + self.top_condition.lhs_constant(&info, s, area)
             + self.back_condition.lhs_constant(&info, s, area);
 
-        let q = s.q_dot * s.del2() / 8.;
+        let q = s.q_dot * s.del2() / (s.k * lit(8.));
 
-        let div = 3. / 4.
+        let div = (ax + ay + az) / lit(4.)
             + self.left_condition.rhs_constant(&info, s, area)
             + self.top_condition.rhs_constant(&info, s, area)
             + self.back_condition.rhs_constant(&info, s, area);
@@ -180,30 +190,36 @@ where
 }
 
 pub struct LeftTopFront<A, B, C> {
-    pub(crate) left_condition: A,
-    pub(crate) top_condition: B,
-    pub(crate) front_condition: C,
+    pub left_condition: A,
+    pub top_condition: B,
+    pub front_condition: C,
 }
 
-impl<A, B, C> CalculateTemperature for LeftTopFront<A, B, C>
+impl<T, A, B, C> CalculateTemperature<T> for LeftTopFront<A, B, C>
 where
-    A: BoundaryCondition,
-    B: BoundaryCondition,
-    C: BoundaryCondition,
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
-        constant_temperature!(self.left_condition, self.top_condition, self.top_condition);
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
+        constant_temperature!(
+            self.left_condition,
+            self.top_condition,
+            self.front_condition
+        );
 
-        let area = s.del2() / 4.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(4.);
 
-        let m = ((info.i_front + info.j_back + info.k_back) / 4.)
+        let m = ((ax * info.i_front + ay * info.j_back + az * info.k_back) / lit(4.))
             + self.left_condition.lhs_constant(&info, s, area)
             + self.top_condition.lhs_constant(&info, s, area)
             + self.front_condition.lhs_constant(&info, s, area);
 
-        let q = s.q_dot * s.del2() / 8.;
+        let q = s.q_dot * s.del2() / (s.k * lit(8.));
 
-        let div = 3. / 4.
+        let div = (ax + ay + az) / lit(4.)
             + self.left_condition.rhs_constant(&info, s, area)
             + self.top_condition.rhs_constant(&info, s, area)
             + self.front_condition.rhs_constant(&info, s, area);
@@ -212,30 +228,32 @@ where
     }
 }
 pub struct LeftBottomBack<A, B, C> {
-    pub(crate) left_condition: A,
-    pub(crate) bot_condition: B,
-    pub(crate) back_condition: C,
+    pub left_condition: A,
+    pub bot_condition: B,
+    pub back_condition: C,
 }
 
-impl<A, B, C> CalculateTemperature for LeftBottomBack<A, B, C>
+impl<T, A, B, C> CalculateTemperature<T> for LeftBottomBack<A, B, C>
 where
-    A: BoundaryCondition,
-    B: BoundaryCondition,
-    C: BoundaryCondition,
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.left_condition, self.bot_condition, self.back_condition);
 
-        let area = s.del2() / 4.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(4.);
 
-        let m = ((info.i_front + info.j_front + info.k_front) / 4.)
+        let m = ((ax * info.i_front + ay * info.j_front + az * info.k_front) / lit(4.))
             + self.left_condition.lhs_constant(&info, s, area)
             + self.bot_condition.lhs_constant(&info, s, area)
             + self.back_condition.lhs_constant(&info, s, area);
 
-        let q = s.q_dot * s.del2() / 8.;
+        let q = s.q_dot * s.del2() / (s.k * lit(8.));
 
-        let div = 3. / 4.
+        let div = (ax + ay + az) / lit(4.)
             + self.left_condition.rhs_constant(&info, s, area)
             + self.bot_condition.rhs_constant(&info, s, area)
             + self.back_condition.rhs_constant(&info, s, area);
@@ -244,34 +262,36 @@ where
     }
 }
 pub struct LeftBottomFront<A, B, C> {
-    pub(crate) left_condition: A,
-    pub(crate) bot_condition: B,
-    pub(crate) front_condition: C,
+    pub left_condition: A,
+    pub bot_condition: B,
+    pub front_condition: C,
 }
 
-impl<A, B, C> CalculateTemperature for LeftBottomFront<A, B, C>
+impl<T, A, B, C> CalculateTemperature<T> for LeftBottomFront<A, B, C>
 where
-    A: BoundaryCondition,
-    B: BoundaryCondition,
-    C: BoundaryCondition,
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(
             self.left_condition,
             self.bot_condition,
             self.front_condition
         );
 
-        let area = s.del2() / 4.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(4.);
 
-        let m = ((info.i_front + info.j_front + info.k_back) / 4.)
+        let m = ((ax * info.i_front + ay * info.j_front + az * info.k_back) / lit(4.))
             + self.left_condition.lhs_constant(&info, s, area)
             + self.bot_condition.lhs_constant(&info, s, area)
             + self.front_condition.lhs_constant(&info, s, area);
 
-        let q = s.q_dot * s.del2() / 8.;
+        let q = s.q_dot * s.del2() / (s.k * lit(8.));
 
-        let div = 3. / 4.
+        let div = (ax + ay + az) / lit(4.)
             + self.left_condition.rhs_constant(&info, s, area)
             + self.bot_condition.rhs_constant(&info, s, area)
             + self.front_condition.rhs_constant(&info, s, area);
@@ -0,0 +1,352 @@
+//! Convection whose heat transfer coefficient comes from a correlation for a flat plate
+//! instead of being given
+//!
+//! each face of the cube is a square plate of side `len`, with gravity along `-y` so the top
+//! faces up, the bottom faces down and the other four are vertical. The stencils take a single
+//! `h` per face, so the solve is repeated with every correlated face's `h` evaluated at its
+//! mean temperature from the previous solve, until none of them changes by more than
+//! `TOLERANCE`. The fluid's properties are taken at the film temperature, the mean of the
+//! surface's and the fluid's, from a table of rounded textbook values
+
+use crate::conditions::{BoundaryCondition, Condition};
+use crate::dump::SimulationResult;
+use crate::input::Conditions;
+use crate::prelude::*;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Largest relative change of any correlated `h` between two solves that ends the iteration
+pub const TOLERANCE: f64 = 1e-3;
+
+/// Most solves made while settling the coefficients
+pub const PASSES: usize = 20;
+
+const GRAVITY: f64 = 9.81;
+
+/// Reynolds number at which the boundary layer over a plate turns turbulent
+const TRANSITION: f64 = 5e5;
+
+/// Fluid the faces are cooled or heated by
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fluid {
+    /// at atmospheric pressure, from 250 to 500 K
+    Air,
+    /// saturated liquid, from 275 to 373 K
+    Water,
+}
+
+/// Properties of a fluid at a single temperature
+#[derive(Clone, Copy, Debug)]
+pub struct Properties<T> {
+    /// conductivity `[W/(mK)]`
+    pub k: T,
+    /// kinematic viscosity `[m^2/s]`
+    pub nu: T,
+    /// Prandtl number
+    pub pr: T,
+    /// volumetric thermal expansion coefficient `[1/K]`
+    pub beta: T,
+}
+
+/// `[T, k, nu, Pr]` of air, with `beta = 1 / T` as for an ideal gas
+const AIR: [[f64; 4]; 6] = [
+    [250., 0.0223, 11.44e-6, 0.720],
+    [300., 0.0263, 15.89e-6, 0.707],
+    [350., 0.0300, 20.92e-6, 0.700],
+    [400., 0.0338, 26.41e-6, 0.690],
+    [450., 0.0373, 32.39e-6, 0.686],
+    [500., 0.0407, 38.79e-6, 0.684],
+];
+
+/// `[T, k, nu, Pr, beta]` of water
+const WATER: [[f64; 5]; 5] = [
+    [275., 0.574, 1.652e-6, 12.22, -32.7e-6],
+    [300., 0.613, 0.858e-6, 5.83, 276.1e-6],
+    [325., 0.645, 0.535e-6, 3.42, 455.0e-6],
+    [350., 0.668, 0.375e-6, 2.29, 624.2e-6],
+    [373., 0.680, 0.291e-6, 1.76, 750.1e-6],
+];
+
+/// Linear interpolation between the rows of `table` by their first column, held at the first
+/// and last rows outside of it
+fn interpolate<const N: usize>(table: &[[f64; N]], t: f64) -> [f64; N] {
+    let last = table.len() - 1;
+
+    if t <= table[0][0] {
+        return table[0];
+    }
+    if t >= table[last][0] {
+        return table[last];
+    }
+
+    let above = table.iter().position(|row| row[0] >= t).unwrap();
+    let (low, high) = (table[above - 1], table[above]);
+    let fraction = (t - low[0]) / (high[0] - low[0]);
+
+    std::array::from_fn(|column| low[column] + fraction * (high[column] - low[column]))
+}
+
+impl Fluid {
+    /// Properties at `t` `[K]`, those at the nearest end of the table outside of it
+    pub fn properties<T: Float>(self, t: T) -> Properties<T> {
+        let t = t.to_f64().unwrap();
+
+        let [k, nu, pr, beta] = match self {
+            Fluid::Air => {
+                let [t, k, nu, pr] = interpolate(&AIR, t);
+                [k, nu, pr, 1. / t]
+            }
+            Fluid::Water => {
+                let [_, k, nu, pr, beta] = interpolate(&WATER, t);
+                [k, nu, pr, beta]
+            }
+        };
+
+        Properties {
+            k: lit(k),
+            nu: lit(nu),
+            pr: lit(pr),
+            beta: lit(beta),
+        }
+    }
+}
+
+/// How the fluid moves over the face
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum Flow<T> {
+    /// driven by the buoyancy of the fluid the face heats or cools
+    Natural,
+    /// along the face at `velocity` `[m/s]`, laminar or turbulent depending on its Reynolds
+    /// number
+    Forced { velocity: T },
+}
+
+/// Which way a face points relative to gravity
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Vertical,
+    Up,
+    Down,
+}
+
+impl Orientation {
+    /// The face of the cube normal to `axis`, on its `high` side, with gravity along `-y`
+    pub fn of(axis: usize, high: bool) -> Self {
+        match (axis, high) {
+            (1, true) => Orientation::Up,
+            (1, false) => Orientation::Down,
+            _ => Orientation::Vertical,
+        }
+    }
+}
+
+/// Convection to a fluid at `t_inf` with `h` from a correlation
+///
+/// ```json
+/// {"type": "Correlated", "fluid": "Air", "t_inf": 293.0, "flow": {"type": "Natural"}}
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(bound(deserialize = "T: Float"))]
+pub struct Correlated<T> {
+    pub fluid: Fluid,
+    pub flow: Flow<T>,
+    pub t_inf: T,
+    /// coefficient the stencils use `[W/(m^2 K)]`, the first solve's when read from an input
+    /// file and the one the temperatures were solved with in a results file
+    #[serde(default = "first_guess")]
+    pub h: T,
+}
+
+fn first_guess<T: Float>() -> T {
+    lit(10.)
+}
+
+impl<T: Float> BoundaryCondition<T> for Correlated<T> {
+    fn lhs_constant(&self, _: &Information<T>, s: &SolverInfo<T>, area: T) -> T {
+        area * self.h * self.t_inf / (s.k * s.del)
+    }
+    fn rhs_constant(&self, _: &Information<T>, s: &SolverInfo<T>, area: T) -> T {
+        area * self.h / (s.k * s.del)
+    }
+}
+
+impl<T: Float> Correlated<T> {
+    /// Heat transfer coefficient `[W/(m^2 K)]` of a square face of side `len` `[m]` at
+    /// `surface` `[K]`
+    pub fn coefficient(&self, surface: T, orientation: Orientation, len: T) -> T {
+        let film = (surface + self.t_inf) / lit(2.);
+        let fluid = self.fluid.properties(film);
+        let third = lit::<T>(1. / 3.);
+
+        let (nusselt, length) = match self.flow {
+            Flow::Natural => {
+                // heat rising off the face, or sinking off a cold one
+                let rising = (surface > self.t_inf) == (orientation == Orientation::Up);
+                // area over perimeter for the horizontal faces
+                let length = match orientation {
+                    Orientation::Vertical => len,
+                    _ => len / lit(4.),
+                };
+
+                let alpha = fluid.nu / fluid.pr;
+                let rayleigh = lit::<T>(GRAVITY)
+                    * fluid.beta.abs()
+                    * (surface - self.t_inf).abs()
+                    * length.powi(3)
+                    / (fluid.nu * alpha);
+
+                let nusselt = match orientation {
+                    Orientation::Vertical => {
+                        let prandtl = (T::one() + (lit::<T>(0.492) / fluid.pr).powf(lit(9. / 16.)))
+                            .powf(lit(8. / 27.));
+                        let root = lit::<T>(0.825)
+                            + lit::<T>(0.387) * rayleigh.powf(lit(1. / 6.)) / prandtl;
+                        root * root
+                    }
+                    _ if !rising => lit::<T>(0.52) * rayleigh.powf(lit(0.2)),
+                    _ if rayleigh < lit(1e7) => lit::<T>(0.54) * rayleigh.powf(lit(0.25)),
+                    _ => lit::<T>(0.15) * rayleigh.powf(third),
+                };
+
+                (nusselt, length)
+            }
+            Flow::Forced { velocity } => {
+                let reynolds = velocity.abs() * len / fluid.nu;
+
+                let nusselt = if reynolds <= lit(TRANSITION) {
+                    lit::<T>(0.664) * reynolds.sqrt() * fluid.pr.powf(third)
+                } else {
+                    (lit::<T>(0.037) * reynolds.powf(lit(0.8)) - lit(871.)) * fluid.pr.powf(third)
+                };
+
+                (nusselt, len)
+            }
+        };
+
+        nusselt * fluid.k / length
+    }
+}
+
+impl<T: Float> fmt::Display for Correlated<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.flow {
+            Flow::Natural => write!(f, "natural convection to {:?}", self.fluid)?,
+            Flow::Forced { velocity } => {
+                write!(f, "forced convection to {:?} at {velocity} m/s", self.fluid)?
+            }
+        }
+
+        write!(f, ", h = {:.4}, t_inf = {}", self.h, self.t_inf)
+    }
+}
+
+/// Mean temperature of the nodes on the face of the cube normal to `axis`, on its `high`
+/// side, weighting those on its edges by half and skipping any outside of a geometry
+pub fn face_temperature<T: Float>(temps: &Matrix<T>, axis: usize, high: bool) -> T {
+    let shape = temps.shape();
+    let layer = if high { shape[axis] - 1 } else { 0 };
+    let weight = |index: usize, n: usize| {
+        if n > 1 && (index == 0 || index == n - 1) {
+            lit::<T>(0.5)
+        } else {
+            T::one()
+        }
+    };
+
+    let (total, area) = temps
+        .indexed_iter()
+        .filter(|&((x, y, z), temp)| [x, y, z][axis] == layer && !temp.is_nan())
+        .fold(
+            (T::zero(), T::zero()),
+            |(total, area), ((x, y, z), &temp)| {
+                let node = [x, y, z];
+                let w = (0..3)
+                    .filter(|&other| other != axis)
+                    .fold(T::one(), |w, other| w * weight(node[other], shape[other]));
+                (total + w * temp, area + w)
+            },
+        );
+
+    total / area
+}
+
+/// Evaluates the `h` of every correlated face of `conditions` at the face's mean temperature
+/// in `temps`, returning the largest relative change of any of them, `None` without any
+pub fn update<T: Float>(conditions: &mut Conditions<T>, temps: &Matrix<T>, len: T) -> Option<T> {
+    let mut change = None;
+
+    for (axis, high, condition) in [
+        (0, true, &mut conditions.right_boundary),
+        (0, false, &mut conditions.left_boundary),
+        (1, true, &mut conditions.top_boundary),
+        (1, false, &mut conditions.bot_boundary),
+        (2, true, &mut conditions.front_boundary),
+        (2, false, &mut conditions.back_boundary),
+    ] {
+        if let Condition::Correlated(correlated) = condition {
+            let surface = face_temperature(temps, axis, high);
+            let h = correlated.coefficient(surface, Orientation::of(axis, high), len);
+
+            // relative to the larger of the two, so a face at `t_inf` without any convection
+            // has settled rather than changed infinitely
+            let scale = h.abs().max(correlated.h.abs());
+            let relative = match scale > T::zero() {
+                true => (h - correlated.h).abs() / scale,
+                false => T::zero(),
+            };
+            change = Some(change.map_or(relative, |change: T| change.max(relative)));
+            correlated.h = h;
+        }
+    }
+
+    change
+}
+
+/// Solves with `solve` until the `h` of every correlated face of `conditions` has settled,
+/// returning the conditions of the last solve with its results
+///
+/// a single solve when no face is correlated, or once a solve is stopped. Each solve after
+/// the first is given the temperatures of the one before to start from. Each face of the cube
+/// is a plate of side `len`
+pub fn settle<T: Float>(
+    conditions: Conditions<T>,
+    len: T,
+    mut solve: impl FnMut(Conditions<T>, Option<&Matrix<T>>) -> SimulationResult<T>,
+) -> (Conditions<T>, SimulationResult<T>) {
+    let mut conditions = conditions;
+    let mut previous = None;
+    let mut pass = 1;
+
+    loop {
+        let result = solve(conditions, previous.as_ref());
+        let solved = conditions;
+
+        if result.stopped {
+            return (solved, result);
+        }
+
+        let temps = result.temperatures();
+        let Some(change) = update(&mut conditions, &temps, len) else {
+            return (solved, result);
+        };
+
+        tracing::info!(pass, change = change.to_f64(), "correlated coefficients");
+
+        if change < lit(TOLERANCE) {
+            return (solved, result);
+        }
+        if pass == PASSES {
+            tracing::warn!(
+                passes = PASSES,
+                change = change.to_f64(),
+                "the correlated coefficients did not settle"
+            );
+            return (solved, result);
+        }
+
+        previous = Some(temps);
+        pass += 1;
+    }
+}
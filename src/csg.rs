@@ -0,0 +1,129 @@
+//! Shapes built from primitives combined by union, intersection and difference, to describe
+//! the solids of a geometry in the input file
+//!
+//! a shape is rasterized onto the grid by testing the centre of every cell against it, so
+//! features smaller than a cell may be missed entirely
+
+use crate::prelude::*;
+use crate::probe::Axis;
+
+use serde::{Deserialize, Serialize};
+
+/// A region of space, serialized with its name in a `type` field, e.g.
+/// `{"type": "Sphere", "centre": [0.04, 0.04, 0.04], "radius": 0.02}`
+///
+/// every coordinate is in `[m]`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum Shape<T> {
+    /// aligned with the axes
+    Box {
+        min: [T; 3],
+        max: [T; 3],
+    },
+    Sphere {
+        centre: [T; 3],
+        radius: T,
+    },
+    /// the centres of its two ends, in any direction
+    Cylinder {
+        start: [T; 3],
+        end: [T; 3],
+        radius: T,
+    },
+    /// a polygon in the plane normal to `axis` swept between `from` and `to` along it
+    ///
+    /// the vertices are in the coordinates of the plane in the order `x, y, z` skipping
+    /// `axis`, and may wind either way
+    Extrusion {
+        axis: Axis,
+        points: Vec<[T; 2]>,
+        from: T,
+        to: T,
+    },
+    /// inside any of the shapes
+    Union {
+        shapes: Vec<Shape<T>>,
+    },
+    /// inside all of the shapes
+    Intersection {
+        shapes: Vec<Shape<T>>,
+    },
+    /// inside `shape` and none of `minus`
+    Difference {
+        shape: Box<Shape<T>>,
+        minus: Vec<Shape<T>>,
+    },
+}
+
+impl<T: Float> Shape<T> {
+    /// Whether `point` is strictly inside the shape
+    pub fn contains(&self, point: [T; 3]) -> bool {
+        match self {
+            Shape::Box { min, max } => {
+                (0..3).all(|axis| point[axis] > min[axis] && point[axis] < max[axis])
+            }
+            Shape::Sphere { centre, radius } => {
+                let offset = sub(point, *centre);
+                dot(offset, offset) < *radius * *radius
+            }
+            Shape::Cylinder { start, end, radius } => {
+                let along = sub(*end, *start);
+                let offset = sub(point, *start);
+                let length2 = dot(along, along);
+                let projection = dot(offset, along);
+
+                if projection <= T::zero() || projection >= length2 {
+                    return false;
+                }
+
+                // distance from the axis by Pythagoras, scaled by the length squared
+                let radial2 = dot(offset, offset) * length2 - projection * projection;
+                radial2 < *radius * *radius * length2
+            }
+            Shape::Extrusion {
+                axis,
+                points,
+                from,
+                to,
+            } => {
+                let height = point[axis.index()];
+                let [u, v] = axis.in_plane().map(|axis| point[axis]);
+
+                height > from.min(*to) && height < from.max(*to) && inside_polygon(points, u, v)
+            }
+            Shape::Union { shapes } => shapes.iter().any(|shape| shape.contains(point)),
+            Shape::Intersection { shapes } => shapes.iter().all(|shape| shape.contains(point)),
+            Shape::Difference { shape, minus } => {
+                shape.contains(point) && !minus.iter().any(|shape| shape.contains(point))
+            }
+        }
+    }
+}
+
+/// Even-odd test of a ray from `(u, v)` towards positive `u` against the edges of `points`
+fn inside_polygon<T: Float>(points: &[[T; 2]], u: T, v: T) -> bool {
+    let mut inside = false;
+
+    for (index, &[u1, v1]) in points.iter().enumerate() {
+        let [u2, v2] = points[(index + 1) % points.len()];
+
+        // half open in `v`, so a ray through a vertex counts it once
+        if (v1 > v) != (v2 > v) {
+            let crossing = u1 + (v - v1) / (v2 - v1) * (u2 - u1);
+            if crossing > u {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+fn sub<T: Float>(a: [T; 3], b: [T; 3]) -> [T; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot<T: Float>(a: [T; 3], b: [T; 3]) -> T {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
@@ -0,0 +1,290 @@
+//! Cylinders, hollow or solid, around the full circle, as a sector, or axisymmetric on a
+//! single `(r, z)` plane
+//!
+//! the nodes are indexed `(r, θ, z)` and every node is the centre of a control volume between
+//! the half way points to its neighbours, so the heat flowing between two nodes is the same
+//! in both of their balances. The six conditions of the input are reused: `left` on the
+//! inner surface, `right` on the outer, `bot` and `top` on the faces at `θ = 0` and
+//! `θ = sector`, and `back` and `front` on the ends at `z = 0` and `z = length`
+//!
+//! the nodes at `r = 0` of a solid cylinder all lie on its axis. Each is the corner of a
+//! thin wedge of the disc around the axis, and at every step they all take the update of the
+//! whole disc, the wedges' updates weighted by their share of it
+
+use crate::prelude::*;
+use crate::setup::BoundaryConditions;
+use crate::stencil::{Balance, Coefficients, Nodes, Stencil, Sweep};
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+
+/// Size and shape of a cylindrical domain, given in place of the cube's `len`
+///
+/// ```json
+/// "cylinder": {"inner_radius": 0.01, "outer_radius": 0.05, "length": 0.2, "axisymmetric": true}
+/// ```
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(bound(deserialize = "T: Float"))]
+pub struct Cylinder<T> {
+    /// `[m]`, zero for a solid cylinder whose axis is part of the domain
+    #[serde(default = "T::zero")]
+    pub inner_radius: T,
+    /// `[m]`
+    pub outer_radius: T,
+    /// `[m]`
+    pub length: T,
+    /// angle of a sector `[rad]`, the full circle when not given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sector: Option<T>,
+    /// the temperatures do not vary with `θ`, so only a single `(r, z)` plane is solved
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub axisymmetric: bool,
+}
+
+/// The part of a control volume inside the cylinder
+struct Extent<T> {
+    /// inner and outer radius `[m]`
+    radial: [T; 2],
+    /// `[rad]`
+    angle: T,
+    /// `[m]`
+    height: T,
+}
+
+impl<T: Float> Cylinder<T> {
+    /// Nodes along `r`, `θ` and `z`, `divisions` along each but a single one around an
+    /// axisymmetric cylinder
+    pub fn shape(&self, divisions: usize) -> [usize; 3] {
+        let around = if self.axisymmetric { 1 } else { divisions };
+        [divisions, around, divisions]
+    }
+
+    /// Whether the last node around the cylinder neighbours the first
+    pub fn is_full(&self) -> bool {
+        self.sector.is_none() && !self.axisymmetric
+    }
+
+    /// Distance between the nodes along `r` `[m]`, `θ` `[rad]` and `z` `[m]`
+    pub fn spacing(&self, divisions: usize) -> [T; 3] {
+        let steps = lit::<T>(divisions as f64 - 1.);
+        let around = match (self.axisymmetric, self.sector) {
+            (true, _) => lit(TAU),
+            (false, Some(sector)) => sector / steps,
+            (false, None) => lit::<T>(TAU) / lit(divisions as f64),
+        };
+
+        [
+            (self.outer_radius - self.inner_radius) / steps,
+            around,
+            self.length / steps,
+        ]
+    }
+
+    /// `(r, θ, z)` of `node`
+    pub fn position(&self, node: [usize; 3], divisions: usize) -> [T; 3] {
+        let [dr, dtheta, dz] = self.spacing(divisions);
+        let [i, j, k] = node.map(|index| lit::<T>(index as f64));
+
+        [self.inner_radius + i * dr, j * dtheta, k * dz]
+    }
+
+    fn extent(&self, node: [usize; 3], divisions: usize) -> Extent<T> {
+        let [dr, dtheta, dz] = self.spacing(divisions);
+        let [r, _, _] = self.position(node, divisions);
+        let half = lit::<T>(0.5);
+        let end = divisions - 1;
+
+        let on_end = |index: usize| index == 0 || index == end;
+        let angle = match self.sector {
+            Some(_) if !self.axisymmetric && on_end(node[1]) => dtheta * half,
+            _ => dtheta,
+        };
+        let height = if on_end(node[2]) { dz * half } else { dz };
+
+        Extent {
+            radial: [
+                (r - dr * half).max(self.inner_radius),
+                (r + dr * half).min(self.outer_radius),
+            ],
+            angle,
+            height,
+        }
+    }
+
+    /// Volume of the control volume of `node` `[m^3]`
+    pub fn volume(&self, node: [usize; 3], divisions: usize) -> T {
+        let Extent {
+            radial: [inner, outer],
+            angle,
+            height,
+        } = self.extent(node, divisions);
+
+        angle * (outer * outer - inner * inner) * lit(0.5) * height
+    }
+
+    /// Area of the face of the control volume of `node` on the surface of the cylinder
+    /// normal to `axis`, on its `high` side `[m^2]`
+    ///
+    /// zero where the node is not on that surface, or the surface does not exist: the axis of
+    /// a solid cylinder and the faces around a full or axisymmetric one
+    pub fn face_area(&self, node: [usize; 3], divisions: usize, axis: usize, high: bool) -> T {
+        let end = divisions - 1;
+        let Extent {
+            radial: [inner, outer],
+            angle,
+            height,
+        } = self.extent(node, divisions);
+
+        let at = |index: usize| if high { index == end } else { index == 0 };
+
+        match axis {
+            0 if at(node[0]) => {
+                let r = if high {
+                    self.outer_radius
+                } else {
+                    self.inner_radius
+                };
+                r * angle * height
+            }
+            1 if self.sector.is_some() && !self.axisymmetric && at(node[1]) => {
+                (outer - inner) * height
+            }
+            2 if at(node[2]) => angle * (outer * outer - inner * inner) * lit(0.5),
+            _ => T::zero(),
+        }
+    }
+
+    /// Conductance to each neighbour of `node` divided by the conductivity `[m]`, ordered like
+    /// the weights of a stencil and zero where there is none
+    fn conductances(&self, node: [usize; 3], divisions: usize) -> [T; 6] {
+        let [dr, dtheta, dz] = self.spacing(divisions);
+        let [r, _, _] = self.position(node, divisions);
+        let Extent {
+            radial: [inner, outer],
+            angle,
+            height,
+        } = self.extent(node, divisions);
+        let end = divisions - 1;
+        let half = lit::<T>(0.5);
+
+        let mut conductances = [T::zero(); 6];
+
+        if node[0] > 0 {
+            conductances[0] = (r - dr * half) * angle * height / dr;
+        }
+        if node[0] < end {
+            conductances[1] = (r + dr * half) * angle * height / dr;
+        }
+
+        // the axis is joined to the rest of the disc through the mean of its updates instead
+        let around = (outer - inner) * height / (r * dtheta);
+        if !self.axisymmetric && r > T::zero() {
+            if self.is_full() || node[1] > 0 {
+                conductances[2] = around;
+            }
+            if self.is_full() || node[1] < end {
+                conductances[3] = around;
+            }
+        }
+
+        let disc = angle * (outer * outer - inner * inner) * half / dz;
+        if node[2] > 0 {
+            conductances[4] = disc;
+        }
+        if node[2] < end {
+            conductances[5] = disc;
+        }
+
+        conductances
+    }
+}
+
+/// Coefficients of the cylinder with `divisions` nodes along `r`, `θ` and `z`
+///
+/// `s.del` only scales the terms of each balance and may be any length
+pub fn coefficients<T, A, B, C, D, E, F>(
+    cylinder: &Cylinder<T>,
+    conditions: &BoundaryConditions<A, B, C, D, E, F>,
+    s: &SolverInfo<T>,
+    divisions: usize,
+    sweep: Sweep,
+) -> Coefficients<T>
+where
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
+    D: BoundaryCondition<T>,
+    E: BoundaryCondition<T>,
+    F: BoundaryCondition<T>,
+{
+    let walls = &conditions.walls;
+    let outer: [&dyn BoundaryCondition<T>; 6] = [
+        &walls.left.left_boundary,
+        &walls.right.right_boundary,
+        &walls.bot.bot_boundary,
+        &walls.top.top_boundary,
+        &walls.back.back_boundary,
+        &walls.front.front_boundary,
+    ];
+
+    let shape = cylinder.shape(divisions);
+    let end = divisions - 1;
+    // the position along `θ` and `z` only matters at their ends
+    let side = |index: usize| match index {
+        0 => 0,
+        i if i == end => 2,
+        _ => 1,
+    };
+
+    let mut stencils = Vec::new();
+    let mut diagonals = Vec::new();
+    let mut shared: HashMap<[usize; 3], u32> = HashMap::new();
+
+    let index = ndarray::Array3::from_shape_fn(shape, |(i, j, k)| {
+        let node = [i, j, k];
+        let around = if cylinder.is_full() { 1 } else { side(j) };
+
+        *shared.entry([i, around, side(k)]).or_insert_with(|| {
+            let faces = (0..6)
+                .map(|direction| {
+                    let area =
+                        cylinder.face_area(node, divisions, direction / 2, direction % 2 == 1);
+                    (area, outer[direction])
+                })
+                .filter(|(area, _)| *area > T::zero())
+                .collect();
+
+            let balance = Balance {
+                conductances: cylinder.conductances(node, divisions),
+                volume: cylinder.volume(node, divisions),
+                faces,
+            };
+
+            stencils.push(Stencil::new(&balance, s));
+            diagonals.push(balance.diagonal(s));
+            (stencils.len() - 1) as u32
+        })
+    });
+
+    // each wedge of the axis counts by how much of the disc's balance it holds
+    let axis = (cylinder.inner_radius.is_zero() && shape[1] > 1).then(|| {
+        let weights: Vec<T> = (0..shape[1])
+            .map(|j| diagonals[index[[0, j, 1.min(end)]] as usize])
+            .collect();
+        let total = weights.iter().fold(T::zero(), |sum, &w| sum + w);
+
+        weights.into_iter().map(|w| w / total).collect()
+    });
+
+    Coefficients::from_nodes(
+        Nodes {
+            stencils,
+            index,
+            periodic: [false, cylinder.is_full(), false],
+            axis,
+        },
+        sweep,
+    )
+}
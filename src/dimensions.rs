@@ -0,0 +1,276 @@
+//! Walls solved in one dimension and cross-sections in two, on the same cube without its
+//! nodes along the axes that are left out
+//!
+//! a wall is solved along `x` and a cross-section over `(x, y)`. Nothing varies along the
+//! missing axes, so the nodes along them are a single layer that stands for the whole length
+//! of the cube, and the results are those of a 3D solve with the faces normal to the missing
+//! axes insulated. The conditions of those faces are not used
+
+use crate::prelude::*;
+use crate::setup::BoundaryConditions;
+use crate::stencil::{Balance, Coefficients, Nodes, Stencil, Sweep};
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Number of axes solved, written as `1`, `2` or `3` in the input file
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(try_from = "usize", into = "usize")]
+pub enum Dimensions {
+    /// along `x`
+    One,
+    /// over `(x, y)`
+    Two,
+    #[default]
+    Three,
+}
+
+impl TryFrom<usize> for Dimensions {
+    type Error = String;
+
+    fn try_from(dimensions: usize) -> Result<Self, Self::Error> {
+        match dimensions {
+            1 => Ok(Dimensions::One),
+            2 => Ok(Dimensions::Two),
+            3 => Ok(Dimensions::Three),
+            _ => Err(format!("{dimensions} dimensions, expected 1, 2 or 3")),
+        }
+    }
+}
+
+impl From<Dimensions> for usize {
+    fn from(dimensions: Dimensions) -> usize {
+        match dimensions {
+            Dimensions::One => 1,
+            Dimensions::Two => 2,
+            Dimensions::Three => 3,
+        }
+    }
+}
+
+impl fmt::Display for Dimensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}D", usize::from(*self))
+    }
+}
+
+impl Dimensions {
+    /// Dimensions of results with nodes along each axis given by `shape`, such as those of
+    /// `SimulationResult::shape`
+    pub fn of(shape: [usize; 3]) -> Self {
+        match shape {
+            [_, 1, 1] => Dimensions::One,
+            [_, _, 1] => Dimensions::Two,
+            _ => Dimensions::Three,
+        }
+    }
+
+    /// Whether each of `x`, `y` and `z` is solved
+    pub fn solved(self) -> [bool; 3] {
+        let count = usize::from(self);
+        [0, 1, 2].map(|axis| axis < count)
+    }
+
+    /// `divisions` nodes along every solved axis and a single one along the others
+    pub fn shape(self, divisions: usize) -> [usize; 3] {
+        self.solved()
+            .map(|solved| if solved { divisions } else { 1 })
+    }
+
+    /// Size of the control volume of `node` along each axis `[m]`, half a cell on the faces
+    /// of the cube and its full length along the missing axes
+    fn extent<T: Float>(self, node: [usize; 3], divisions: usize, del: T) -> [T; 3] {
+        let end = divisions - 1;
+        let solved = self.solved();
+
+        [0, 1, 2].map(|axis| match (solved[axis], node[axis]) {
+            (false, _) => del * lit(end as f64),
+            (true, i) if i == 0 || i == end => del * lit(0.5),
+            (true, _) => del,
+        })
+    }
+
+    /// Volume of the control volume of `node` `[m^3]`
+    pub fn volume<T: Float>(self, node: [usize; 3], divisions: usize, del: T) -> T {
+        let [x, y, z] = self.extent(node, divisions, del);
+        x * y * z
+    }
+
+    /// Area of the face of the control volume of `node` on the face of the cube normal to
+    /// `axis`, on its `high` side `[m^2]`
+    ///
+    /// zero where the node is not on that face, and on the faces normal to the missing axes
+    pub fn face_area<T: Float>(
+        self,
+        node: [usize; 3],
+        divisions: usize,
+        del: T,
+        axis: usize,
+        high: bool,
+    ) -> T {
+        let layer = if high { divisions - 1 } else { 0 };
+
+        if !self.solved()[axis] || node[axis] != layer {
+            return T::zero();
+        }
+
+        let extent = self.extent(node, divisions, del);
+        (0..3)
+            .filter(|&other| other != axis)
+            .fold(T::one(), |area, other| area * extent[other])
+    }
+
+    /// Conductance to each neighbour of `node` divided by the conductivity `[m]`, ordered like
+    /// the weights of a stencil and zero where there is none
+    fn conductances<T: Float>(self, node: [usize; 3], divisions: usize, del: T) -> [T; 6] {
+        let end = divisions - 1;
+        let solved = self.solved();
+        let extent = self.extent(node, divisions, del);
+
+        let mut conductances = [T::zero(); 6];
+
+        for axis in (0..3).filter(|&axis| solved[axis]) {
+            let area = (0..3)
+                .filter(|&other| other != axis)
+                .fold(T::one(), |area, other| area * extent[other]);
+
+            if node[axis] > 0 {
+                conductances[2 * axis] = area / del;
+            }
+            if node[axis] < end {
+                conductances[2 * axis + 1] = area / del;
+            }
+        }
+
+        conductances
+    }
+}
+
+/// Coefficients of the cube with `divisions` nodes along each axis that `dimensions` solves
+///
+/// every node has its own control volume, as in the cylindrical grid, so three dimensions
+/// give the same temperatures as `Coefficients::new` through the slower sweep of single nodes
+pub fn coefficients<T, A, B, C, D, E, F>(
+    dimensions: Dimensions,
+    conditions: &BoundaryConditions<A, B, C, D, E, F>,
+    s: &SolverInfo<T>,
+    divisions: usize,
+    sweep: Sweep,
+) -> Coefficients<T>
+where
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
+    D: BoundaryCondition<T>,
+    E: BoundaryCondition<T>,
+    F: BoundaryCondition<T>,
+{
+    let walls = &conditions.walls;
+    let outer: [&dyn BoundaryCondition<T>; 6] = [
+        &walls.left.left_boundary,
+        &walls.right.right_boundary,
+        &walls.bot.bot_boundary,
+        &walls.top.top_boundary,
+        &walls.back.back_boundary,
+        &walls.front.front_boundary,
+    ];
+
+    let end = divisions - 1;
+    // only whether a node is on either face along each axis matters
+    let side = |index: usize| match index {
+        0 => 0,
+        i if i == end => 2,
+        _ => 1,
+    };
+
+    let mut stencils = Vec::new();
+    let mut shared: HashMap<[usize; 3], u32> = HashMap::new();
+
+    let index = ndarray::Array3::from_shape_fn(dimensions.shape(divisions), |(i, j, k)| {
+        let node = [i, j, k];
+
+        *shared.entry(node.map(side)).or_insert_with(|| {
+            let faces = (0..6)
+                .map(|direction| {
+                    let area = dimensions.face_area(
+                        node,
+                        divisions,
+                        s.del,
+                        direction / 2,
+                        direction % 2 == 1,
+                    );
+                    (area, outer[direction])
+                })
+                .filter(|(area, _)| *area > T::zero())
+                .collect();
+
+            let balance = Balance {
+                conductances: dimensions.conductances(node, divisions, s.del),
+                volume: dimensions.volume(node, divisions, s.del),
+                faces,
+            };
+
+            stencils.push(Stencil::new(&balance, s));
+            (stencils.len() - 1) as u32
+        })
+    });
+
+    Coefficients::from_nodes(
+        Nodes {
+            stencils,
+            index,
+            periodic: [false; 3],
+            axis: None,
+        },
+        sweep,
+    )
+}
+
+/// Largest difference between the temperatures of a reduced solve and a 3D one of the same
+/// cube
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct Comparison<T> {
+    /// `[K]`
+    pub max: T,
+    /// node of the 3D results where the difference is largest
+    pub index: [usize; 3],
+    /// mean of the absolute difference over every node of the 3D results `[K]`
+    pub mean: T,
+}
+
+/// Compares every node of `cube` to the node of `reduced` with the same index along the
+/// solved axes, so every layer of the cube along the missing axes is checked against the
+/// single one solved
+///
+/// `None` when the shapes do not match along the solved axes
+pub fn compare<T: Float>(reduced: &Matrix<T>, cube: &Matrix<T>) -> Option<Comparison<T>> {
+    let shape = reduced.shape();
+    let matches = (0..3).all(|axis| shape[axis] == 1 || shape[axis] == cube.shape()[axis]);
+
+    if !matches {
+        return None;
+    }
+
+    let mut comparison = Comparison {
+        max: T::zero(),
+        index: [0; 3],
+        mean: T::zero(),
+    };
+
+    for ((x, y, z), &temp) in cube.indexed_iter() {
+        let node = [x, y, z];
+        let solved = [0, 1, 2].map(|axis| if shape[axis] == 1 { 0 } else { node[axis] });
+        let difference = (temp - reduced[solved]).abs();
+
+        if difference > comparison.max {
+            comparison.max = difference;
+            comparison.index = node;
+        }
+        comparison.mean = comparison.mean + difference;
+    }
+
+    comparison.mean = comparison.mean / lit(cube.len() as f64);
+    Some(comparison)
+}
@@ -1,32 +1,92 @@
+use crate::conditions::Condition;
 use crate::prelude::*;
 use std::fmt;
 
-use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
 
-#[derive(Serialize)]
-pub(crate) struct SaveFile<A, B, C, D, E, F>
-where
-    A: BoundaryCondition + Serialize + Clone,
-    B: BoundaryCondition + Serialize + Clone,
-    C: BoundaryCondition + Serialize + Clone,
-    D: BoundaryCondition + Serialize + Clone,
-    E: BoundaryCondition + Serialize + Clone,
-    F: BoundaryCondition + Serialize + Clone,
-{
-    pub(crate) conditions: crate::setup::SetupConditions<A, B, C, D, E, F>,
-    pub(crate) simulation: SimulationResult,
-    pub(crate) solver_params: crate::SolverParams,
+/// Everything written to a results file
+///
+/// also read back from old files, so fields added since the first version are optional
+#[derive(Serialize, Deserialize)]
+#[serde(
+    bound(deserialize = "T: Float, A: Deserialize<'de>, B: Deserialize<'de>, \
+    C: Deserialize<'de>, D: Deserialize<'de>, E: Deserialize<'de>, F: Deserialize<'de>")
+)]
+pub struct SaveFile<T, A, B, C, D, E, F> {
+    pub conditions: crate::setup::SetupConditions<A, B, C, D, E, F>,
+    pub simulation: SimulationResult<T>,
+    pub solver_params: crate::SolverParams<T>,
+    /// double in results saved before the field was added
+    #[serde(default)]
+    pub precision: Precision,
+    /// `[W/(mK)]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thermal_conductivity: Option<T>,
+    /// `[W/m^3]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub energy_generation: Option<T>,
+    /// lines and planes through the final temperatures
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub samples: Option<crate::probe::Samples<T>>,
+    /// gradient and heat flux of the final temperatures
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heat_flux: Option<crate::flux::FluxField<T>>,
+    /// voids cut out of the cube, whose nodes are saved as `null`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geometry: Option<crate::geometry::Geometry<T>>,
+    /// the cylinder solved in place of the cube, whose nodes are indexed `(r, θ, z)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cylinder: Option<crate::cylindrical::Cylinder<T>>,
+    /// conductivities along the axes of the material, which replace `thermal_conductivity`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orthotropic: Option<crate::anisotropy::Orthotropic<T>>,
+    /// the final temperatures mirrored across the symmetry faces, as the whole part
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mirrored: Option<crate::symmetry::Mirrored<T>>,
 }
 
-#[derive(Clone, Serialize)]
-pub(crate) struct SimulationResult {
-    pub(crate) step_data: Vec<StepData>,
-    pub(crate) error_decay: ErrorData,
+/// A results file with the boundary conditions chosen at run time, as written by the binary
+pub type Results<T> =
+    SaveFile<T, Condition<T>, Condition<T>, Condition<T>, Condition<T>, Condition<T>, Condition<T>>;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound(deserialize = "T: Float"))]
+pub struct SimulationResult<T> {
+    pub step_data: Vec<StepData<T>>,
+    pub error_decay: ErrorData<T>,
     // the number of sides in each dimension
-    pub(crate) size: usize,
-    pub(crate) num_steps: usize,
+    pub size: usize,
+    /// nodes along each axis of a grid that is not a cube of `size`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<[usize; 3]>,
+    pub num_steps: usize,
+    /// temperatures at each probe point after every step
+    #[serde(skip_serializing_if = "Vec::is_empty", default = "Vec::new")]
+    pub probes: Vec<ProbeHistory<T>>,
+    /// error between the last two steps, which `error_decay` only has if it fell on an
+    /// `error_steps` boundary
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub final_error: Option<T>,
+    /// time spent iterating `[s]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wall_time: Option<f64>,
+    /// an observer stopped the solve before the error reached `error_epsilon`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stopped: bool,
+}
+impl<T: Float> SimulationResult<T> {
+    /// Temperatures of the last recorded step
+    pub fn temperatures(&self) -> Matrix<T> {
+        self.step_data[self.step_data.len() - 1].temperatures(self.shape())
+    }
+
+    /// Nodes along each axis
+    pub fn shape(&self) -> [usize; 3] {
+        self.shape.unwrap_or([self.size; 3])
+    }
 }
-impl fmt::Debug for SimulationResult {
+impl<T: Float> fmt::Debug for SimulationResult<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let last = &self.step_data[&self.step_data.len() - 1];
         let min = min_temp(last.data.iter());
@@ -41,26 +101,86 @@ impl fmt::Debug for SimulationResult {
     }
 }
 
-#[derive(Clone, Serialize, Debug)]
-pub(crate) struct StepData {
-    pub(crate) step: usize,
-    pub(crate) data: Vec<T>,
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(bound(deserialize = "T: Float"))]
+pub struct StepData<T> {
+    pub step: usize,
+    #[serde(deserialize_with = "nullable")]
+    pub data: Vec<T>,
+}
+impl<T: Float> StepData<T> {
+    /// Temperatures of a grid of `shape` nodes
+    pub fn temperatures(&self, shape: [usize; 3]) -> Matrix<T> {
+        Matrix::from_shape_vec(shape, self.data.clone()).expect("steps hold every node")
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(bound(deserialize = "T: Float"))]
+pub struct ProbeHistory<T> {
+    pub point: [T; 3],
+    /// `temperatures[i]` is the temperature after step `i`
+    #[serde(deserialize_with = "nullable")]
+    pub temperatures: Vec<T>,
 }
 
-#[derive(Clone, Serialize, Debug)]
-pub(crate) struct ErrorData {
-    pub(crate) error_type: ErrorType,
-    pub(crate) data: Vec<T>,
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ErrorData<T> {
+    pub error_type: ErrorType,
+    pub data: Vec<T>,
 }
-impl ErrorData {
-    pub(crate) fn add_error(&mut self, new_point: T) {
+impl<T: Float> ErrorData<T> {
+    pub fn add_error(&mut self, new_point: T) {
         self.data.push(new_point)
     }
 }
 
-#[derive(Clone, Serialize, Debug)]
-pub(crate) enum ErrorType {
+#[allow(clippy::enum_variant_names)]
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default)]
+pub enum ErrorType {
     InfinityNorm,
     L1Norm,
+    #[default]
     L2Norm,
 }
+
+/// Values serde_json may have written as `null`, which it does for the NaN temperatures of
+/// nodes outside of a masked geometry
+pub(crate) trait Nullable: Sized {
+    type Raw: DeserializeOwned;
+
+    fn from_raw(raw: Self::Raw) -> Self;
+}
+
+impl<T: Float> Nullable for T {
+    type Raw = Option<T>;
+
+    fn from_raw(raw: Self::Raw) -> Self {
+        raw.unwrap_or_else(T::nan)
+    }
+}
+
+impl<V: Nullable> Nullable for Vec<V> {
+    type Raw = Vec<V::Raw>;
+
+    fn from_raw(raw: Self::Raw) -> Self {
+        raw.into_iter().map(V::from_raw).collect()
+    }
+}
+
+impl<V: Nullable> Nullable for [V; 3] {
+    type Raw = [V::Raw; 3];
+
+    fn from_raw(raw: Self::Raw) -> Self {
+        raw.map(V::from_raw)
+    }
+}
+
+/// Reads `null` back as NaN, for `#[serde(deserialize_with = "...")]`
+pub(crate) fn nullable<'de, D, V>(deserializer: D) -> Result<V, D::Error>
+where
+    D: Deserializer<'de>,
+    V: Nullable,
+{
+    V::Raw::deserialize(deserializer).map(V::from_raw)
+}
@@ -1,218 +1,234 @@
 use crate::prelude::*;
 
-pub struct RightTop<V: BoundaryCondition, K: BoundaryCondition> {
+pub struct RightTop<V, K> {
     pub right_boundary: V,
     pub top_boundary: K,
 }
-impl<V, K> CalculateTemperature for RightTop<V, K>
+impl<T, V, K> CalculateTemperature<T> for RightTop<V, K>
 where
-    V: BoundaryCondition,
-    K: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
+    K: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.right_boundary, self.top_boundary);
 
-        let area = s.del2() / 2.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(2.);
 
-        let m = ((info.i_back + info.j_back) / 2.)
-            + ((info.k_back + info.k_front) / 4.)
+        let m = ((ax * info.i_back + ay * info.j_back) / lit(2.))
+            + (az * (info.k_back + info.k_front) / lit(4.))
             + self.right_boundary.lhs_constant(&info, s, area)
             + self.top_boundary.lhs_constant(&info, s, area);
 
-        let div = 3. / 2.
+        let div = (ax + ay + az) / lit(2.)
             + self.right_boundary.rhs_constant(&info, s, area)
             + self.top_boundary.rhs_constant(&info, s, area);
 
-        (m + (s.q_dot * s.del2() / (s.k * 4.))) / div
+        (m + (s.q_dot * s.del2() / (s.k * lit(4.)))) / div
     }
 }
 
-pub struct LeftTop<V: BoundaryCondition, K: BoundaryCondition> {
+pub struct LeftTop<V, K> {
     pub left_boundary: V,
     pub top_boundary: K,
 }
-impl<V, K> CalculateTemperature for LeftTop<V, K>
+impl<T, V, K> CalculateTemperature<T> for LeftTop<V, K>
 where
-    V: BoundaryCondition,
-    K: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
+    K: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.left_boundary, self.top_boundary);
 
-        let area = s.del2() / 2.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(2.);
 
-        let m = ((info.i_front + info.j_back) / 2.)
-            + ((info.k_back + info.k_front) / 4.)
+        let m = ((ax * info.i_front + ay * info.j_back) / lit(2.))
+            + (az * (info.k_back + info.k_front) / lit(4.))
             + self.left_boundary.lhs_constant(&info, s, area)
             + self.top_boundary.lhs_constant(&info, s, area);
 
-        let div = 3. / 2.
+        let div = (ax + ay + az) / lit(2.)
             + self.left_boundary.rhs_constant(&info, s, area)
             + self.top_boundary.rhs_constant(&info, s, area);
 
-        (m + (s.q_dot * s.del2() / (s.k * 4.))) / div
+        (m + (s.q_dot * s.del2() / (s.k * lit(4.)))) / div
     }
 }
 
-pub struct LeftBot<V: BoundaryCondition, K: BoundaryCondition> {
+pub struct LeftBot<V, K> {
     pub left_boundary: V,
     pub bot_boundary: K,
 }
-impl<V, K> CalculateTemperature for LeftBot<V, K>
+impl<T, V, K> CalculateTemperature<T> for LeftBot<V, K>
 where
-    V: BoundaryCondition,
-    K: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
+    K: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.left_boundary, self.bot_boundary);
 
-        let area = s.del2() / 2.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(2.);
 
-        let m = ((info.i_front + info.j_front) / 2.)
-            + ((info.k_back + info.k_front) / 4.)
+        let m = ((ax * info.i_front + ay * info.j_front) / lit(2.))
+            + (az * (info.k_back + info.k_front) / lit(4.))
             + self.left_boundary.lhs_constant(&info, s, area)
             + self.bot_boundary.lhs_constant(&info, s, area);
 
-        let div = 3. / 2.
+        let div = (ax + ay + az) / lit(2.)
             + self.left_boundary.rhs_constant(&info, s, area)
             + self.bot_boundary.rhs_constant(&info, s, area);
 
-        (m + (s.q_dot * s.del2() / (s.k * 4.))) / div
+        (m + (s.q_dot * s.del2() / (s.k * lit(4.)))) / div
     }
 }
 
-pub struct RightBot<V: BoundaryCondition, K: BoundaryCondition> {
+pub struct RightBot<V, K> {
     pub right_boundary: V,
     pub bot_boundary: K,
 }
-impl<V, K> CalculateTemperature for RightBot<V, K>
+impl<T, V, K> CalculateTemperature<T> for RightBot<V, K>
 where
-    V: BoundaryCondition,
-    K: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
+    K: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.right_boundary, self.bot_boundary);
 
-        let area = s.del2() / 2.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(2.);
 
-        let m = ((info.i_back + info.j_front) / 2.)
-            + ((info.k_back + info.k_front) / 4.)
+        let m = ((ax * info.i_back + ay * info.j_front) / lit(2.))
+            + (az * (info.k_back + info.k_front) / lit(4.))
             + self.right_boundary.lhs_constant(&info, s, area)
             + self.bot_boundary.lhs_constant(&info, s, area);
 
-        let div = 3. / 2.
+        let div = (ax + ay + az) / lit(2.)
             + self.right_boundary.rhs_constant(&info, s, area)
             + self.bot_boundary.rhs_constant(&info, s, area);
 
-        (m + (s.q_dot * s.del2() / (s.k * 4.))) / div
+        (m + (s.q_dot * s.del2() / (s.k * lit(4.)))) / div
     }
 }
 
-pub struct FrontTop<V: BoundaryCondition, K: BoundaryCondition> {
+pub struct FrontTop<V, K> {
     pub front_boundary: V,
     pub top_boundary: K,
 }
-impl<V, K> CalculateTemperature for FrontTop<V, K>
+impl<T, V, K> CalculateTemperature<T> for FrontTop<V, K>
 where
-    V: BoundaryCondition,
-    K: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
+    K: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.front_boundary, self.top_boundary);
 
-        let area = s.del2() / 2.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(2.);
 
-        let m = ((info.k_back + info.j_back) / 2.)
-            + ((info.i_front + info.i_back) / 4.)
+        let m = ((az * info.k_back + ay * info.j_back) / lit(2.))
+            + (ax * (info.i_front + info.i_back) / lit(4.))
             + self.front_boundary.lhs_constant(&info, s, area)
             + self.top_boundary.lhs_constant(&info, s, area);
 
-        let div = 3. / 2.
+        let div = (ax + ay + az) / lit(2.)
             + self.top_boundary.rhs_constant(&info, s, area)
             + self.front_boundary.rhs_constant(&info, s, area);
 
-        (m + (s.q_dot * s.del2() / (s.k * 4.))) / div
+        (m + (s.q_dot * s.del2() / (s.k * lit(4.)))) / div
     }
 }
 
-pub struct BackTop<V: BoundaryCondition, K: BoundaryCondition> {
+pub struct BackTop<V, K> {
     pub back_boundary: V,
     pub top_boundary: K,
 }
-impl<V, K> CalculateTemperature for BackTop<V, K>
+impl<T, V, K> CalculateTemperature<T> for BackTop<V, K>
 where
-    V: BoundaryCondition,
-    K: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
+    K: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.back_boundary, self.top_boundary);
 
-        let area = s.del2() / 2.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(2.);
 
-        let m = ((info.k_front + info.j_back) / 2.)
-            + ((info.i_front + info.i_back) / 4.)
+        let m = ((az * info.k_front + ay * info.j_back) / lit(2.))
+            + (ax * (info.i_front + info.i_back) / lit(4.))
             + self.back_boundary.lhs_constant(&info, s, area)
             + self.top_boundary.lhs_constant(&info, s, area);
 
-        let div = 3. / 2.
+        let div = (ax + ay + az) / lit(2.)
             + self.top_boundary.rhs_constant(&info, s, area)
             + self.back_boundary.rhs_constant(&info, s, area);
 
-        (m + (s.q_dot * s.del2() / (s.k * 4.))) / div
+        (m + (s.q_dot * s.del2() / (s.k * lit(4.)))) / div
     }
 }
 
-pub struct BackBot<V: BoundaryCondition, K: BoundaryCondition> {
+pub struct BackBot<V, K> {
     pub back_boundary: V,
     pub bot_boundary: K,
 }
-impl<V, K> CalculateTemperature for BackBot<V, K>
+impl<T, V, K> CalculateTemperature<T> for BackBot<V, K>
 where
-    V: BoundaryCondition,
-    K: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
+    K: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.back_boundary, self.bot_boundary);
 
-        let area = s.del2() / 2.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(2.);
 
-        let m = ((info.k_front + info.j_front) / 2.)
-            + ((info.i_front + info.i_back) / 4.)
+        let m = ((az * info.k_front + ay * info.j_front) / lit(2.))
+            + (ax * (info.i_front + info.i_back) / lit(4.))
             + self.back_boundary.lhs_constant(&info, s, area)
             + self.bot_boundary.lhs_constant(&info, s, area);
 
-        let div = 3. / 2.
+        let div = (ax + ay + az) / lit(2.)
             + self.bot_boundary.rhs_constant(&info, s, area)
             + self.back_boundary.rhs_constant(&info, s, area);
 
-        (m + (s.q_dot * s.del2() / (s.k * 4.))) / div
+        (m + (s.q_dot * s.del2() / (s.k * lit(4.)))) / div
     }
 }
 
-pub struct FrontBot<V: BoundaryCondition, K: BoundaryCondition> {
+pub struct FrontBot<V, K> {
     pub front_boundary: V,
     pub bot_boundary: K,
 }
-impl<V, K> CalculateTemperature for FrontBot<V, K>
+impl<T, V, K> CalculateTemperature<T> for FrontBot<V, K>
 where
-    V: BoundaryCondition,
-    K: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
+    K: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.front_boundary, self.bot_boundary);
 
-        let area = s.del2() / 2.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(2.);
 
-        let m = ((info.k_back + info.j_front) / 2.)
-            + ((info.i_front + info.i_back) / 4.)
+        let m = ((az * info.k_back + ay * info.j_front) / lit(2.))
+            + (ax * (info.i_front + info.i_back) / lit(4.))
             + self.front_boundary.lhs_constant(&info, s, area)
             + self.bot_boundary.lhs_constant(&info, s, area);
 
-        let div = 3. / 2.
+        let div = (ax + ay + az) / lit(2.)
             + self.bot_boundary.rhs_constant(&info, s, area)
             + self.front_boundary.rhs_constant(&info, s, area);
 
-        (m + (s.q_dot * s.del2() / (s.k * 4.))) / div
+        (m + (s.q_dot * s.del2() / (s.k * lit(4.)))) / div
     }
 }
 
@@ -222,109 +238,117 @@ where
 //
 //
 
-pub struct FrontRight<V: BoundaryCondition, K: BoundaryCondition> {
+pub struct FrontRight<V, K> {
     pub front_boundary: V,
     pub right_boundary: K,
 }
-impl<V, K> CalculateTemperature for FrontRight<V, K>
+impl<T, V, K> CalculateTemperature<T> for FrontRight<V, K>
 where
-    V: BoundaryCondition,
-    K: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
+    K: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.front_boundary, self.right_boundary);
 
-        let area = s.del2() / 2.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(2.);
 
-        let m = ((info.k_back + info.i_back) / 2.)
-            + ((info.j_front + info.j_back) / 4.)
+        let m = ((az * info.k_back + ax * info.i_back) / lit(2.))
+            + (ay * (info.j_front + info.j_back) / lit(4.))
             + self.front_boundary.lhs_constant(&info, s, area)
             + self.right_boundary.lhs_constant(&info, s, area);
 
-        let div = 3. / 2.
+        let div = (ax + ay + az) / lit(2.)
             + self.right_boundary.rhs_constant(&info, s, area)
             + self.front_boundary.rhs_constant(&info, s, area);
 
-        (m + (s.q_dot * s.del2() / (s.k * 4.))) / div
+        (m + (s.q_dot * s.del2() / (s.k * lit(4.)))) / div
     }
 }
 
-pub struct FrontLeft<V: BoundaryCondition, K: BoundaryCondition> {
+pub struct FrontLeft<V, K> {
     pub front_boundary: V,
     pub left_boundary: K,
 }
-impl<V, K> CalculateTemperature for FrontLeft<V, K>
+impl<T, V, K> CalculateTemperature<T> for FrontLeft<V, K>
 where
-    V: BoundaryCondition,
-    K: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
+    K: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.front_boundary, self.left_boundary);
 
-        let area = s.del2() / 2.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(2.);
 
-        let m = ((info.k_back + info.i_front) / 2.)
-            + ((info.j_front + info.j_back) / 4.)
+        let m = ((az * info.k_back + ax * info.i_front) / lit(2.))
+            + (ay * (info.j_front + info.j_back) / lit(4.))
             + self.front_boundary.lhs_constant(&info, s, area)
             + self.left_boundary.lhs_constant(&info, s, area);
 
-        let div = 3. / 2.
+        let div = (ax + ay + az) / lit(2.)
             + self.left_boundary.rhs_constant(&info, s, area)
             + self.front_boundary.rhs_constant(&info, s, area);
 
-        (m + (s.q_dot * s.del2() / (s.k * 4.))) / div
+        (m + (s.q_dot * s.del2() / (s.k * lit(4.)))) / div
     }
 }
 
-pub struct BackLeft<V: BoundaryCondition, K: BoundaryCondition> {
+pub struct BackLeft<V, K> {
     pub back_boundary: V,
     pub left_boundary: K,
 }
-impl<V, K> CalculateTemperature for BackLeft<V, K>
+impl<T, V, K> CalculateTemperature<T> for BackLeft<V, K>
 where
-    V: BoundaryCondition,
-    K: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
+    K: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.back_boundary, self.left_boundary);
 
-        let area = s.del2() / 2.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(2.);
 
-        let m = ((info.k_front + info.i_front) / 2.)
-            + ((info.j_front + info.j_back) / 4.)
+        let m = ((az * info.k_front + ax * info.i_front) / lit(2.))
+            + (ay * (info.j_front + info.j_back) / lit(4.))
             + self.back_boundary.lhs_constant(&info, s, area)
             + self.left_boundary.lhs_constant(&info, s, area);
 
-        let div = 3. / 2.
+        let div = (ax + ay + az) / lit(2.)
             + self.left_boundary.rhs_constant(&info, s, area)
             + self.back_boundary.rhs_constant(&info, s, area);
 
-        (m + (s.q_dot * s.del2() / (s.k * 4.))) / div
+        (m + (s.q_dot * s.del2() / (s.k * lit(4.)))) / div
     }
 }
-pub struct BackRight<V: BoundaryCondition, K: BoundaryCondition> {
+pub struct BackRight<V, K> {
     pub back_boundary: V,
     pub right_boundary: K,
 }
-impl<V, K> CalculateTemperature for BackRight<V, K>
+impl<T, V, K> CalculateTemperature<T> for BackRight<V, K>
 where
-    V: BoundaryCondition,
-    K: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
+    K: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.back_boundary, self.right_boundary);
 
-        let area = s.del2() / 2.;
+        let [ax, ay, az] = s.anisotropy;
+        let area = s.del2() / lit(2.);
 
-        let m = ((info.k_front + info.i_back) / 2.)
-            + ((info.j_front + info.j_back) / 4.)
+        let m = ((az * info.k_front + ax * info.i_back) / lit(2.))
+            + (ay * (info.j_front + info.j_back) / lit(4.))
             + self.back_boundary.lhs_constant(&info, s, area)
             + self.right_boundary.lhs_constant(&info, s, area);
 
-        let div = 3. / 2.
+        let div = (ax + ay + az) / lit(2.)
             + self.right_boundary.rhs_constant(&info, s, area)
             + self.back_boundary.rhs_constant(&info, s, area);
 
-        (m + (s.q_dot * s.del2() / (s.k * 4.))) / div
+        (m + (s.q_dot * s.del2() / (s.k * lit(4.)))) / div
     }
 }
@@ -1,39 +1,50 @@
+use crate::dump::ErrorType;
 use crate::prelude::*;
+
 pub struct InfinityNorm;
 
-impl CalculateError for InfinityNorm {
-    fn calculate_error(&self, previous_step: &Matrix, current_step: &Matrix) -> T {
-        let diff = current_step - previous_step;
-        diff.into_iter()
-            .map(|x| x.abs())
-            .max_by(|left, right| left.partial_cmp(right).unwrap())
-            .unwrap()
+impl<T: Float> CalculateError<T> for InfinityNorm {
+    fn accumulate(&self, total: T, difference: T) -> T {
+        total.max(difference.abs())
+    }
+    fn combine(&self, left: T, right: T) -> T {
+        left.max(right)
     }
-    fn to_error_type(&self) -> crate::dump::ErrorType {
-        crate::dump::ErrorType::InfinityNorm
+    fn to_error_type(&self) -> ErrorType {
+        ErrorType::InfinityNorm
     }
 }
 
 pub struct L1Norm;
 
-impl CalculateError for L1Norm {
-    fn calculate_error(&self, previous_step: &Matrix, current_step: &Matrix) -> T {
-        let diff = current_step - previous_step;
-        diff.into_iter().map(|x| x.abs()).sum::<T>().sqrt()
+impl<T: Float> CalculateError<T> for L1Norm {
+    fn accumulate(&self, total: T, difference: T) -> T {
+        total + difference.abs()
+    }
+    fn combine(&self, left: T, right: T) -> T {
+        left + right
     }
-    fn to_error_type(&self) -> crate::dump::ErrorType {
-        crate::dump::ErrorType::L1Norm
+    fn finish(&self, total: T) -> T {
+        total.sqrt()
+    }
+    fn to_error_type(&self) -> ErrorType {
+        ErrorType::L1Norm
     }
 }
 
 pub struct L2Norm;
 
-impl CalculateError for L2Norm {
-    fn calculate_error(&self, previous_step: &Matrix, current_step: &Matrix) -> T {
-        let diff = current_step - previous_step;
-        diff.into_iter().map(|x| x.abs().powi(2)).sum::<T>().sqrt()
+impl<T: Float> CalculateError<T> for L2Norm {
+    fn accumulate(&self, total: T, difference: T) -> T {
+        total + difference * difference
+    }
+    fn combine(&self, left: T, right: T) -> T {
+        left + right
+    }
+    fn finish(&self, total: T) -> T {
+        total.sqrt()
     }
-    fn to_error_type(&self) -> crate::dump::ErrorType {
-        crate::dump::ErrorType::L2Norm
+    fn to_error_type(&self) -> ErrorType {
+        ErrorType::L2Norm
     }
 }
@@ -0,0 +1,177 @@
+//! Temperature gradient and conductive heat flux, calculated from a solved temperature field
+//!
+//! derivatives are second order everywhere: central differences between interior nodes and
+//! one sided three point differences along any axis where a node lies on a face of the cube,
+//! so edges and corners use one sided differences along each of their two or three axes. The
+//! NaN nodes outside of a masked geometry are treated like the outside of the cube
+
+use crate::anisotropy;
+use crate::dump::{Results, StepData};
+use crate::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+/// Derivative of `temps` along `axis` at every node, `[K/m]`
+pub fn derivative<T: Float>(temps: &Matrix<T>, del: T, axis: usize) -> Matrix<T> {
+    let n = temps.shape()[axis] as isize;
+    let two_del = lit::<T>(2.) * del;
+
+    Matrix::from_shape_fn(temps.raw_dim(), |(x, y, z)| {
+        let index = [x, y, z];
+        let at = |offset: isize| {
+            let mut node = index;
+            node[axis] = (node[axis] as isize + offset) as usize;
+            temps[node]
+        };
+        let has = |offset: isize| {
+            let i = index[axis] as isize + offset;
+            i >= 0 && i < n && !at(offset).is_nan()
+        };
+
+        if !has(0) {
+            T::nan()
+        } else if has(-1) && has(1) {
+            (at(1) - at(-1)) / two_del
+        } else if has(1) && has(2) {
+            (lit::<T>(-3.) * at(0) + lit::<T>(4.) * at(1) - at(2)) / two_del
+        } else if has(-1) && has(-2) {
+            (lit::<T>(3.) * at(0) - lit::<T>(4.) * at(-1) + at(-2)) / two_del
+        } else if has(1) {
+            // only two nodes along the axis, a single first order difference
+            (at(1) - at(0)) / del
+        } else if has(-1) {
+            (at(0) - at(-1)) / del
+        } else {
+            T::zero()
+        }
+    })
+}
+
+/// `[dT/dx, dT/dy, dT/dz]` at every node, `[K/m]`
+pub fn gradient<T: Float>(temps: &Matrix<T>, del: T) -> [Matrix<T>; 3] {
+    [0, 1, 2].map(|axis| derivative(temps, del, axis))
+}
+
+/// Conductive heat flux `-k grad T` at every node, `[W/m^2]`
+pub fn heat_flux<T: Float>(temps: &Matrix<T>, del: T, k: T) -> [Matrix<T>; 3] {
+    gradient(temps, del).map(|component| component.mapv(|value| -k * value))
+}
+
+/// Conductive heat flux `-K grad T` at every node, with the conductivity tensor `k` of each
+/// node in the cube's frame `[W/(mK)]`, `[W/m^2]`
+pub fn anisotropic_heat_flux<T: Float>(
+    temps: &Matrix<T>,
+    del: T,
+    k: impl Fn([usize; 3]) -> [[T; 3]; 3],
+) -> [Matrix<T>; 3] {
+    conducted(&gradient(temps, del), k)
+}
+
+/// Heat flux `[W/m^2]` at every node of a saved step of `results`, along each axis of its
+/// grid
+///
+/// the flux saved with the results is that of their last step. Any other step is calculated
+/// with the conductivity tensor saved with them, that of each material of a geometry, and
+/// along `(r, θ, z)` in a cylinder. Results saved without a conductivity are taken to conduct
+/// alike along every axis
+pub fn of_step<T: Float>(results: &Results<T>, step: &StepData<T>) -> [Matrix<T>; 3] {
+    let simulation = &results.simulation;
+    let shape = simulation.shape();
+    let last = simulation.step_data.last().map(|last| last.step);
+
+    if let (Some(saved), true) = (&results.heat_flux, last == Some(step.step)) {
+        return saved.heat_flux.clone().map(|component| {
+            Matrix::from_shape_vec(shape, component).expect("the heat flux is saved at every node")
+        });
+    }
+
+    let temps = step.temperatures(shape);
+    let n = simulation.size;
+    let del = results.solver_params.del();
+    let k = match (&results.orthotropic, results.thermal_conductivity) {
+        (Some(orthotropic), _) => orthotropic.tensor(),
+        (None, k) => anisotropy::diagonal([k.unwrap_or_else(T::one); 3]),
+    };
+
+    match (&results.geometry, &results.cylinder) {
+        (_, Some(cylinder)) => {
+            let spacing = cylinder.spacing(n);
+            let mut gradient = [0, 1, 2].map(|axis| derivative(&temps, spacing[axis], axis));
+
+            // the change per radian around the cylinder is spread over the node's radius, and
+            // there is no direction around its axis
+            ndarray::Zip::indexed(&mut gradient[1]).for_each(|(i, j, l), gradient| {
+                let r = cylinder.position([i, j, l], n)[0];
+                *gradient = if r > T::zero() {
+                    *gradient / r
+                } else {
+                    T::zero()
+                };
+            });
+
+            conducted(&gradient, |_| k)
+        }
+        (Some(geometry), None) => {
+            let mask = geometry
+                .mask(n, del)
+                .unwrap_or_else(|error| panic!("{error}"));
+            anisotropic_heat_flux(&temps, del, |node| mask.tensor(node, k))
+        }
+        (None, None) => anisotropic_heat_flux(&temps, del, |_| k),
+    }
+}
+
+/// `-K grad T` from the gradient at every node and the tensor `k` of each node
+fn conducted<T: Float>(
+    gradient: &[Matrix<T>; 3],
+    k: impl Fn([usize; 3]) -> [[T; 3]; 3],
+) -> [Matrix<T>; 3] {
+    let tensors = Matrix::from_shape_fn(gradient[0].raw_dim(), |(x, y, z)| k([x, y, z]));
+
+    [0, 1, 2].map(|row| {
+        ndarray::Zip::from(&tensors)
+            .and(&gradient[0])
+            .and(&gradient[1])
+            .and(&gradient[2])
+            .map_collect(|k, &x, &y, &z| -(k[row][0] * x + k[row][1] * y + k[row][2] * z))
+    })
+}
+
+/// Gradient and heat flux of a whole field, flattened in the same order as `StepData::data`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(bound(deserialize = "T: Float"))]
+pub struct FluxField<T> {
+    /// `[dT/dx, dT/dy, dT/dz]` `[K/m]`
+    #[serde(deserialize_with = "crate::dump::nullable")]
+    pub gradient: [Vec<T>; 3],
+    /// `-k grad T` `[W/m^2]`
+    #[serde(deserialize_with = "crate::dump::nullable")]
+    pub heat_flux: [Vec<T>; 3],
+    /// length of the heat flux vector `[W/m^2]`
+    #[serde(deserialize_with = "crate::dump::nullable")]
+    pub magnitude: Vec<T>,
+}
+
+impl<T: Float> FluxField<T> {
+    pub fn new(temps: &Matrix<T>, del: T, k: T) -> Self {
+        Self::with_tensor(temps, del, |_| crate::anisotropy::diagonal([k; 3]))
+    }
+
+    /// The field of a part with the conductivity tensor `k` `[W/m K]` of each node in the
+    /// cube's frame
+    pub fn with_tensor(temps: &Matrix<T>, del: T, k: impl Fn([usize; 3]) -> [[T; 3]; 3]) -> Self {
+        let gradient = gradient(temps, del);
+        let heat_flux = conducted(&gradient, k);
+
+        let magnitude = ndarray::Zip::from(&heat_flux[0])
+            .and(&heat_flux[1])
+            .and(&heat_flux[2])
+            .map_collect(|&x, &y, &z| (x * x + y * y + z * z).sqrt());
+
+        FluxField {
+            gradient: gradient.map(|component| component.into_iter().collect()),
+            heat_flux: heat_flux.map(|component| component.into_iter().collect()),
+            magnitude: magnitude.into_iter().collect(),
+        }
+    }
+}
@@ -0,0 +1,832 @@
+//! Parts that are not a full cube, described by which cells between the nodes are solid
+//!
+//! the nodes stay on the same grid as the cube's, cell `[i, j, k]` is the box between nodes
+//! `[i, j, k]` and `[i + 1, j + 1, k + 1]`. A node takes part in the solve when any of the
+//! eight cells around it is solid, and the faces between solid and void cells are boundaries
+//! with the condition of the void's surface. Each solid cell is of a material with its own
+//! conductivity and heat generation
+//!
+//! a contact between two materials, or across a plane, lies on a layer of nodes. Half of its
+//! resistance is put in series with the conduction from those nodes towards either side, so
+//! the temperature jumps across the cells on both sides of the contact and the nodes on it
+//! are at the mean of the two faces
+
+use crate::anisotropy;
+use crate::conditions::{Condition, HeatFlux};
+use crate::csg::Shape;
+use crate::prelude::*;
+use crate::probe::Axis;
+use crate::stl::{self, Mesh};
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// A single cell of the mask
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Cell {
+    /// of material `index`
+    Solid(usize),
+    /// empty, the faces it shares with solid cells take the condition of surface `index`
+    Void(usize),
+}
+
+impl Cell {
+    pub fn is_solid(self) -> bool {
+        matches!(self, Cell::Solid(_))
+    }
+}
+
+/// Properties of the solid cells of a material, those not given are the input's
+/// `thermal_conductivity` and `energy_generation`
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Material<T> {
+    /// `[W/m K]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub k: Option<T>,
+    /// `[W/m^3]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q_dot: Option<T>,
+}
+
+impl<T: Float> Material<T> {
+    /// The material of the input's properties
+    pub fn inherited() -> Self {
+        Material {
+            k: None,
+            q_dot: None,
+        }
+    }
+
+    pub fn k(&self, s: &SolverInfo<T>) -> T {
+        self.k.unwrap_or(s.k)
+    }
+
+    /// Conductivity along `axis`, the input's is the same along every axis unless it is
+    /// orthotropic
+    pub fn k_along(&self, s: &SolverInfo<T>, axis: usize) -> T {
+        self.k.unwrap_or(s.k * s.anisotropy[axis])
+    }
+
+    pub fn q_dot(&self, s: &SolverInfo<T>) -> T {
+        self.q_dot.unwrap_or(s.q_dot)
+    }
+}
+
+/// A contact conductance between the cells of a mask
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Junction<T> {
+    /// `[W/(m^2 K)]`
+    pub conductance: T,
+    pub between: Between,
+}
+
+/// Where a contact is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Between {
+    /// solid cells of materials `first` and `second` sharing a face, heat flows from the
+    /// first to the second
+    Materials(usize, usize),
+    /// solid cells on either side of the layer of nodes `layer` along `axis`, heat flows
+    /// towards the high side
+    Plane { axis: usize, layer: usize },
+}
+
+/// Solid and void cells of a cube of `divisions` nodes along each side
+#[derive(Clone)]
+pub struct Mask<T> {
+    /// `divisions - 1` cells along each side
+    pub cells: ndarray::Array3<Cell>,
+    /// conditions of the faces between solid and void cells, the first is adiabatic
+    pub surfaces: Vec<Condition<T>>,
+    /// materials of the solid cells, the first has the input's properties
+    pub materials: Vec<Material<T>>,
+    /// contact conductances, the first that applies to a face is used
+    pub contacts: Vec<Junction<T>>,
+}
+
+impl<T: Float> Mask<T> {
+    /// Every cell solid, the full cube of the input's material
+    pub fn solid(divisions: usize) -> Self {
+        let cells = divisions.saturating_sub(1);
+
+        Mask {
+            cells: ndarray::Array3::from_elem((cells, cells, cells), Cell::Solid(0)),
+            surfaces: vec![Condition::HeatFlux(HeatFlux {
+                heat_flux: T::zero(),
+            })],
+            materials: vec![Material::inherited()],
+            contacts: Vec::new(),
+        }
+    }
+
+    /// Every cell void with adiabatic surfaces, to be filled
+    pub fn empty(divisions: usize) -> Self {
+        let mut mask = Self::solid(divisions);
+        mask.cells.fill(Cell::Void(0));
+        mask
+    }
+
+    pub fn divisions(&self) -> usize {
+        self.cells.shape()[0] + 1
+    }
+
+    /// Adds a condition for the surfaces of voids, returning its index
+    pub fn surface(&mut self, condition: Condition<T>) -> usize {
+        self.surfaces.push(condition);
+        self.surfaces.len() - 1
+    }
+
+    /// Adds a material for solid cells, returning its index
+    pub fn material(&mut self, material: Material<T>) -> usize {
+        self.materials.push(material);
+        self.materials.len() - 1
+    }
+
+    /// Empties every cell `void` is true for, called with the index of each cell
+    ///
+    /// the faces the emptied cells share with solid ones take the condition of `surface`
+    pub fn carve(&mut self, surface: usize, void: impl Fn([usize; 3]) -> bool) {
+        assert!(
+            surface < self.surfaces.len(),
+            "there is no surface {surface}"
+        );
+
+        for ((i, j, k), cell) in self.cells.indexed_iter_mut() {
+            if void([i, j, k]) {
+                *cell = Cell::Void(surface);
+            }
+        }
+    }
+
+    /// Fills every cell `solid` is true for with `material`, called with the index of each
+    /// cell
+    pub fn fill(&mut self, material: usize, solid: impl Fn([usize; 3]) -> bool) {
+        assert!(
+            material < self.materials.len(),
+            "there is no material {material}"
+        );
+
+        for ((i, j, k), cell) in self.cells.indexed_iter_mut() {
+            if solid([i, j, k]) {
+                *cell = Cell::Solid(material);
+            }
+        }
+    }
+
+    /// Whether the cell at `index` is void and shares a face with a solid cell
+    pub fn on_surface(&self, index: [usize; 3]) -> bool {
+        !self.cells[index].is_solid()
+            && face_neighbours(index, self.cells.shape()[0])
+                .any(|neighbour| self.cells[neighbour].is_solid())
+    }
+
+    /// The cell at `index`, `None` outside of the cube
+    pub fn cell(&self, index: [isize; 3]) -> Option<Cell> {
+        let index: [usize; 3] = [
+            usize::try_from(index[0]).ok()?,
+            usize::try_from(index[1]).ok()?,
+            usize::try_from(index[2]).ok()?,
+        ];
+
+        self.cells.get(index).copied()
+    }
+
+    /// The eight cells around `node`, octant `o` is on the high side of the node along axis
+    /// `a` when bit `a` of `o` is set
+    pub fn octants(&self, node: [usize; 3]) -> [Option<Cell>; 8] {
+        std::array::from_fn(|octant| self.cell(Self::cell_index(node, octant)))
+    }
+
+    /// Whether any of the cells around `node` is solid
+    pub fn is_active(&self, node: [usize; 3]) -> bool {
+        self.octants(node).into_iter().flatten().any(Cell::is_solid)
+    }
+
+    /// Fraction of a full cell's volume that `node` represents, an eighth for each solid cell
+    /// around it
+    pub fn volume(&self, node: [usize; 3]) -> T {
+        let solid = self
+            .octants(node)
+            .into_iter()
+            .flatten()
+            .filter(|cell| cell.is_solid())
+            .count();
+
+        lit(solid as f64 / 8.)
+    }
+
+    /// Fraction of a full cell's face that `node` represents on the face of the cube normal
+    /// to `axis`, a quarter for each solid cell touching it
+    pub fn face_area(&self, node: [usize; 3], axis: usize) -> T {
+        let end = self.divisions() - 1;
+        // only the cells inside the cube can touch its face
+        let inward = if node[axis] == end { 0 } else { 1 };
+
+        let solid = self
+            .octants(node)
+            .iter()
+            .enumerate()
+            .filter(|&(octant, &cell)| {
+                (octant >> axis) & 1 == inward && cell.is_some_and(Cell::is_solid)
+            })
+            .count();
+
+        lit(solid as f64 / 4.)
+    }
+
+    /// Volume of the solid cells `[m^3]`
+    pub fn solid_volume(&self, del: T) -> T {
+        let solid = self.cells.iter().filter(|cell| cell.is_solid()).count();
+        lit::<T>(solid as f64) * del * del * del
+    }
+
+    /// Heat generated in the solid cells `[W]`, `None` when a cell takes the input's
+    /// generation and `q_dot` is not known
+    pub fn heat_generated(&self, q_dot: Option<T>, del: T) -> Option<T> {
+        let mut total = T::zero();
+
+        for cell in &self.cells {
+            if let Cell::Solid(material) = cell {
+                total = total + self.materials[*material].q_dot.or(q_dot)?;
+            }
+        }
+
+        Some(total * del * del * del)
+    }
+
+    /// Index of the cell in `octant` around `node`, which may be outside of the cube
+    fn cell_index(node: [usize; 3], octant: usize) -> [isize; 3] {
+        std::array::from_fn(|axis| {
+            let high = (octant >> axis) & 1 == 1;
+            node[axis] as isize - if high { 0 } else { 1 }
+        })
+    }
+
+    /// The contact between `cell` and the cell `facing` it across the layer of nodes `layer`
+    /// along `axis`, with whether `cell` is on its second side, given whether `cell` is on the
+    /// `high` side of the layer
+    fn contact(
+        &self,
+        layer: usize,
+        axis: usize,
+        cell: Cell,
+        facing: Option<Cell>,
+        high: bool,
+    ) -> Option<(usize, bool)> {
+        let (Cell::Solid(material), Some(Cell::Solid(other))) = (cell, facing) else {
+            return None;
+        };
+
+        self.contacts
+            .iter()
+            .enumerate()
+            .find_map(|(index, junction)| match junction.between {
+                Between::Materials(first, second) if (other, material) == (first, second) => {
+                    Some((index, true))
+                }
+                Between::Materials(first, second) if (material, other) == (first, second) => {
+                    Some((index, false))
+                }
+                Between::Plane {
+                    axis: normal,
+                    layer: plane,
+                } if (normal, plane) == (axis, layer) => Some((index, high)),
+                _ => None,
+            })
+    }
+
+    /// Conduction from `node` along `direction`, ordered like the weights of a stencil,
+    /// through the solid cell in `octant` relative to `s.k * s.del`, `None` when the cell is
+    /// not solid or not on that side of the node
+    ///
+    /// half the resistance of a contact at either end of the edge is in series with the
+    /// quarter cell, and the contact at `node` is returned with the weight, with whether the
+    /// cell is on its second side
+    fn quarter(
+        &self,
+        node: [usize; 3],
+        octant: usize,
+        direction: usize,
+        s: &SolverInfo<T>,
+    ) -> Option<(T, Option<(usize, bool)>)> {
+        let axis = direction / 2;
+        let high = (octant >> axis) & 1 == 1;
+        let index = Self::cell_index(node, octant);
+        let cell = self.cell(index)?;
+
+        let Cell::Solid(material) = cell else {
+            return None;
+        };
+        if high != (direction % 2 == 1) {
+            return None;
+        }
+
+        // the cells facing this one across the node and across the neighbour
+        let mut across = index;
+        across[axis] += if high { -1 } else { 1 };
+        let mut beyond = index;
+        beyond[axis] += if high { 1 } else { -1 };
+        let neighbour = if high { node[axis] + 1 } else { node[axis] - 1 };
+
+        let at_node = self.contact(node[axis], axis, cell, self.cell(across), high);
+        let at_neighbour = self.contact(neighbour, axis, cell, self.cell(beyond), !high);
+
+        let k = self.materials[material].k_along(s, axis);
+        let contacts =
+            [at_node, at_neighbour]
+                .into_iter()
+                .flatten()
+                .fold(T::zero(), |sum, (contact, _)| {
+                    sum + k / (lit::<T>(2.) * self.contacts[contact].conductance * s.del)
+                });
+
+        let weight = lit::<T>(0.25) * k / s.k / (T::one() + contacts);
+        Some((weight, at_node))
+    }
+
+    /// Conduction from `node` to each of its neighbours relative to `s.k * s.del`, ordered like
+    /// the weights of a stencil, a quarter of a face scaled by the conductivity for each solid
+    /// cell between them with any contacts in series
+    pub fn conductances(&self, node: [usize; 3], s: &SolverInfo<T>) -> [T; 6] {
+        std::array::from_fn(|direction| {
+            (0..8)
+                .filter_map(|octant| self.quarter(node, octant, direction, s))
+                .fold(T::zero(), |sum, (weight, _)| sum + weight)
+        })
+    }
+
+    /// The quarter faces through `node` that conduct across a contact lying on it, as the
+    /// direction of the conduction ordered like the weights of a stencil, its weight relative
+    /// to `s.k * s.del`, the contact and whether the direction leads to its second side
+    pub fn crossings(&self, node: [usize; 3], s: &SolverInfo<T>) -> Vec<(usize, T, usize, bool)> {
+        if self.contacts.is_empty() {
+            return Vec::new();
+        }
+
+        (0..6)
+            .flat_map(|direction| (0..8).map(move |octant| (direction, octant)))
+            .filter_map(|(direction, octant)| {
+                let (weight, contact) = self.quarter(node, octant, direction, s)?;
+                let (contact, second) = contact?;
+                Some((direction, weight, contact, second))
+            })
+            .collect()
+    }
+
+    /// Mean conductivity along each axis of the solid cells around `node`, with `k` for the
+    /// input's, NaN without any
+    pub fn conductivity(&self, node: [usize; 3], k: [T; 3]) -> [T; 3] {
+        let tensor = self.tensor(node, anisotropy::diagonal(k));
+        [0, 1, 2].map(|axis| tensor[axis][axis])
+    }
+
+    /// Mean conductivity tensor of the solid cells around `node`, with `k` for the input's and
+    /// the same along every axis for a material of its own, NaN without any
+    pub fn tensor(&self, node: [usize; 3], k: [[T; 3]; 3]) -> [[T; 3]; 3] {
+        let (total, count) = self.octants(node).into_iter().flatten().fold(
+            ([[T::zero(); 3]; 3], 0),
+            |(total, count), cell| match cell {
+                Cell::Solid(material) => {
+                    let k = match self.materials[material].k {
+                        Some(k) => anisotropy::diagonal([k; 3]),
+                        None => k,
+                    };
+                    let total = [0, 1, 2]
+                        .map(|row| [0, 1, 2].map(|column| total[row][column] + k[row][column]));
+                    (total, count + 1)
+                }
+                Cell::Void(_) => (total, count),
+            },
+        );
+
+        total.map(|row| row.map(|total| total / lit(count as f64)))
+    }
+}
+
+/// The cells inside a cube of `cells` along each side that share a face with `index`
+fn face_neighbours(index: [usize; 3], cells: usize) -> impl Iterator<Item = [usize; 3]> {
+    (0..3).flat_map(move |axis| {
+        [index[axis].checked_sub(1), Some(index[axis] + 1)]
+            .into_iter()
+            .flatten()
+            .filter(move |&i| i < cells)
+            .map(move |i| {
+                let mut neighbour = index;
+                neighbour[axis] = i;
+                neighbour
+            })
+    })
+}
+
+/// The quarter cell faces through a node that separate a solid cell from a void one, or from
+/// the outside of the cube, given the eight cells around it as from `Mask::octants`
+///
+/// each is returned as the direction of its outward normal, ordered `-x, +x, -y, +y, -z, +z`
+/// like the weights of a stencil, and the cell on the other side, `None` outside of the cube
+pub fn exposed(octants: &[Option<Cell>; 8]) -> Vec<(usize, Option<Cell>)> {
+    let solid = |octant: usize| octants[octant].is_some_and(Cell::is_solid);
+    let mut faces = Vec::new();
+
+    for axis in 0..3 {
+        for low in (0..8).filter(|octant| (octant >> axis) & 1 == 0) {
+            let high = low | (1 << axis);
+
+            match (solid(low), solid(high)) {
+                (true, false) => faces.push((2 * axis + 1, octants[high])),
+                (false, true) => faces.push((2 * axis, octants[low])),
+                _ => {}
+            }
+        }
+    }
+
+    faces
+}
+
+/// Geometry of an input file: the cube, or the inside of STL meshes and solid shapes, with
+/// boxes cut out of it
+///
+/// ```json
+/// "geometry": {
+///     "surfaces": {"cooled": {"type": "Convection", "h": 25.0, "t_inf": 293.0}},
+///     "materials": {"copper": {"k": 400.0, "q_dot": 0.0}},
+///     "solids": [
+///         {"shape": {"type": "Box", "min": [0.0, 0.0, 0.0], "max": [0.1, 0.02, 0.1]}},
+///         {"shape": {"type": "Sphere", "centre": [0.05, 0.04, 0.05], "radius": 0.02},
+///          "material": "copper", "surface": "cooled"}
+///     ],
+///     "voids": [
+///         {"min": [0.05, 0.05, 0.0], "max": [0.1, 0.1, 0.1], "surface": "cooled"}
+///     ],
+///     "contacts": [
+///         {"name": "joint", "conductance": 2000.0,
+///          "interface": {"plane": {"axis": "y", "position": 0.02}}}
+///     ]
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(bound(deserialize = "T: Float"))]
+pub struct Geometry<T> {
+    /// conditions of void surfaces, by name
+    #[serde(default = "BTreeMap::new")]
+    pub surfaces: BTreeMap<String, Condition<T>>,
+    /// materials of solids and meshes, by name
+    #[serde(default = "BTreeMap::new", skip_serializing_if = "BTreeMap::is_empty")]
+    pub materials: BTreeMap<String, Material<T>>,
+    /// the part is the union of the insides of the meshes and the solids, or the full cube
+    /// without any
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub meshes: Vec<StlFile<T>>,
+    /// filled in order after the meshes, each replacing the material of what it overlaps
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub solids: Vec<Solid<T>>,
+    #[serde(default = "Vec::new")]
+    pub voids: Vec<Block<T>>,
+    /// contact resistances between solids, the first that applies to a face is used
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub contacts: Vec<Contact<T>>,
+}
+
+/// A box removed from the cube, every cell with its centre inside is void
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Block<T> {
+    /// `[m]`
+    pub min: [T; 3],
+    /// `[m]`
+    pub max: [T; 3],
+    /// name of the condition on the faces the box exposes, adiabatic when not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surface: Option<String>,
+}
+
+impl<T> Default for Geometry<T> {
+    /// The full cube
+    fn default() -> Self {
+        Geometry {
+            surfaces: BTreeMap::new(),
+            materials: BTreeMap::new(),
+            meshes: Vec::new(),
+            solids: Vec::new(),
+            voids: Vec::new(),
+            contacts: Vec::new(),
+        }
+    }
+}
+
+/// A thermal contact resistance between solid cells, such as a bolted joint or a bond line
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Contact<T> {
+    /// names the heat flow across it in the report
+    pub name: String,
+    /// the inverse of the contact resistance `[W/(m^2 K)]`
+    pub conductance: T,
+    pub interface: Interface<T>,
+}
+
+/// Where a contact lies
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum Interface<T> {
+    /// every face between cells of the two named materials of the geometry, heat flowing from
+    /// the first to the second is positive
+    Materials([String; 2]),
+    /// every face between solid cells on the layer of nodes closest to `position` along
+    /// `axis`, heat flowing towards the high side is positive
+    Plane { axis: Axis, position: T },
+}
+
+/// A shape filled with a material, every cell with its centre inside is solid
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Solid<T> {
+    pub shape: Shape<T>,
+    /// name of the material of its cells, the input's when not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material: Option<String>,
+    /// name of the condition on the faces it exposes, left to whatever was filled before
+    /// when not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surface: Option<String>,
+}
+
+/// A closed surface read from an STL file, placed in the cube by scaling its coordinates
+/// and then moving them by `offset`
+///
+/// every cell with its centre inside the surface is solid. The faces of a solid of the file
+/// take the condition of the surface with the solid's name, or of `surface` when there is
+/// none, and are adiabatic when neither is given
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(bound(deserialize = "T: Float"))]
+pub struct StlFile<T> {
+    pub path: PathBuf,
+    /// e.g. `0.001` for a file in millimetres
+    #[serde(default = "T::one")]
+    pub scale: T,
+    /// `[m]`
+    #[serde(default = "zero_offset")]
+    pub offset: [T; 3],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surface: Option<String>,
+    /// name of the material inside, the input's when not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material: Option<String>,
+}
+
+fn zero_offset<T: Float>() -> [T; 3] {
+    [T::zero(); 3]
+}
+
+/// Why the mask of a geometry could not be made
+#[derive(Debug)]
+pub enum Error {
+    /// a void, mesh or solid refers to a surface that is not defined
+    UnknownSurface(String),
+    UnknownMaterial(String),
+    Stl(PathBuf, stl::Error),
+    /// the plane of the named contact is not between two layers of cells
+    ContactOutside(String),
+    /// the named surface has a condition only the faces of the cube can take
+    UnsupportedSurface(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownSurface(name) => write!(f, "the geometry has no surface called `{name}`"),
+            Error::UnknownMaterial(name) => {
+                write!(f, "the geometry has no material called `{name}`")
+            }
+            Error::Stl(path, error) => write!(f, "failed to read {}: {error}", path.display()),
+            Error::ContactOutside(name) => {
+                write!(f, "the plane of contact `{name}` is not inside the cube")
+            }
+            Error::UnsupportedSurface(name) => write!(
+                f,
+                "surface `{name}` has a condition only supported on the faces of the cube"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Index of the entry of `names` called `name`
+fn find(
+    names: &BTreeMap<&str, usize>,
+    name: &str,
+    unknown: fn(String) -> Error,
+) -> Result<usize, Error> {
+    names
+        .get(name)
+        .copied()
+        .ok_or_else(|| unknown(name.to_string()))
+}
+
+impl<T: Float> Geometry<T> {
+    /// The mask of a grid of `divisions` nodes spaced `del` apart, reading any STL files
+    ///
+    /// the surfaces follow the adiabatic default and the materials the input's, each in the
+    /// order of their names
+    pub fn mask(&self, divisions: usize, del: T) -> Result<Mask<T>, Error> {
+        // a void has no opposite face to wrap around to, nor a direction for a coolant to
+        // flow along
+        for (name, condition) in &self.surfaces {
+            if matches!(condition, Condition::Periodic(_) | Condition::Channel(_)) {
+                return Err(Error::UnsupportedSurface(name.clone()));
+            }
+        }
+
+        let mut mask = Mask::solid(divisions);
+
+        let surfaces: BTreeMap<&str, usize> = self
+            .surfaces
+            .iter()
+            .map(|(name, &condition)| (name.as_str(), mask.surface(condition)))
+            .collect();
+        let materials: BTreeMap<&str, usize> = self
+            .materials
+            .iter()
+            .map(|(name, &material)| (name.as_str(), mask.material(material)))
+            .collect();
+
+        if !self.meshes.is_empty() || !self.solids.is_empty() {
+            mask.cells.fill(Cell::Void(0));
+        }
+
+        if !self.meshes.is_empty() {
+            self.fill_meshes(&mut mask, del.to_f64().unwrap(), &surfaces, &materials)?;
+        }
+
+        if !self.solids.is_empty() {
+            self.fill_solids(&mut mask, del, &surfaces, &materials)?;
+        }
+
+        for block in &self.voids {
+            let surface = match &block.surface {
+                Some(name) => find(&surfaces, name, Error::UnknownSurface)?,
+                None => 0,
+            };
+
+            mask.carve(surface, |index| {
+                (0..3).all(|axis| {
+                    let centre = (lit::<T>(index[axis] as f64) + lit(0.5)) * del;
+                    centre > block.min[axis] && centre < block.max[axis]
+                })
+            });
+        }
+
+        for contact in &self.contacts {
+            let between = match &contact.interface {
+                Interface::Materials([first, second]) => Between::Materials(
+                    find(&materials, first, Error::UnknownMaterial)?,
+                    find(&materials, second, Error::UnknownMaterial)?,
+                ),
+                Interface::Plane { axis, position } => {
+                    let layer = (*position / del).round().to_usize();
+                    match layer {
+                        Some(layer) if layer > 0 && layer < divisions - 1 => Between::Plane {
+                            axis: axis.index(),
+                            layer,
+                        },
+                        _ => return Err(Error::ContactOutside(contact.name.clone())),
+                    }
+                }
+            };
+
+            mask.contacts.push(Junction {
+                conductance: contact.conductance,
+                between,
+            });
+        }
+
+        Ok(mask)
+    }
+
+    /// Voxelizes every mesh into `mask`, then gives each void cell on the surface of the part
+    /// the condition of the closest solid of the meshes
+    fn fill_meshes(
+        &self,
+        mask: &mut Mask<T>,
+        del: f64,
+        surfaces: &BTreeMap<&str, usize>,
+        materials: &BTreeMap<&str, usize>,
+    ) -> Result<(), Error> {
+        let cells = mask.cells.shape()[0];
+
+        // all the meshes together, with the surface of each of their groups
+        let mut all = Mesh::default();
+        let mut group_surfaces = Vec::new();
+
+        for file in &self.meshes {
+            let material = match &file.material {
+                Some(name) => find(materials, name, Error::UnknownMaterial)?,
+                None => 0,
+            };
+
+            let mut mesh =
+                Mesh::read(&file.path).map_err(|error| Error::Stl(file.path.clone(), error))?;
+            mesh.transform(
+                file.scale.to_f64().unwrap(),
+                file.offset.map(|x| x.to_f64().unwrap()),
+            );
+
+            let inside = mesh.voxelize(cells, del);
+            mask.fill(material, |index| inside[index]);
+
+            for group in &mesh.groups {
+                let surface = match (surfaces.get(group.as_str()), &file.surface) {
+                    (Some(&surface), _) => surface,
+                    (None, Some(name)) => find(surfaces, name, Error::UnknownSurface)?,
+                    (None, None) => 0,
+                };
+                group_surfaces.push(surface);
+            }
+
+            let first = all.groups.len();
+            all.triangles
+                .extend(mesh.triangles.into_iter().map(|triangle| stl::Triangle {
+                    group: first + triangle.group,
+                    ..triangle
+                }));
+            all.groups.extend(mesh.groups);
+        }
+
+        // a single surface needs no search for the closest triangle
+        if group_surfaces.iter().all(|&surface| surface == 0) {
+            return Ok(());
+        }
+
+        let on_surface = all.nearest_groups(cells, del, |index| mask.on_surface(index));
+
+        for (cell, group) in mask.cells.iter_mut().zip(on_surface) {
+            if let Some(group) = group {
+                *cell = Cell::Void(group_surfaces[group]);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fills the solids in order, then gives each void cell next to a solid with a surface
+    /// that surface's condition, the latest solid's where it touches several
+    fn fill_solids(
+        &self,
+        mask: &mut Mask<T>,
+        del: T,
+        surfaces: &BTreeMap<&str, usize>,
+        materials: &BTreeMap<&str, usize>,
+    ) -> Result<(), Error> {
+        let cells = mask.cells.shape()[0];
+        let centre = |index: [usize; 3]| index.map(|i| (lit::<T>(i as f64) + lit(0.5)) * del);
+
+        // the solid filled last into every cell, and the surface of every solid
+        let mut owner = ndarray::Array3::from_elem((cells, cells, cells), None);
+        let mut solid_surfaces = Vec::new();
+
+        for (number, solid) in self.solids.iter().enumerate() {
+            let material = match &solid.material {
+                Some(name) => find(materials, name, Error::UnknownMaterial)?,
+                None => 0,
+            };
+            let surface = match &solid.surface {
+                Some(name) => Some(find(surfaces, name, Error::UnknownSurface)?),
+                None => None,
+            };
+            solid_surfaces.push(surface);
+
+            let inside = ndarray::Array3::from_shape_fn((cells, cells, cells), |(i, j, k)| {
+                solid.shape.contains(centre([i, j, k]))
+            });
+            mask.fill(material, |index| inside[index]);
+
+            for (owner, _) in owner.iter_mut().zip(&inside).filter(|(_, &inside)| inside) {
+                *owner = Some(number);
+            }
+        }
+
+        if solid_surfaces.iter().all(Option::is_none) {
+            return Ok(());
+        }
+
+        let tags = ndarray::Array3::from_shape_fn((cells, cells, cells), |(i, j, k)| {
+            let index = [i, j, k];
+            if !mask.on_surface(index) {
+                return None;
+            }
+
+            face_neighbours(index, cells)
+                .filter_map(|neighbour| owner[neighbour])
+                .filter(|&solid| solid_surfaces[solid].is_some())
+                .max()
+                .and_then(|solid| solid_surfaces[solid])
+        });
+
+        for (cell, surface) in mask.cells.iter_mut().zip(tags) {
+            if let Some(surface) = surface {
+                *cell = Cell::Void(surface);
+            }
+        }
+
+        Ok(())
+    }
+}
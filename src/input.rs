@@ -0,0 +1,97 @@
+use crate::conditions::Condition;
+use crate::dump::ErrorType;
+use crate::prelude::*;
+use crate::setup::SetupConditions;
+use crate::SolverParams;
+
+use serde::Deserialize;
+use std::path::Path;
+
+pub type Conditions<T> = SetupConditions<
+    Condition<T>,
+    Condition<T>,
+    Condition<T>,
+    Condition<T>,
+    Condition<T>,
+    Condition<T>,
+>;
+
+/// A full simulation description, read from a JSON input file
+#[derive(serde::Serialize, Deserialize, Clone)]
+#[serde(bound(deserialize = "T: Float"))]
+pub struct Input<T> {
+    #[serde(default)]
+    pub precision: Precision,
+    /// `[W/(mK)]`
+    pub thermal_conductivity: T,
+    /// conductivities along the axes of a material that may be turned relative to the cube,
+    /// which replace `thermal_conductivity` in the stencils
+    #[serde(default)]
+    pub orthotropic: Option<crate::anisotropy::Orthotropic<T>>,
+    /// `[W/m^3]`
+    #[serde(default = "num_traits::Zero::zero")]
+    pub energy_generation: T,
+    #[serde(default)]
+    pub error_type: ErrorType,
+    pub solver_params: SolverParams<T>,
+    pub conditions: Conditions<T>,
+    /// `1` for a wall solved along `x` or `2` for a cross-section over `(x, y)`, a full cube
+    /// when not given
+    #[serde(default)]
+    pub dimensions: crate::dimensions::Dimensions,
+    /// voids cut out of the cube, the full cube when not given
+    #[serde(default)]
+    pub geometry: Option<crate::geometry::Geometry<T>>,
+    /// a cylinder solved in place of the cube, whose size replaces `len`
+    #[serde(default)]
+    pub cylinder: Option<crate::cylindrical::Cylinder<T>>,
+    /// save the temperature gradient and heat flux at every node with the results
+    #[serde(default)]
+    pub export_heat_flux: bool,
+    /// save the temperatures mirrored across the symmetry faces with the results
+    #[serde(default)]
+    pub export_mirrored: bool,
+    /// points to track during the solve, and lines and planes to sample from the result
+    #[serde(default)]
+    pub probes: Option<crate::probe::Sampling<T>>,
+    /// grids and probes for `finite-difference convergence`
+    #[serde(default)]
+    pub convergence: Option<crate::convergence::Study<T>>,
+}
+
+impl<T: Float> Input<T> {
+    /// Conductivity along each axis relative to `thermal_conductivity`
+    pub fn anisotropy(&self) -> [T; 3] {
+        match &self.orthotropic {
+            Some(orthotropic) => orthotropic.anisotropy(self.thermal_conductivity),
+            None => [T::one(); 3],
+        }
+    }
+
+    /// Conductivity tensor in the cube's frame `[W/(mK)]`, with any cross terms of a turned
+    /// material that the stencils leave out
+    pub fn conductivity(&self) -> [[T; 3]; 3] {
+        match &self.orthotropic {
+            Some(orthotropic) => orthotropic.tensor(),
+            None => crate::anisotropy::diagonal([self.thermal_conductivity; 3]),
+        }
+    }
+}
+
+/// The fields that have to be known before the precision of the rest of the file is
+#[derive(Deserialize)]
+struct Header {
+    #[serde(default)]
+    precision: Precision,
+}
+
+/// Reads an input file without committing to a precision yet
+pub fn read(path: impl AsRef<Path>) -> Result<serde_json::Value, std::io::Error> {
+    let file = std::fs::File::open(path)?;
+    let value = serde_json::from_reader(std::io::BufReader::new(file))?;
+    Ok(value)
+}
+
+pub fn precision(input: &serde_json::Value) -> Result<Precision, serde_json::Error> {
+    Header::deserialize(input).map(|header| header.precision)
+}
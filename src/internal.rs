@@ -1,12 +1,15 @@
 use crate::prelude::*;
 pub struct InternalConduction;
 
-impl CalculateTemperature for InternalConduction {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
-        let m =
-            info.i_back + info.i_front + info.j_back + info.j_front + info.k_back + info.k_front;
+impl<T: Float> CalculateTemperature<T> for InternalConduction {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
+        let [ax, ay, az] = s.anisotropy;
 
-        let div = 6.;
+        let m = ax * (info.i_back + info.i_front)
+            + ay * (info.j_back + info.j_front)
+            + az * (info.k_back + info.k_front);
+
+        let div = lit::<T>(2.) * (ax + ay + az);
 
         let numerator = m + (s.q_dot * s.del2() / s.k);
 
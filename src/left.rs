@@ -1,25 +1,27 @@
 use crate::prelude::*;
 
-pub struct LeftSurface<V: BoundaryCondition> {
+pub struct LeftSurface<V> {
     pub left_boundary: V,
 }
 
-impl<V> CalculateTemperature for LeftSurface<V>
+impl<T, V> CalculateTemperature<T> for LeftSurface<V>
 where
-    V: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.left_boundary);
 
+        let [ax, ay, az] = s.anisotropy;
         let area = s.del2();
 
-        let m = (info.j_front + info.j_back + info.k_front + info.k_back) / 2.
-            + info.i_front
-            + self.left_boundary.lhs_constant(&info, &s, area);
+        let m = (ay * (info.j_front + info.j_back) + az * (info.k_front + info.k_back)) / lit(2.)
+            + ax * info.i_front
+            + self.left_boundary.lhs_constant(&info, s, area);
 
-        let numerator = m + (s.q_dot * s.del2() / (2.0 * s.k));
+        let numerator = m + (s.q_dot * s.del2() / (lit::<T>(2.0) * s.k));
 
-        let div = 3. + self.left_boundary.rhs_constant(&info, s, area);
+        let div = ax + ay + az + self.left_boundary.rhs_constant(&info, s, area);
 
         numerator / div
     }
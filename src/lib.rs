@@ -0,0 +1,77 @@
+mod macros;
+
+pub mod anisotropy;
+mod back;
+mod bot;
+pub mod channel;
+pub mod conditions;
+pub mod convergence;
+mod corner;
+pub mod correlation;
+pub mod csg;
+pub mod cylindrical;
+pub mod dimensions;
+pub mod dump;
+mod edge;
+pub mod error;
+pub mod flux;
+mod front;
+pub mod geometry;
+pub mod input;
+mod internal;
+mod left;
+pub mod mms;
+pub mod observer;
+pub mod prelude;
+pub mod probe;
+pub mod render;
+pub mod report;
+mod right;
+pub mod setup;
+pub mod solver;
+pub mod stencil;
+pub mod stl;
+pub mod symmetry;
+mod top;
+#[cfg(feature = "tui")]
+pub mod tui;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy)]
+pub struct SolverParams<T> {
+    pub len: T,
+    /// nodes along each side, at least the two on its ends
+    #[serde(deserialize_with = "divisions")]
+    pub divisions: usize,
+    pub error_epsilon: T,
+    pub data_steps: usize,
+    pub error_steps: usize,
+    /// how the interior is divided between threads
+    #[serde(default)]
+    pub sweep: stencil::Sweep,
+    /// size of the thread pool, rayon's global pool is used when not given
+    #[serde(default)]
+    pub threads: Option<usize>,
+}
+
+impl<T: prelude::Float> SolverParams<T> {
+    /// Spacing between nodes, the `divisions` nodes along each side span the full `len`
+    pub fn del(&self) -> T {
+        self.len / prelude::lit((self.divisions - 1) as f64)
+    }
+}
+
+/// Reads `divisions`, rejecting grids too coarse to have a spacing between their nodes
+fn divisions<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+    <usize as serde::Deserialize>::deserialize(deserializer).and_then(spaced)
+}
+
+/// `divisions` if it puts a node on each end of a side
+pub(crate) fn spaced<E: serde::de::Error>(divisions: usize) -> Result<usize, E> {
+    if divisions < 2 {
+        return Err(E::custom(format!(
+            "{divisions} divisions, expected at least 2 so that there is a node on each end of a side"
+        )));
+    }
+
+    Ok(divisions)
+}
@@ -1,97 +1,658 @@
-mod macros;
-
-mod back;
-mod bot;
-mod conditions;
-mod corner;
-mod dump;
-mod edge;
-mod error;
-mod front;
-mod internal;
-mod left;
-mod prelude;
-mod right;
-mod setup;
-mod solver;
-mod top;
-
-use conditions::*;
-
-use prelude::{SolverInfo, T};
+use finite_difference::channel::Coolant;
+use finite_difference::conditions::Condition;
+use finite_difference::convergence::{Grid, Summary};
+use finite_difference::dimensions::{self, Dimensions};
+use finite_difference::dump::{ErrorType, SimulationResult};
+use finite_difference::observer::Observer;
+use finite_difference::prelude::{lit, Float, Matrix, Precision, SolverInfo};
+use finite_difference::probe::Axis;
+use finite_difference::render::{Coordinates, Figure, Style};
+use finite_difference::report::Report;
+use finite_difference::stencil::Coefficients;
+use finite_difference::{
+    channel, correlation, cylindrical, dump, error, flux, input, probe, solver, symmetry,
+    SolverParams,
+};
+use std::path::Path;
 use std::time::Instant;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::EnvFilter;
 
+const DEFAULT_INPUT: &str = "inputs/constant_temperature.json";
+
+/// `finite-difference [input] [output] [--tui]` solves a single input file, `--tui` follows
+/// the solve in a terminal view when built with the `tui` feature
+///
+/// `finite-difference convergence [input] [output directory]` solves it on every grid of
+/// its `convergence` section and summarizes how the results change with resolution
+///
+/// `finite-difference report <results> [--json]` summarizes a saved results file
+///
+/// `finite-difference compare <reduced results> <results>` compares a 1D or 2D solve to a 3D
+/// solve of the same cube
+///
+/// `finite-difference render <results> <image> [options]` draws a slice of a saved results
+/// file to a PNG, or an SVG for paths ending in `.svg`. The options are
+/// `--axis <x|y|z>` normal to the slice (x), `--index <n>` of the slice along it (the middle),
+/// `--step <n>` to draw (the last saved), `--colormap <coolwarm|viridis|inferno|greys>`,
+/// `--isotherms <count>` and `--arrows` for the heat flux
+///
+/// every command logs to stderr, filtered by `RUST_LOG` (`info`, with the solver's progress at
+/// `debug`). `--log-json` logs one JSON object per line instead and `--log-file <path>` writes
+/// the log to a file
 fn main() {
-    let now = Instant::now();
-    let h = 10.;
-    let t_inf = 273. + 25.;
-    let energy_generation = 0.;
-    let thermal_conductivity = 43.;
-
-    let top_boundary = Temperature { temperature: 350. };
-    let bot_boundary = Temperature { temperature: 350. };
-
-    let front_boundary = Convection { h, t_inf };
-    let back_boundary = Convection { h, t_inf };
-
-    let left_boundary = Convection { h, t_inf };
-    let right_boundary = Convection { h, t_inf };
-
-    let setup = setup::SetupConditions {
-        right_boundary,
-        left_boundary,
-        top_boundary,
-        bot_boundary,
-        front_boundary,
-        back_boundary,
-    };
+    let mut args = init_logging(std::env::args().skip(1))
+        .into_iter()
+        .peekable();
+
+    if args.peek().map(String::as_str) == Some("render") {
+        args.next();
+
+        let results_path = args.next().expect("no results file was given to render");
+        let image_path = args.next().expect("no image path was given to render to");
+        let options = RenderOptions::parse(args);
+
+        let results = input::read(&results_path).unwrap();
+
+        match input::precision(&results).unwrap() {
+            Precision::Single => render::<f32>(results, &image_path, options),
+            Precision::Double => render::<f64>(results, &image_path, options),
+        }
+
+        return;
+    }
+
+    if args.peek().map(String::as_str) == Some("report") {
+        args.next();
+
+        let results_path = args.next().expect("no results file was given to report on");
+        let json = args.next().as_deref() == Some("--json");
+
+        let results = input::read(&results_path).unwrap();
+
+        match input::precision(&results).unwrap() {
+            Precision::Single => report::<f32>(results, json),
+            Precision::Double => report::<f64>(results, json),
+        }
+
+        return;
+    }
+
+    if args.peek().map(String::as_str) == Some("compare") {
+        args.next();
+
+        let reduced_path = args
+            .next()
+            .expect("no reduced results file was given to compare");
+        let cube_path = args
+            .next()
+            .expect("no 3D results file was given to compare to");
+
+        let reduced = input::read(&reduced_path).unwrap();
+        let cube = input::read(&cube_path).unwrap();
+
+        match input::precision(&reduced).unwrap() {
+            Precision::Single => compare::<f32>(reduced, cube),
+            Precision::Double => compare::<f64>(reduced, cube),
+        }
+
+        return;
+    }
+
+    if args.peek().map(String::as_str) == Some("convergence") {
+        args.next();
+
+        let input_path = args.next().unwrap_or_else(|| DEFAULT_INPUT.to_string());
+        let output_dir = args.next().unwrap_or_else(|| "convergence".to_string());
+
+        let input = input::read(&input_path).unwrap();
+
+        match input::precision(&input).unwrap() {
+            Precision::Single => study::<f32>(input, Path::new(&output_dir)),
+            Precision::Double => study::<f64>(input, Path::new(&output_dir)),
+        }
+
+        return;
+    }
+
+    let (flags, mut args): (Vec<String>, Vec<String>) = args.partition(|arg| arg == "--tui");
+    let tui = !flags.is_empty();
+    let mut args = args.drain(..);
+
+    let input_path = args.next().unwrap_or_else(|| DEFAULT_INPUT.to_string());
+    let output_path = args.next().unwrap_or_else(|| "results.json".to_string());
+
+    let input = input::read(&input_path).unwrap();
+
+    match input::precision(&input).unwrap() {
+        Precision::Single => run::<f32>(input, &output_path, tui),
+        Precision::Double => run::<f64>(input, &output_path, tui),
+    }
+}
+
+/// Takes the logging flags out of the arguments and installs the subscriber, returning the rest
+fn init_logging(mut args: impl Iterator<Item = String>) -> Vec<String> {
+    let mut json = false;
+    let mut file = None;
+    let mut rest = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--log-json" => json = true,
+            "--log-file" => file = Some(args.next().expect("`--log-file` needs a path")),
+            _ => rest.push(arg),
+        }
+    }
 
-    let bcs = setup.make_boundaries();
+    // the terminal view owns the screen, only warnings are written under it unless the log
+    // goes to a file
+    let tui = rest.iter().any(|arg| arg == "--tui");
+    let default = if tui && file.is_none() {
+        "warn"
+    } else {
+        "info,finite_difference=debug"
+    };
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default));
 
-    let params = SolverParams {
-        len: 0.5,
-        divisions: 100,
-        error_epsilon: 0.0000001,
-        data_steps: 50000,
-        error_steps: 1000,
+    let writer = match &file {
+        Some(path) => {
+            let file = std::fs::File::create(path).unwrap();
+            BoxMakeWriter::new(std::sync::Mutex::new(file))
+        }
+        None => BoxMakeWriter::new(std::io::stderr),
     };
 
-    let div: T = params.divisions as f64;
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(file.is_none())
+        .with_writer(writer);
+
+    if json {
+        subscriber.json().init();
+    } else {
+        subscriber.init();
+    }
+
+    rest
+}
+
+/// Solves the input with the given solver parameters
+fn simulate<T: Float>(
+    input: &input::Input<T>,
+    params: SolverParams<T>,
+    coolant: &Coolant<T>,
+    initial: Option<&Matrix<T>>,
+    observer: impl Observer<T>,
+) -> SimulationResult<T> {
+    let bcs = input.conditions.make_boundaries();
 
     let solver_info = SolverInfo::builder()
-        .k(thermal_conductivity)
-        .q_dot(energy_generation)
-        .del(params.len / div)
+        .k(input.thermal_conductivity)
+        .q_dot(input.energy_generation)
+        .del(params.del())
+        .anisotropy(input.anisotropy())
         .build();
 
-    let error = error::L2Norm;
+    if let Some(orthotropic) = &input.orthotropic {
+        assert!(
+            input.cylinder.is_none() || orthotropic.rotation.is_none(),
+            "the conductivities of a cylinder are along r, θ and z and cannot be rotated"
+        );
+        orthotropic
+            .check()
+            .unwrap_or_else(|error| panic!("{error}"));
+    }
+
+    let periodic =
+        symmetry::periodic(&input.conditions).expect("periodic faces come in opposite pairs");
+    assert!(
+        !periodic.contains(&true)
+            || input.cylinder.is_none() && input.dimensions == Dimensions::Three,
+        "periodic faces are only supported on the full cube"
+    );
+
+    let conditions = &input.conditions;
+    let faces = [
+        conditions.right_boundary,
+        conditions.left_boundary,
+        conditions.top_boundary,
+        conditions.bot_boundary,
+        conditions.front_boundary,
+        conditions.back_boundary,
+    ];
+    let correlated = |condition: &Condition<T>| matches!(condition, Condition::Correlated(_));
+    assert!(
+        !faces.iter().any(correlated)
+            || input.cylinder.is_none() && input.dimensions == Dimensions::Three,
+        "correlated convection is only supported on the faces of the 3D cube"
+    );
+    if let Some(geometry) = &input.geometry {
+        assert!(
+            !geometry.surfaces.values().any(correlated),
+            "correlated convection is only supported on the faces of the 3D cube"
+        );
+    }
 
-    let mut file = std::fs::File::create("results.json").unwrap();
-    let result = solver::solver(solver_info, params, bcs, error);
+    let channel = |condition: &Condition<T>| matches!(condition, Condition::Channel(_));
+    assert!(
+        !faces.iter().any(channel)
+            || input.geometry.is_none()
+                && input.cylinder.is_none()
+                && input.dimensions == Dimensions::Three,
+        "coolant channels are only supported on the faces of the full cube"
+    );
 
-    dbg! {&result};
+    let probes = match &input.probes {
+        Some(sampling) => sampling
+            .probes(params.del(), params.divisions)
+            .expect("a probe point is outside of the cube"),
+        None => Vec::new(),
+    };
+
+    let coefficients = tracing::info_span!("setup", divisions = params.divisions).in_scope(|| {
+        if let Some(cylinder) = &input.cylinder {
+            assert!(
+                input.geometry.is_none()
+                    && input.probes.is_none()
+                    && !input.export_heat_flux
+                    && !input.export_mirrored,
+                "geometry, probes, heat flux and mirrored export are only supported on the cube"
+            );
+
+            return cylindrical::coefficients(
+                cylinder,
+                &bcs,
+                &solver_info,
+                params.divisions,
+                params.sweep,
+            );
+        }
+
+        if input.dimensions != Dimensions::Three {
+            assert!(
+                input.geometry.is_none() && input.probes.is_none() && !input.export_heat_flux,
+                "geometry, probes and heat flux export are only supported in 3D"
+            );
+
+            return dimensions::coefficients(
+                input.dimensions,
+                &bcs,
+                &solver_info,
+                params.divisions,
+                params.sweep,
+            );
+        }
+
+        match &input.geometry {
+            Some(geometry) => {
+                let mask = geometry
+                    .mask(params.divisions, params.del())
+                    .unwrap_or_else(|error| panic!("{error}"));
+
+                Coefficients::masked(&bcs, &solver_info, &mask, params.sweep)
+                    .unwrap_or_else(|error| panic!("{error}"))
+            }
+            None => coolant.constants(
+                Coefficients::new(&bcs, &solver_info, params.divisions, params.sweep)
+                    .unwrap_or_else(|error| panic!("{error}")),
+                &input.conditions,
+                &solver_info,
+                params.divisions,
+            ),
+        }
+    });
+
+    // dispatch once here so the sweep is monomorphised over the chosen norm
+    match input.error_type {
+        ErrorType::InfinityNorm => solver::solve_from(
+            &coefficients,
+            initial,
+            params,
+            &error::InfinityNorm,
+            &probes,
+            observer,
+        ),
+        ErrorType::L1Norm => solver::solve_from(
+            &coefficients,
+            initial,
+            params,
+            &error::L1Norm,
+            &probes,
+            observer,
+        ),
+        ErrorType::L2Norm => solver::solve_from(
+            &coefficients,
+            initial,
+            params,
+            &error::L2Norm,
+            &probes,
+            observer,
+        ),
+    }
+}
+
+/// Solves `input` with `solve` until the coefficients of its correlated faces and the
+/// coolant of its channels settle, returning it with the coefficients the result was solved
+/// with
+///
+/// the coolant carries over between the solves for each set of coefficients, and each solve
+/// but the first starts from the temperatures of the one before
+fn settle<T: Float>(
+    input: &input::Input<T>,
+    params: SolverParams<T>,
+    mut solve: impl FnMut(&input::Input<T>, &Coolant<T>, Option<&Matrix<T>>) -> SimulationResult<T>,
+) -> (input::Input<T>, SimulationResult<T>) {
+    let mut coolant = Coolant::inlet(&input.conditions, params.divisions)
+        .unwrap_or_else(|error| panic!("{error}"));
+
+    let (conditions, result) =
+        correlation::settle(input.conditions, params.len, |conditions, initial| {
+            let input = input::Input {
+                conditions,
+                ..input.clone()
+            };
+
+            channel::settle(
+                &conditions,
+                params.del(),
+                &mut coolant,
+                |coolant, previous| solve(&input, coolant, previous.or(initial)),
+            )
+            .unwrap_or_else(|error| panic!("{error}"))
+        });
+
+    (
+        input::Input {
+            conditions,
+            ..input.clone()
+        },
+        result,
+    )
+}
+
+#[cfg(feature = "tui")]
+fn simulate_in_terminal<T: Float>(
+    input: &input::Input<T>,
+    params: SolverParams<T>,
+    coolant: &Coolant<T>,
+    initial: Option<&Matrix<T>>,
+) -> SimulationResult<T> {
+    finite_difference::tui::run(params, input.error_type, |observer| {
+        simulate(input, params, coolant, initial, observer)
+    })
+}
+
+#[cfg(not(feature = "tui"))]
+fn simulate_in_terminal<T: Float>(
+    _: &input::Input<T>,
+    _: SolverParams<T>,
+    _: &Coolant<T>,
+    _: Option<&Matrix<T>>,
+) -> SimulationResult<T> {
+    panic!("`--tui` needs the `tui` feature, build with `cargo build --release --features tui`")
+}
+
+fn save<T: Float>(
+    path: &Path,
+    input: &input::Input<T>,
+    params: SolverParams<T>,
+    result: SimulationResult<T>,
+) -> Report<T> {
+    let _output = tracing::info_span!("output", path = %path.display()).entered();
+
+    let mut file = std::fs::File::create(path).unwrap();
+
+    let temps = result.temperatures();
+
+    let samples = input.probes.as_ref().map(|sampling| {
+        sampling
+            .sample(&temps, params.del())
+            .expect("a sampled line or plane leaves the cube")
+    });
+
+    let heat_flux = input.export_heat_flux.then(|| {
+        let k = input.conductivity();
+
+        // the materials of a geometry each have their own conductivity
+        match &input.geometry {
+            Some(geometry) => {
+                let mask = geometry
+                    .mask(params.divisions, params.del())
+                    .unwrap_or_else(|error| panic!("{error}"));
+
+                flux::FluxField::with_tensor(&temps, params.del(), |node| mask.tensor(node, k))
+            }
+            None => flux::FluxField::with_tensor(&temps, params.del(), |_| k),
+        }
+    });
+
+    let mirrored = input
+        .export_mirrored
+        .then(|| symmetry::Mirrored::new(&temps, &input.conditions))
+        .flatten();
 
     let save = dump::SaveFile {
         simulation: result,
-        conditions: setup,
+        conditions: input.conditions,
         solver_params: params,
+        precision: T::PRECISION,
+        thermal_conductivity: Some(input.thermal_conductivity),
+        energy_generation: Some(input.energy_generation),
+        samples,
+        heat_flux,
+        geometry: input.geometry.clone(),
+        cylinder: input.cylinder,
+        orthotropic: input.orthotropic,
+        mirrored,
     };
 
     serde_json::to_writer(&mut file, &save).unwrap();
-    let diff = Instant::now() - now;
-    println!("total runtime: {}", diff.as_secs());
+
+    Report::new(&save)
 }
 
-#[derive(serde::Serialize, Clone, Copy)]
-struct SolverParams {
-    len: T,
-    divisions: usize,
-    pub(crate) error_epsilon: T,
-    pub(crate) data_steps: usize,
-    pub(crate) error_steps: usize,
+fn run<T: Float>(input: serde_json::Value, output_path: &str, tui: bool) {
+    let now = Instant::now();
+
+    let input: input::Input<T> = serde_json::from_value(input).unwrap();
+    let params = input.solver_params;
+
+    let (input, result) = if tui {
+        settle(&input, params, |input, coolant, initial| {
+            simulate_in_terminal(input, params, coolant, initial)
+        })
+    } else {
+        settle(&input, params, |input, coolant, initial| {
+            simulate(input, params, coolant, initial, ())
+        })
+    };
+
+    let report = save(Path::new(output_path), &input, params, result);
+    println!("{report}");
+
+    tracing::info!(wall_time = now.elapsed().as_secs_f64(), "finished");
 }
-impl SolverParams {
-    fn div_end(&self) -> usize {
-        self.divisions - 1
+
+fn report<T: Float>(results: serde_json::Value, json: bool) {
+    let results: dump::Results<T> = serde_json::from_value(results)
+        .unwrap_or_else(|error| panic!("not a results file to report on: {error}"));
+    let report = Report::new(&results);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        println!("{report}");
     }
 }
+
+fn compare<T: Float>(reduced: serde_json::Value, cube: serde_json::Value) {
+    let reduced: dump::Results<T> = serde_json::from_value(reduced).unwrap();
+    let cube: dump::Results<T> = serde_json::from_value(cube).unwrap();
+
+    let dimensions = Dimensions::of(reduced.simulation.shape());
+    let comparison = dimensions::compare(
+        &reduced.simulation.temperatures(),
+        &cube.simulation.temperatures(),
+    )
+    .expect("the results are not of the same number of divisions");
+
+    let [x, y, z] = comparison.index;
+    println!(
+        "{dimensions} against 3D: largest difference {:.4e} K at node ({x}, {y}, {z}), mean {:.4e} K",
+        comparison.max.to_f64().unwrap(),
+        comparison.mean.to_f64().unwrap(),
+    );
+}
+
+struct RenderOptions {
+    axis: Axis,
+    index: Option<usize>,
+    step: Option<usize>,
+    style: Style,
+}
+
+impl RenderOptions {
+    fn parse(mut args: impl Iterator<Item = String>) -> Self {
+        let mut options = RenderOptions {
+            axis: Axis::X,
+            index: None,
+            step: None,
+            style: Style::default(),
+        };
+
+        while let Some(flag) = args.next() {
+            let mut value = || {
+                args.next()
+                    .unwrap_or_else(|| panic!("`{flag}` needs a value"))
+            };
+
+            match flag.as_str() {
+                "--axis" => options.axis = value().parse().unwrap(),
+                "--index" => options.index = Some(value().parse().unwrap()),
+                "--step" => options.step = Some(value().parse().unwrap()),
+                "--colormap" => options.style.colormap = value().parse().unwrap(),
+                "--isotherms" => options.style.isotherms = value().parse().unwrap(),
+                "--arrows" => options.style.arrows = true,
+                _ => panic!("unknown render option `{flag}`"),
+            }
+        }
+
+        options
+    }
+}
+
+fn render<T: Float>(results: serde_json::Value, image_path: &str, options: RenderOptions) {
+    let results: dump::Results<T> = serde_json::from_value(results).unwrap();
+    let simulation = &results.simulation;
+
+    let step = match options.step {
+        Some(step) => simulation
+            .step_data
+            .iter()
+            .find(|data| data.step == step)
+            .unwrap_or_else(|| {
+                let saved: Vec<usize> = simulation.step_data.iter().map(|data| data.step).collect();
+                panic!("step {step} was not saved, the saved steps are {saved:?}")
+            }),
+        None => simulation.step_data.last().expect("no steps were saved"),
+    };
+
+    let temps = step.temperatures(simulation.shape());
+    let nodes = simulation.shape()[options.axis.index()];
+    let index = options.index.unwrap_or(nodes / 2);
+
+    let heat_flux = options.style.arrows.then(|| flux::of_step(&results, step));
+    let figure = Figure::slice(
+        &temps,
+        Coordinates::of(&results),
+        heat_flux.as_ref(),
+        options.axis,
+        index,
+        &options.style,
+    )
+    .unwrap_or_else(|| {
+        panic!(
+            "there is no slice {index}, the grid has {nodes} nodes along {:?}",
+            options.axis
+        )
+    });
+
+    figure.save(image_path).unwrap();
+}
+
+fn study<T: Float>(input: serde_json::Value, output_dir: &Path) {
+    let now = Instant::now();
+
+    let input: input::Input<T> = serde_json::from_value(input).unwrap();
+    let len = input.solver_params.len;
+
+    let study = input
+        .convergence
+        .clone()
+        .expect("the input file has no `convergence` section");
+    assert!(
+        input.cylinder.is_none() && input.dimensions == Dimensions::Three,
+        "convergence studies are only supported on the 3D cube"
+    );
+
+    let mut probes = study.probes;
+    if probes.is_empty() {
+        probes.push([len / lit(2.); 3]);
+    }
+
+    // catch probes outside the cube before spending any time solving
+    for probe in &probes {
+        assert!(
+            probe.iter().all(|&x| x >= T::zero() && x <= len),
+            "probe {probe:?} is outside of the cube"
+        );
+    }
+
+    std::fs::create_dir_all(output_dir).unwrap();
+
+    let mut grids = Vec::with_capacity(study.divisions.len());
+
+    for &divisions in &study.divisions {
+        let params = SolverParams {
+            divisions,
+            ..input.solver_params
+        };
+
+        let _grid = tracing::info_span!("grid", divisions).entered();
+
+        let (settled, result) = settle(&input, params, |input, coolant, initial| {
+            simulate(input, params, coolant, initial, ())
+        });
+        let temps = result.temperatures();
+
+        let values = probes
+            .iter()
+            .map(|&point| probe::interpolate(&temps, params.del(), point).unwrap())
+            .collect();
+
+        grids.push(Grid {
+            divisions,
+            del: params.del(),
+            values,
+        });
+
+        save(
+            &output_dir.join(format!("divisions_{divisions}.json")),
+            &settled,
+            params,
+            result,
+        );
+    }
+
+    let summary = Summary::new(&probes, grids);
+
+    println!("{summary}");
+
+    std::fs::write(output_dir.join("summary.txt"), summary.to_string()).unwrap();
+    let file = std::fs::File::create(output_dir.join("summary.json")).unwrap();
+    serde_json::to_writer_pretty(file, &summary).unwrap();
+
+    tracing::info!(wall_time = now.elapsed().as_secs_f64(), "finished");
+}
@@ -0,0 +1,311 @@
+//! Method of manufactured solutions
+//!
+//! an analytic temperature field is chosen up front, the heat generation and boundary data
+//! that make it the exact solution are derived from it, and the error of the solver against
+//! it is measured on a sequence of grids. Every wall, edge and corner stencil is exercised
+//! with whichever kind of condition its faces are given
+//!
+//! nodes are placed at `i * del` for `i` in `0..divisions`, so the cube spans `0..len` along
+//! each axis with `del = len / (divisions - 1)`
+
+use crate::conditions::{Condition, Convection, HeatFlux, Temperature};
+use crate::error::InfinityNorm;
+use crate::input::Conditions;
+use crate::prelude::*;
+use crate::setup::SetupConditions;
+use crate::solver;
+use crate::stencil::{Coefficients, Sweep};
+use crate::SolverParams;
+
+use std::fmt;
+
+/// Kind of boundary condition on a face, its data is derived from the manufactured field
+#[derive(Clone, Copy, Debug)]
+pub enum Face<T> {
+    Temperature,
+    HeatFlux,
+    /// convection with a fixed coefficient `h`, only the ambient temperature is derived
+    Convection {
+        h: T,
+    },
+}
+
+pub type Faces<T> = SetupConditions<Face<T>, Face<T>, Face<T>, Face<T>, Face<T>, Face<T>>;
+
+/// Groups of nodes the error is reported for, by how many faces of the cube they lie on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    Interior,
+    Wall,
+    Edge,
+    Corner,
+}
+
+impl Part {
+    pub const ALL: [Part; 4] = [Part::Interior, Part::Wall, Part::Edge, Part::Corner];
+
+    fn of(index: [usize; 3], divisions: usize) -> Self {
+        let faces = index
+            .iter()
+            .filter(|&&i| i == 0 || i == divisions - 1)
+            .count();
+
+        Part::ALL[faces]
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Part::Interior => "interior",
+            Part::Wall => "wall",
+            Part::Edge => "edge",
+            Part::Corner => "corner",
+        };
+
+        f.pad(name)
+    }
+}
+
+/// A manufactured problem: the field `T(x, y, z)` in `[K]` of position in `[m]`
+pub struct Manufactured<T, Field> {
+    pub field: Field,
+    /// `[W/(mK)]`
+    pub k: T,
+    /// `[m]`
+    pub len: T,
+    pub faces: Faces<T>,
+    /// infinity norm between steps at which each grid is considered converged
+    pub error_epsilon: T,
+}
+
+impl<T, Field> Manufactured<T, Field>
+where
+    T: Float,
+    Field: Fn(f64, f64, f64) -> f64,
+{
+    fn temperature(&self, p: [f64; 3]) -> f64 {
+        (self.field)(p[0], p[1], p[2])
+    }
+
+    /// step of the finite differences used to differentiate the field
+    fn step(&self) -> f64 {
+        self.len.to_f64().unwrap() * 1e-3
+    }
+
+    /// the field at `p` moved by `offset` steps along `axis`
+    fn shifted(&self, p: [f64; 3], axis: usize, offset: f64) -> f64 {
+        let mut p = p;
+        p[axis] += offset * self.step();
+        self.temperature(p)
+    }
+
+    /// fourth order central difference of the first derivative
+    fn gradient(&self, p: [f64; 3]) -> [f64; 3] {
+        let h = self.step();
+
+        [0, 1, 2].map(|axis| {
+            let f = |offset| self.shifted(p, axis, offset);
+            (-f(2.) + 8. * f(1.) - 8. * f(-1.) + f(-2.)) / (12. * h)
+        })
+    }
+
+    /// fourth order central difference of the second derivatives
+    fn laplacian(&self, p: [f64; 3]) -> f64 {
+        let h = self.step();
+
+        [0, 1, 2]
+            .iter()
+            .map(|&axis| {
+                let f = |offset| self.shifted(p, axis, offset);
+                (-f(2.) + 16. * f(1.) - 30. * f(0.) + 16. * f(-1.) - f(-2.)) / (12. * h * h)
+            })
+            .sum()
+    }
+
+    /// Heat generation `[W/m^3]` that balances conduction at `p`
+    pub fn source(&self, p: [f64; 3]) -> T {
+        -self.k * lit(self.laplacian(p))
+    }
+
+    /// Condition on a face with outward `normal` that the field satisfies at `p`
+    fn condition(&self, face: Face<T>, normal: [f64; 3], p: [f64; 3]) -> Condition<T> {
+        let gradient = self.gradient(p);
+        let outward: f64 = (0..3).map(|axis| gradient[axis] * normal[axis]).sum();
+
+        // heat conducted into the domain through the face
+        let heat_in = self.k * lit(outward);
+
+        match face {
+            Face::Temperature => Condition::Temperature(Temperature {
+                temperature: lit(self.temperature(p)),
+            }),
+            Face::HeatFlux => Condition::HeatFlux(HeatFlux { heat_flux: heat_in }),
+            Face::Convection { h } => Condition::Convection(Convection {
+                h,
+                t_inf: lit::<T>(self.temperature(p)) + heat_in / h,
+            }),
+        }
+    }
+
+    /// Boundary conditions for every face, with their data taken at `p`
+    pub fn conditions_at(&self, p: [f64; 3]) -> Conditions<T> {
+        let faces = &self.faces;
+
+        SetupConditions {
+            right_boundary: self.condition(faces.right_boundary, [1., 0., 0.], p),
+            left_boundary: self.condition(faces.left_boundary, [-1., 0., 0.], p),
+            top_boundary: self.condition(faces.top_boundary, [0., 1., 0.], p),
+            bot_boundary: self.condition(faces.bot_boundary, [0., -1., 0.], p),
+            front_boundary: self.condition(faces.front_boundary, [0., 0., 1.], p),
+            back_boundary: self.condition(faces.back_boundary, [0., 0., -1.], p),
+        }
+    }
+
+    /// Solves the problem on a grid of `divisions` nodes per side and measures the error
+    pub fn solve(&self, divisions: usize) -> GridError<T> {
+        let del = self.len / lit((divisions - 1) as f64);
+        let del_f64 = del.to_f64().unwrap();
+        let position = |index: [usize; 3]| index.map(|i| i as f64 * del_f64);
+
+        let s = SolverInfo::builder()
+            .k(self.k)
+            .q_dot(T::zero())
+            .del(del)
+            .build();
+
+        let uniform = self.conditions_at([0.; 3]).make_boundaries();
+
+        let coefficients = Coefficients::new(&uniform, &s, divisions, Sweep::default())
+            .expect("the manufactured faces are not periodic")
+            .with_constants(divisions, |index| {
+                let p = position(index);
+                let s = SolverInfo {
+                    q_dot: self.source(p),
+                    ..s.clone()
+                };
+
+                (self.conditions_at(p).make_boundaries(), s)
+            });
+
+        let params = SolverParams {
+            len: self.len,
+            divisions,
+            error_epsilon: self.error_epsilon,
+            data_steps: usize::MAX,
+            error_steps: usize::MAX,
+            sweep: Sweep::default(),
+            threads: None,
+        };
+
+        let result = solver::solve(&coefficients, params, &InfinityNorm, &[], ());
+        let temps = result.temperatures();
+
+        let mut errors = [T::zero(); 4];
+
+        for ((x, y, z), &temp) in temps.indexed_iter() {
+            let exact = lit::<T>(self.temperature(position([x, y, z])));
+            let part = Part::of([x, y, z], divisions) as usize;
+
+            errors[part] = errors[part].max((temp - exact).abs());
+        }
+
+        GridError {
+            divisions,
+            del,
+            errors,
+            steps: result.num_steps,
+        }
+    }
+
+    /// Solves the problem on every grid in `grids`, from coarsest to finest
+    pub fn convergence(&self, grids: &[usize]) -> Convergence<T> {
+        Convergence {
+            grids: grids
+                .iter()
+                .map(|&divisions| self.solve(divisions))
+                .collect(),
+        }
+    }
+}
+
+/// Largest error of each part of a single grid
+#[derive(Clone, Debug)]
+pub struct GridError<T> {
+    pub divisions: usize,
+    pub del: T,
+    /// indexed by `Part as usize`
+    pub errors: [T; 4],
+    pub steps: usize,
+}
+
+impl<T: Float> GridError<T> {
+    pub fn error(&self, part: Part) -> T {
+        self.errors[part as usize]
+    }
+}
+
+/// Errors on a sequence of grids and the order of accuracy observed between them
+#[derive(Clone, Debug)]
+pub struct Convergence<T> {
+    pub grids: Vec<GridError<T>>,
+}
+
+impl<T: Float> Convergence<T> {
+    /// Observed order of `part` between each pair of successive grids
+    ///
+    /// `None` where the part is reproduced exactly, e.g. nodes on a constant temperature face
+    pub fn orders(&self, part: Part) -> Vec<Option<T>> {
+        self.grids
+            .windows(2)
+            .map(|pair| {
+                let (coarse, fine) = (pair[0].error(part), pair[1].error(part));
+
+                if coarse.is_zero() || fine.is_zero() {
+                    return None;
+                }
+
+                Some((coarse / fine).ln() / (pair[0].del / pair[1].del).ln())
+            })
+            .collect()
+    }
+
+    /// Lowest observed order of `part` over all the grids
+    pub fn min_order(&self, part: Part) -> Option<T> {
+        self.orders(part)
+            .into_iter()
+            .flatten()
+            .reduce(|left, right| left.min(right))
+    }
+}
+
+impl<T: Float> fmt::Display for Convergence<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>9}", "divisions")?;
+        for part in Part::ALL {
+            write!(f, " {:>10}", part)?;
+        }
+        writeln!(f)?;
+
+        for (index, grid) in self.grids.iter().enumerate() {
+            if index > 0 {
+                write!(f, "{:>9}", "order")?;
+                for part in Part::ALL {
+                    match self.orders(part)[index - 1] {
+                        Some(order) => write!(f, " {:>10.3}", order)?,
+                        None => write!(f, " {:>10}", "exact")?,
+                    }
+                }
+                writeln!(f)?;
+            }
+
+            write!(f, "{:>9}", grid.divisions)?;
+            for part in Part::ALL {
+                write!(f, " {:>10.3e}", grid.error(part).to_f64().unwrap())?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
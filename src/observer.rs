@@ -0,0 +1,143 @@
+//! Hooks into a running solve
+//!
+//! the solver hands every [`Observer`] the index of each step, its error and the temperatures
+//! after it, and stops early if the observer asks it to. Observers compose: a pair observes
+//! with both of its members, [`every`] thins the calls out and a [`Cancel`] token stops the
+//! solve once it has been cancelled from any thread
+
+use crate::prelude::*;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// What the solver should do after an observer has seen a step
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    /// stop before the error has reached `error_epsilon`, keeping the latest temperatures
+    Stop,
+}
+
+/// A single step of the solver
+#[derive(Clone, Copy, Debug)]
+pub struct Progress<'a, T> {
+    pub step: usize,
+    /// error between this step and the previous one
+    pub error: T,
+    /// temperatures after the step
+    pub temperatures: &'a Matrix<T>,
+}
+
+/// Called by the solver after every step
+///
+/// observers run on the thread driving the solve, between sweeps, so anything slow they do
+/// holds the solve up
+pub trait Observer<T>: Send {
+    fn observe(&mut self, progress: Progress<'_, T>) -> Control;
+}
+
+/// Observes nothing
+impl<T> Observer<T> for () {
+    fn observe(&mut self, _: Progress<'_, T>) -> Control {
+        Control::Continue
+    }
+}
+
+impl<T, O: Observer<T> + ?Sized> Observer<T> for &mut O {
+    fn observe(&mut self, progress: Progress<'_, T>) -> Control {
+        (**self).observe(progress)
+    }
+}
+
+impl<T, O: Observer<T> + ?Sized> Observer<T> for Box<O> {
+    fn observe(&mut self, progress: Progress<'_, T>) -> Control {
+        (**self).observe(progress)
+    }
+}
+
+/// Both observers see every step, the solve stops if either asks it to
+impl<T: Copy, A: Observer<T>, B: Observer<T>> Observer<T> for (A, B) {
+    fn observe(&mut self, progress: Progress<'_, T>) -> Control {
+        let first = self.0.observe(progress);
+        let second = self.1.observe(progress);
+
+        if first == Control::Stop || second == Control::Stop {
+            Control::Stop
+        } else {
+            Control::Continue
+        }
+    }
+}
+
+/// An observer calling a closure
+pub struct FromFn<F>(F);
+
+pub fn from_fn<T, F>(observe: F) -> FromFn<F>
+where
+    F: FnMut(Progress<'_, T>) -> Control + Send,
+{
+    FromFn(observe)
+}
+
+impl<T, F> Observer<T> for FromFn<F>
+where
+    F: FnMut(Progress<'_, T>) -> Control + Send,
+{
+    fn observe(&mut self, progress: Progress<'_, T>) -> Control {
+        (self.0)(progress)
+    }
+}
+
+/// An observer only shown every `interval` steps, starting with the first
+pub struct Every<O> {
+    interval: usize,
+    observer: O,
+}
+
+pub fn every<O>(interval: usize, observer: O) -> Every<O> {
+    Every {
+        interval: interval.max(1),
+        observer,
+    }
+}
+
+impl<T, O: Observer<T>> Observer<T> for Every<O> {
+    fn observe(&mut self, progress: Progress<'_, T>) -> Control {
+        if progress.step.is_multiple_of(self.interval) {
+            self.observer.observe(progress)
+        } else {
+            Control::Continue
+        }
+    }
+}
+
+/// Stops a solve from another thread, clones share the same flag
+///
+/// the solver checks it after every step, so cancelling takes effect once the sweep in
+/// progress has finished
+#[derive(Clone, Debug, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> Observer<T> for Cancel {
+    fn observe(&mut self, _: Progress<'_, T>) -> Control {
+        if self.is_cancelled() {
+            Control::Stop
+        } else {
+            Control::Continue
+        }
+    }
+}
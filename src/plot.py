@@ -38,6 +38,21 @@ def error_plot(error_information, error_name, collection_rate):
 
     plt.savefig(f"figures/error.png")
 
+def probe_plot(probes):
+    plt.cla()
+    plt.clf()
+
+    for probe in probes:
+        x, y, z = probe["point"]
+        plt.plot(probe["temperatures"], label=f"({x}, {y}, {z})")
+
+    plt.xlabel("Step")
+    plt.ylabel("Temperature [K]")
+    plt.title("Probe temperatures")
+    plt.legend()
+
+    plt.savefig(f"figures/probes.png")
+
 def load_data(path):
     with open(path, 'r') as file:
         raw_data = file.read()
@@ -48,11 +63,12 @@ if __name__ == "__main__":
     all_data = load_data("results.json")
     data =all_data["simulation"]
 
-    arr = np.asarray(data["step_data"][-1]["data"])
+    # nodes outside of a masked geometry are saved as null
+    arr = np.asarray(data["step_data"][-1]["data"], dtype=float)
     size = data["size"]
     arr = arr.reshape((size, size, size))
-    arr_min = arr.min()
-    arr_max = arr.max() 
+    arr_min = np.nanmin(arr)
+    arr_max = np.nanmax(arr)
     print(f"min {arr_min} max {arr_max}")
 
     for i in range(0, size):
@@ -67,3 +83,7 @@ if __name__ == "__main__":
     print(error_collection_rate)
 
     error_plot(error_info, f"{error_name} value", error_collection_rate)
+
+    # plot how each probe converged, if any were tracked
+    if "probes" in data:
+        probe_plot(data["probes"])
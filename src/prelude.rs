@@ -1,7 +1,47 @@
-pub type T = f64;
+/// Floating point type the solver is generic over
+///
+/// implemented for `f32` and `f64`, the choice is made at run time from the
+/// `precision` field of the input file
+pub trait Float:
+    num_traits::Float
+    + num_traits::FromPrimitive
+    + std::iter::Sum
+    + std::fmt::Debug
+    + std::fmt::Display
+    + serde::Serialize
+    + serde::de::DeserializeOwned
+    + Send
+    + Sync
+    + 'static
+{
+    const PRECISION: Precision;
+}
+
+impl Float for f32 {
+    const PRECISION: Precision = Precision::Single;
+}
+
+impl Float for f64 {
+    const PRECISION: Precision = Precision::Double;
+}
 
-pub trait CalculateTemperature {
-    fn calculate_temperature(&self, info: Information, solver: &SolverInfo) -> T;
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Precision {
+    /// `f32`
+    Single,
+    /// `f64`
+    #[default]
+    Double,
+}
+
+/// Converts an `f64` constant into the working precision
+pub fn lit<T: Float>(value: f64) -> T {
+    T::from_f64(value).unwrap()
+}
+
+pub trait CalculateTemperature<T: Float> {
+    fn calculate_temperature(&self, info: Information<T>, solver: &SolverInfo<T>) -> T;
 }
 
 pub use crate::conditions::BoundaryCondition;
@@ -11,35 +51,48 @@ pub use crate::conditions::BoundaryCondition;
 /// x_front denotes a temperature at x + 1
 /// x_back denotes a temperature at x - 1
 #[derive(typed_builder::TypedBuilder)]
-pub struct Information {
-    pub(crate) i_front: T,
-    pub(crate) i_back: T,
-    pub(crate) j_front: T,
-    pub(crate) j_back: T,
-    pub(crate) k_front: T,
-    pub(crate) k_back: T,
+pub struct Information<T: Float> {
+    pub i_front: T,
+    pub i_back: T,
+    pub j_front: T,
+    pub j_back: T,
+    pub k_front: T,
+    pub k_back: T,
 }
 
 #[derive(typed_builder::TypedBuilder, Clone)]
-pub struct SolverInfo {
-    pub(crate) k: T,
-    pub(crate) q_dot: T,
-    pub(crate) del: T,
+pub struct SolverInfo<T: Float> {
+    pub k: T,
+    pub q_dot: T,
+    pub del: T,
+    /// conductivity along x, y and z relative to `k`, which every term of a stencil and the
+    /// boundary conditions' constants are divided by
+    #[builder(default = [T::one(); 3])]
+    pub anisotropy: [T; 3],
 }
-impl SolverInfo {
-    pub(crate) fn del2(&self) -> T {
+impl<T: Float> SolverInfo<T> {
+    pub fn del2(&self) -> T {
         self.del * self.del
     }
 }
 
-pub(crate) trait CalculateError {
-    fn calculate_error(&self, previous_step: &Matrix, current_step: &Matrix) -> T;
+/// Error norm between two successive steps, evaluated as a reduction over the
+/// per-node temperature differences so it can be fused into the parallel sweep
+///
+/// Every partial total starts at zero, is built with `accumulate`, merged across
+/// threads with `combine`, and turned into the final norm with `finish`
+pub trait CalculateError<T: Float> {
+    fn accumulate(&self, total: T, difference: T) -> T;
+    fn combine(&self, left: T, right: T) -> T;
+    fn finish(&self, total: T) -> T {
+        total
+    }
     fn to_error_type(&self) -> crate::dump::ErrorType;
 }
 
-pub type Matrix = ndarray::Array3<T>;
+pub type Matrix<T> = ndarray::Array3<T>;
 
-pub(crate) fn max_temp<ITER, ITEM>(matrix: ITER) -> ITEM
+pub fn max_temp<ITER, ITEM>(matrix: ITER) -> ITEM
 where
     ITER: Iterator<Item = ITEM>,
     ITEM: PartialOrd<ITEM>,
@@ -48,7 +101,7 @@ where
         .max_by(|left, right| left.partial_cmp(right).unwrap())
         .unwrap()
 }
-pub(crate) fn min_temp<ITER, ITEM>(matrix: ITER) -> ITEM
+pub fn min_temp<ITER, ITEM>(matrix: ITER) -> ITEM
 where
     ITER: Iterator<Item = ITEM>,
     ITEM: PartialOrd<ITEM>,
@@ -0,0 +1,291 @@
+//! Sampling of the temperature field between nodes
+//!
+//! node `(i, j, k)` sits at `(i, j, k) * del`, so the field is defined on `0..=(n - 1) * del`
+//! along each axis and is interpolated trilinearly between the nodes
+
+use crate::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+/// A point of the cube, with the nodes and weights its temperature is interpolated from
+#[derive(Clone, Debug)]
+pub struct Probe<T> {
+    pub point: [T; 3],
+    nodes: [[usize; 3]; 8],
+    weights: [T; 8],
+}
+
+impl<T: Float> Probe<T> {
+    /// Returns `None` for points outside of a grid of `shape` nodes spaced `del` apart
+    pub fn new(point: [T; 3], del: T, shape: [usize; 3]) -> Option<Self> {
+        let mut lower = [0; 3];
+        let mut fraction = [T::zero(); 3];
+
+        for axis in 0..3 {
+            let end = shape[axis] - 1;
+            let last = lit::<T>(end as f64);
+            let position = point[axis] / del;
+
+            // points on the faces of the cube should not be lost to rounding in the division
+            let tolerance = lit::<T>(1e-9);
+
+            if !(position >= -tolerance && position <= last + tolerance) {
+                return None;
+            }
+
+            let position = position.max(T::zero()).min(last);
+
+            // the last cell also owns its upper face
+            let cell = position
+                .floor()
+                .to_usize()
+                .unwrap()
+                .min(end.saturating_sub(1));
+
+            lower[axis] = cell;
+            fraction[axis] = position - lit(cell as f64);
+        }
+
+        let mut nodes = [lower; 8];
+        let mut weights = [T::one(); 8];
+
+        for corner in 0..8 {
+            for axis in 0..3 {
+                let upper = (corner >> axis) & 1 == 1;
+
+                if upper {
+                    nodes[corner][axis] = (lower[axis] + 1).min(shape[axis] - 1);
+                    weights[corner] = weights[corner] * fraction[axis];
+                } else {
+                    weights[corner] = weights[corner] * (T::one() - fraction[axis]);
+                }
+            }
+        }
+
+        Some(Probe {
+            point,
+            nodes,
+            weights,
+        })
+    }
+
+    /// Temperature at the probe
+    pub fn sample(&self, temps: &Matrix<T>) -> T {
+        self.nodes
+            .iter()
+            .zip(self.weights.iter())
+            .fold(T::zero(), |total, (&node, &weight)| {
+                total + weight * temps[node]
+            })
+    }
+}
+
+fn shape<T>(temps: &Matrix<T>) -> [usize; 3] {
+    let shape = temps.shape();
+    [shape[0], shape[1], shape[2]]
+}
+
+/// Temperature at `point` `[m]`, `None` for points outside of the cube
+pub fn interpolate<T: Float>(temps: &Matrix<T>, del: T, point: [T; 3]) -> Option<T> {
+    Probe::new(point, del, shape(temps)).map(|probe| probe.sample(temps))
+}
+
+/// Temperature at a single point
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(bound(deserialize = "T: Float"))]
+pub struct Sample<T> {
+    pub point: [T; 3],
+    #[serde(deserialize_with = "crate::dump::nullable")]
+    pub temperature: T,
+}
+
+/// `samples` evenly spaced temperatures from `start` to `end` `[m]`, both included
+///
+/// `None` if either end is outside of the cube
+pub fn line<T: Float>(
+    temps: &Matrix<T>,
+    del: T,
+    start: [T; 3],
+    end: [T; 3],
+    samples: usize,
+) -> Option<Vec<Sample<T>>> {
+    let intervals = lit::<T>(samples.saturating_sub(1).max(1) as f64);
+
+    (0..samples)
+        .map(|index| {
+            let t = lit::<T>(index as f64) / intervals;
+            let point = [0, 1, 2].map(|axis| start[axis] + (end[axis] - start[axis]) * t);
+
+            interpolate(temps, del, point).map(|temperature| Sample { point, temperature })
+        })
+        .collect()
+}
+
+/// Axis normal to a sampling plane
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    pub fn index(self) -> usize {
+        match self {
+            Axis::X => 0,
+            Axis::Y => 1,
+            Axis::Z => 2,
+        }
+    }
+
+    /// the two axes spanning the plane normal to this one, in order
+    pub fn in_plane(self) -> [usize; 2] {
+        match self {
+            Axis::X => [1, 2],
+            Axis::Y => [0, 2],
+            Axis::Z => [0, 1],
+        }
+    }
+}
+
+impl std::str::FromStr for Axis {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "x" => Ok(Axis::X),
+            "y" => Ok(Axis::Y),
+            "z" => Ok(Axis::Z),
+            _ => Err(format!("`{s}` is not an axis, expected x, y or z")),
+        }
+    }
+}
+
+/// Temperatures on a regular grid covering a cross section of the cube
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(bound(deserialize = "T: Float"))]
+pub struct Plane<T> {
+    pub axis: Axis,
+    /// distance along `axis` `[m]`
+    pub position: T,
+    /// coordinates `[m]` along the first and second in plane axes, (y, z) for a plane
+    /// normal to x, (x, z) normal to y and (x, y) normal to z
+    pub u: Vec<T>,
+    pub v: Vec<T>,
+    /// `temperatures[i][j]` is at `(u[i], v[j])`
+    #[serde(deserialize_with = "crate::dump::nullable")]
+    pub temperatures: Vec<Vec<T>>,
+}
+
+/// Samples the cross section at `position` `[m]` along `axis`, with `samples` points along
+/// each of the in plane axes
+///
+/// `None` if `position` is outside of the cube
+pub fn plane<T: Float>(
+    temps: &Matrix<T>,
+    del: T,
+    axis: Axis,
+    position: T,
+    samples: [usize; 2],
+) -> Option<Plane<T>> {
+    let shape = shape(temps);
+    let [first, second] = axis.in_plane();
+
+    let coordinates = |in_plane: usize, samples: usize| -> Vec<T> {
+        let len = del * lit((shape[in_plane] - 1) as f64);
+        let intervals = lit::<T>(samples.saturating_sub(1).max(1) as f64);
+
+        (0..samples)
+            .map(|index| len * lit(index as f64) / intervals)
+            .collect()
+    };
+
+    let u = coordinates(first, samples[0]);
+    let v = coordinates(second, samples[1]);
+
+    let temperatures = u
+        .iter()
+        .map(|&u| {
+            v.iter()
+                .map(|&v| {
+                    let mut point = [T::zero(); 3];
+                    point[axis.index()] = position;
+                    point[first] = u;
+                    point[second] = v;
+
+                    interpolate(temps, del, point)
+                })
+                .collect::<Option<Vec<T>>>()
+        })
+        .collect::<Option<Vec<Vec<T>>>>()?;
+
+    Some(Plane {
+        axis,
+        position,
+        u,
+        v,
+        temperatures,
+    })
+}
+
+/// What to sample, the `probes` field of an input file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Sampling<T> {
+    /// positions `[m]` whose temperatures are recorded after every step of the solver
+    #[serde(default = "Vec::new")]
+    pub points: Vec<[T; 3]>,
+    #[serde(default = "Vec::new")]
+    pub lines: Vec<LineParams<T>>,
+    #[serde(default = "Vec::new")]
+    pub planes: Vec<PlaneParams<T>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct LineParams<T> {
+    pub start: [T; 3],
+    pub end: [T; 3],
+    pub samples: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct PlaneParams<T> {
+    pub axis: Axis,
+    pub position: T,
+    pub samples: [usize; 2],
+}
+
+/// Lines and planes sampled from the final temperatures, saved with the results
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(bound(deserialize = "T: Float"))]
+pub struct Samples<T> {
+    pub lines: Vec<Vec<Sample<T>>>,
+    pub planes: Vec<Plane<T>>,
+}
+
+impl<T: Float> Sampling<T> {
+    /// Probes for every point, `None` if any of them is outside of the cube
+    pub fn probes(&self, del: T, divisions: usize) -> Option<Vec<Probe<T>>> {
+        self.points
+            .iter()
+            .map(|&point| Probe::new(point, del, [divisions; 3]))
+            .collect()
+    }
+
+    /// Samples every line and plane, `None` if any of them leaves the cube
+    pub fn sample(&self, temps: &Matrix<T>, del: T) -> Option<Samples<T>> {
+        let lines = self
+            .lines
+            .iter()
+            .map(|l| line(temps, del, l.start, l.end, l.samples))
+            .collect::<Option<_>>()?;
+
+        let planes = self
+            .planes
+            .iter()
+            .map(|p| plane(temps, del, p.axis, p.position, p.samples))
+            .collect::<Option<_>>()?;
+
+        Some(Samples { lines, planes })
+    }
+}
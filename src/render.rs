@@ -0,0 +1,810 @@
+//! Images of axis aligned slices through a temperature field, written as PNG or SVG
+//!
+//! every node of the slice is drawn as a square cell coloured by its temperature, with the
+//! first in plane axis of the slice (see [`Axis::in_plane`]) to the right and the second
+//! upwards. A figure is laid out once as a list of shapes, which is then either rasterised or
+//! written out as SVG elements so both formats show the same picture
+
+use crate::cylindrical::Cylinder;
+use crate::dump::Results;
+use crate::prelude::*;
+use crate::probe::Axis;
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+
+pub type Rgb = [u8; 3];
+
+const BLACK: Rgb = [0, 0, 0];
+const WHITE: Rgb = [255, 255, 255];
+
+/// space around the figure `[px]`
+const MARGIN: f64 = 16.;
+/// height of the title above the slice `[px]`
+const TITLE: f64 = 32.;
+const BAR_GAP: f64 = 24.;
+const BAR_WIDTH: f64 = 18.;
+const TICKS: usize = 5;
+
+/// every glyph is 5 by 7 font pixels, drawn `FONT_SCALE` image pixels wide
+const FONT_SCALE: f64 = 2.;
+const CHAR_WIDTH: f64 = 6. * FONT_SCALE;
+const CHAR_HEIGHT: f64 = 7. * FONT_SCALE;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Colormap {
+    /// diverging blue to red, the map used by `src/plot.py`
+    #[default]
+    Coolwarm,
+    Viridis,
+    Inferno,
+    Greys,
+}
+
+impl Colormap {
+    /// evenly spaced colours, linearly interpolated between
+    fn stops(self) -> &'static [Rgb] {
+        match self {
+            Colormap::Coolwarm => &[
+                [59, 76, 192],
+                [98, 130, 234],
+                [141, 176, 254],
+                [184, 208, 249],
+                [221, 221, 221],
+                [245, 196, 173],
+                [244, 154, 123],
+                [222, 96, 77],
+                [180, 4, 38],
+            ],
+            Colormap::Viridis => &[
+                [68, 1, 84],
+                [71, 44, 122],
+                [59, 81, 139],
+                [44, 113, 142],
+                [33, 144, 141],
+                [39, 173, 129],
+                [92, 200, 99],
+                [170, 220, 50],
+                [253, 231, 37],
+            ],
+            Colormap::Inferno => &[
+                [0, 0, 4],
+                [31, 12, 72],
+                [85, 15, 109],
+                [136, 34, 106],
+                [186, 54, 85],
+                [227, 89, 51],
+                [249, 140, 10],
+                [249, 201, 50],
+                [252, 255, 164],
+            ],
+            Colormap::Greys => &[BLACK, WHITE],
+        }
+    }
+
+    /// Colour `fraction` of the way along the map, clamped to `0..=1`
+    pub fn color(self, fraction: f64) -> Rgb {
+        let stops = self.stops();
+        let position = fraction.clamp(0., 1.) * (stops.len() - 1) as f64;
+
+        let lower = (position.floor() as usize).min(stops.len() - 2);
+        let t = position - lower as f64;
+        let (a, b) = (stops[lower], stops[lower + 1]);
+
+        [0, 1, 2].map(|c| (a[c] as f64 + (b[c] as f64 - a[c] as f64) * t).round() as u8)
+    }
+}
+
+impl std::str::FromStr for Colormap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "coolwarm" => Ok(Colormap::Coolwarm),
+            "viridis" => Ok(Colormap::Viridis),
+            "inferno" => Ok(Colormap::Inferno),
+            "greys" => Ok(Colormap::Greys),
+            _ => Err(format!(
+                "`{s}` is not a colour map, expected coolwarm, viridis, inferno or greys"
+            )),
+        }
+    }
+}
+
+/// How a slice is drawn
+#[derive(typed_builder::TypedBuilder, Clone, Copy, Debug)]
+pub struct Style {
+    #[builder(default)]
+    pub colormap: Colormap,
+    /// width and height of the slice `[px]`, rounded down to a whole number of pixels per node
+    #[builder(default = 400)]
+    pub size: usize,
+    /// temperatures `[K]` at the ends of the colour map, the range of the slice when `None`
+    #[builder(default, setter(strip_option))]
+    pub range: Option<[f64; 2]>,
+    /// number of evenly spaced isotherms drawn between the ends of the colour map
+    #[builder(default)]
+    pub isotherms: usize,
+    /// draw the in plane heat flux given with the slice as arrows, scaled to the largest of them
+    #[builder(default)]
+    pub arrows: bool,
+    /// nodes between arrows along each axis
+    #[builder(default = 4)]
+    pub arrow_spacing: usize,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style::builder().build()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Anchor {
+    Start,
+    Middle,
+}
+
+#[derive(Clone, Debug)]
+enum Shape {
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        color: Rgb,
+    },
+    Line {
+        from: [f64; 2],
+        to: [f64; 2],
+        width: f64,
+        color: Rgb,
+    },
+    /// `at` is the vertical centre of the text, at its start or middle
+    Text {
+        at: [f64; 2],
+        text: String,
+        anchor: Anchor,
+    },
+}
+
+/// Where the nodes of a slice lie, for its title
+#[derive(Clone, Copy, Debug)]
+pub enum Coordinates<T> {
+    /// a cube's, `del` apart along every axis `[m]`
+    Cube(T),
+    /// `(r, θ, z)` of a cylinder with `divisions` nodes along its sides
+    Cylinder(Cylinder<T>, usize),
+}
+
+impl<T: Float> Coordinates<T> {
+    /// The grid the temperatures of `results` were solved on
+    pub fn of(results: &Results<T>) -> Self {
+        match results.cylinder {
+            Some(cylinder) => Coordinates::Cylinder(cylinder, results.simulation.size),
+            None => Coordinates::Cube(results.solver_params.del()),
+        }
+    }
+
+    /// The axis and position of the nodes `index` along it
+    fn label(self, axis: Axis, index: usize) -> String {
+        let position = |del: T| (del * lit(index as f64)).to_f64().unwrap();
+
+        match self {
+            Coordinates::Cube(del) => {
+                let name = ["x", "y", "z"][axis.index()];
+                format!("{name} = {:.4} m", position(del))
+            }
+            Coordinates::Cylinder(cylinder, divisions) => {
+                let spacing = cylinder.spacing(divisions);
+
+                match axis {
+                    Axis::X => format!(
+                        "r = {:.4} m",
+                        position(spacing[0]) + cylinder.inner_radius.to_f64().unwrap()
+                    ),
+                    Axis::Y => format!("θ = {:.1}°", position(spacing[1]).to_degrees()),
+                    Axis::Z => format!("z = {:.4} m", position(spacing[2])),
+                }
+            }
+        }
+    }
+}
+
+/// A laid out image, `[px]` with the origin at the top left
+#[derive(Clone, Debug)]
+pub struct Figure {
+    width: usize,
+    height: usize,
+    /// `[x, y, width, height]` of the slice itself
+    plot: [f64; 4],
+    shapes: Vec<Shape>,
+}
+
+impl Figure {
+    /// Draws the slice of nodes `index` along `axis`, `None` if there is no such slice
+    ///
+    /// `heat_flux` is along each axis of the grid at every node, drawn as arrows when the
+    /// style asks for them
+    pub fn slice<T: Float>(
+        temps: &Matrix<T>,
+        coordinates: Coordinates<T>,
+        heat_flux: Option<&[Matrix<T>; 3]>,
+        axis: Axis,
+        index: usize,
+        style: &Style,
+    ) -> Option<Self> {
+        let along = ndarray::Axis(axis.index());
+
+        if index >= temps.len_of(along) {
+            return None;
+        }
+
+        let values = temps
+            .index_axis(along, index)
+            .mapv(|temp| temp.to_f64().unwrap());
+        let (nu, nv) = values.dim();
+
+        let [low, high] = style.range.unwrap_or_else(|| {
+            values
+                .iter()
+                .fold([f64::INFINITY, f64::NEG_INFINITY], |[lo, hi], &t| {
+                    [lo.min(t), hi.max(t)]
+                })
+        });
+        // a uniform slice is drawn in the colour of the bottom of the map
+        let span = if high > low { high - low } else { 1. };
+
+        let cell = (style.size / nu.max(nv)).max(1) as f64;
+        let plot = [MARGIN, MARGIN + TITLE, nu as f64 * cell, nv as f64 * cell];
+        let centre = |i: f64, j: f64| {
+            [
+                plot[0] + (i + 0.5) * cell,
+                plot[1] + plot[3] - (j + 0.5) * cell,
+            ]
+        };
+
+        let decimals = (2. - span.log10().floor()).clamp(0., 6.) as usize;
+        let labels: Vec<(f64, String)> = (0..TICKS)
+            .map(|tick| {
+                let fraction = tick as f64 / (TICKS - 1) as f64;
+                (fraction, format!("{:.*}", decimals, low + span * fraction))
+            })
+            .collect();
+        let label_width = labels
+            .iter()
+            .map(|(_, label)| label.len())
+            .max()
+            .unwrap_or(0) as f64
+            * CHAR_WIDTH;
+
+        let bar = plot[0] + plot[2] + BAR_GAP;
+        let width = (bar + BAR_WIDTH + 8. + label_width + MARGIN).ceil() as usize;
+        let height = (plot[1] + plot[3] + MARGIN).ceil() as usize;
+
+        let mut shapes = vec![Shape::Rect {
+            x: 0.,
+            y: 0.,
+            width: width as f64,
+            height: height as f64,
+            color: WHITE,
+        }];
+
+        shapes.push(Shape::Text {
+            at: [plot[0], MARGIN + CHAR_HEIGHT / 2.],
+            text: coordinates.label(axis, index),
+            anchor: Anchor::Start,
+        });
+
+        // nodes outside of a masked geometry are left blank
+        for ((i, j), &temp) in values.indexed_iter().filter(|(_, temp)| !temp.is_nan()) {
+            shapes.push(Shape::Rect {
+                x: plot[0] + i as f64 * cell,
+                y: plot[1] + plot[3] - (j + 1) as f64 * cell,
+                width: cell,
+                height: cell,
+                color: style.colormap.color((temp - low) / span),
+            });
+        }
+
+        for level in 1..=style.isotherms {
+            let level = low + span * level as f64 / (style.isotherms + 1) as f64;
+
+            for [from, to] in isotherm(&values, level) {
+                shapes.push(Shape::Line {
+                    from: centre(from[0], from[1]),
+                    to: centre(to[0], to[1]),
+                    width: 1.,
+                    color: BLACK,
+                });
+            }
+        }
+
+        if let (true, Some(heat_flux)) = (style.arrows, heat_flux) {
+            let [first, second] = axis.in_plane();
+            let component = |in_plane: usize| {
+                heat_flux[in_plane]
+                    .index_axis(along, index)
+                    .mapv(|flux| flux.to_f64().unwrap())
+            };
+
+            shapes.extend(arrows(
+                &component(first),
+                &component(second),
+                style.arrow_spacing.max(1),
+                cell,
+                plot,
+                centre,
+            ));
+        }
+
+        // colour bar, hottest at the top
+        let strips = plot[3].round().max(1.) as usize;
+        for strip in 0..strips {
+            let fraction = 1. - (strip as f64 + 0.5) / strips as f64;
+
+            shapes.push(Shape::Rect {
+                x: bar,
+                y: plot[1] + plot[3] * strip as f64 / strips as f64,
+                width: BAR_WIDTH,
+                height: plot[3] / strips as f64,
+                color: style.colormap.color(fraction),
+            });
+        }
+
+        let (top, bottom, right) = (plot[1], plot[1] + plot[3], bar + BAR_WIDTH);
+        for [from, to] in [
+            [[bar, top], [right, top]],
+            [[right, top], [right, bottom]],
+            [[right, bottom], [bar, bottom]],
+            [[bar, bottom], [bar, top]],
+        ] {
+            shapes.push(Shape::Line {
+                from,
+                to,
+                width: 1.,
+                color: BLACK,
+            });
+        }
+
+        for (fraction, label) in labels {
+            let y = bottom - plot[3] * fraction;
+
+            shapes.push(Shape::Line {
+                from: [right, y],
+                to: [right + 4., y],
+                width: 1.,
+                color: BLACK,
+            });
+            shapes.push(Shape::Text {
+                at: [right + 8., y],
+                text: label,
+                anchor: Anchor::Start,
+            });
+        }
+
+        shapes.push(Shape::Text {
+            at: [bar + BAR_WIDTH / 2., top - CHAR_HEIGHT / 2. - 8.],
+            text: "[K]".to_string(),
+            anchor: Anchor::Middle,
+        });
+
+        Some(Figure {
+            width,
+            height,
+            plot,
+            shapes,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// `[x, y, width, height]` of the slice itself `[px]`
+    pub fn plot(&self) -> [f64; 4] {
+        self.plot
+    }
+
+    pub fn rasterise(&self) -> Raster {
+        let mut raster = Raster {
+            width: self.width,
+            height: self.height,
+            pixels: vec![WHITE; self.width * self.height],
+        };
+
+        for shape in &self.shapes {
+            match shape {
+                &Shape::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                    color,
+                } => raster.rect(x, y, width, height, color),
+                &Shape::Line {
+                    from,
+                    to,
+                    width,
+                    color,
+                } => raster.line(from, to, width, color),
+                Shape::Text { at, text, anchor } => raster.text(*at, text, *anchor),
+            }
+        }
+
+        raster
+    }
+
+    pub fn to_svg(&self) -> String {
+        let mut svg = String::new();
+        let hex = |c: Rgb| format!("#{:02x}{:02x}{:02x}", c[0], c[1], c[2]);
+
+        // cells are drawn edge to edge, antialiasing them would leave seams between them
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" shape-rendering="crispEdges">"#,
+            w = self.width,
+            h = self.height
+        )
+        .unwrap();
+
+        for shape in &self.shapes {
+            match shape {
+                Shape::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                    color,
+                } => writeln!(
+                    svg,
+                    r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="{}"/>"#,
+                    hex(*color)
+                ),
+                Shape::Line {
+                    from,
+                    to,
+                    width,
+                    color,
+                } => writeln!(
+                    svg,
+                    r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{width}" stroke-linecap="round" shape-rendering="geometricPrecision"/>"#,
+                    from[0],
+                    from[1],
+                    to[0],
+                    to[1],
+                    hex(*color)
+                ),
+                Shape::Text { at, text, anchor } => {
+                    let anchor = match anchor {
+                        Anchor::Start => "start",
+                        Anchor::Middle => "middle",
+                    };
+                    let text = text
+                        .replace('&', "&amp;")
+                        .replace('<', "&lt;")
+                        .replace('>', "&gt;");
+
+                    writeln!(
+                        svg,
+                        r#"<text x="{}" y="{}" font-family="monospace" font-size="{CHAR_HEIGHT}" dominant-baseline="middle" text-anchor="{anchor}">{text}</text>"#,
+                        at[0], at[1]
+                    )
+                }
+            }
+            .unwrap();
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Writes an SVG for paths ending in `.svg` and a PNG otherwise
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+
+        if path.extension().is_some_and(|extension| extension == "svg") {
+            std::fs::write(path, self.to_svg())
+        } else {
+            let file = std::fs::File::create(path)?;
+            self.rasterise().write_png(io::BufWriter::new(file))
+        }
+    }
+}
+
+/// Segments of the contour at `level`, in fractional node indices, by marching squares
+fn isotherm(values: &ndarray::Array2<f64>, level: f64) -> Vec<[[f64; 2]; 2]> {
+    let (nu, nv) = values.dim();
+    let mut segments = Vec::new();
+
+    for i in 0..nu.saturating_sub(1) {
+        for j in 0..nv.saturating_sub(1) {
+            // counter clockwise from the lower left, edge `e` runs from corner `e` to `e + 1`
+            let corners = [[i, j], [i + 1, j], [i + 1, j + 1], [i, j + 1]];
+            let temps = corners.map(|[u, v]| values[[u, v]]);
+
+            if temps.iter().any(|temp| temp.is_nan()) {
+                continue;
+            }
+
+            let above = temps.map(|temp| temp >= level);
+
+            let crossing = |edge: usize| {
+                let (a, b) = (edge, (edge + 1) % 4);
+                let t = (level - temps[a]) / (temps[b] - temps[a]);
+
+                [0, 1].map(|c| {
+                    corners[a][c] as f64 + (corners[b][c] as f64 - corners[a][c] as f64) * t
+                })
+            };
+
+            let edges: Vec<usize> = (0..4).filter(|&e| above[e] != above[(e + 1) % 4]).collect();
+
+            match edges.len() {
+                2 => segments.push([crossing(edges[0]), crossing(edges[1])]),
+                4 => {
+                    // a saddle, resolved by the mean of the cell
+                    let mean = temps.iter().sum::<f64>() / 4.;
+
+                    let pairs = if (mean >= level) == above[0] {
+                        [[0, 1], [2, 3]]
+                    } else {
+                        [[3, 0], [1, 2]]
+                    };
+
+                    for [a, b] in pairs {
+                        segments.push([crossing(a), crossing(b)]);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    segments
+}
+
+/// Arrows along `(u, v)` every `spacing` nodes, the longest spanning most of the gap to the
+/// next arrow, and those near the edges shortened to stay inside `plot`
+fn arrows(
+    u: &ndarray::Array2<f64>,
+    v: &ndarray::Array2<f64>,
+    spacing: usize,
+    cell: f64,
+    plot: [f64; 4],
+    centre: impl Fn(f64, f64) -> [f64; 2],
+) -> Vec<Shape> {
+    let (nu, nv) = u.dim();
+    let nodes = || {
+        (spacing / 2..nu)
+            .step_by(spacing)
+            .flat_map(move |i| (spacing / 2..nv).step_by(spacing).map(move |j| [i, j]))
+    };
+
+    let magnitude = |node: [usize; 2]| u[node].hypot(v[node]);
+    let largest = nodes().map(magnitude).fold(0., f64::max);
+
+    if largest <= 0. {
+        return Vec::new();
+    }
+
+    let longest = spacing as f64 * cell * 0.8;
+    let inside = |[x, y]: [f64; 2]| {
+        x >= plot[0] && x <= plot[0] + plot[2] && y >= plot[1] && y <= plot[1] + plot[3]
+    };
+    let (sin, cos) = 25f64.to_radians().sin_cos();
+    let mut shapes = Vec::new();
+
+    for node in nodes() {
+        // the image's y axis points down
+        let direction = [u[node], -v[node]].map(|c| c / magnitude(node));
+        let [x, y] = centre(node[0] as f64, node[1] as f64);
+
+        // the tail, the tip and the ends of the two strokes of the head
+        let arrow = |length: f64| {
+            let head = (length * 0.35).max(3.);
+            let tip = [
+                x + direction[0] * length / 2.,
+                y + direction[1] * length / 2.,
+            ];
+
+            // the shaft turned back on itself and rotated either way
+            let barb = |sin: f64| {
+                let back = [
+                    -(direction[0] * cos - direction[1] * sin),
+                    -(direction[0] * sin + direction[1] * cos),
+                ];
+                [tip[0] + back[0] * head, tip[1] + back[1] * head]
+            };
+
+            [
+                [
+                    x - direction[0] * length / 2.,
+                    y - direction[1] * length / 2.,
+                ],
+                tip,
+                barb(sin),
+                barb(-sin),
+            ]
+        };
+
+        let mut length = magnitude(node) / largest * longest;
+        while length >= 2. && !arrow(length).into_iter().all(inside) {
+            length *= 0.9;
+        }
+
+        if length.is_nan() || length < 2. {
+            continue;
+        }
+
+        let [tail, tip, left, right] = arrow(length);
+
+        for [from, to] in [[tail, tip], [tip, left], [tip, right]] {
+            shapes.push(Shape::Line {
+                from,
+                to,
+                width: 1.5,
+                color: BLACK,
+            });
+        }
+    }
+
+    shapes
+}
+
+/// An RGB image
+#[derive(Clone, Debug)]
+pub struct Raster {
+    width: usize,
+    height: usize,
+    /// row by row from the top left
+    pixels: Vec<Rgb>,
+}
+
+impl Raster {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    fn blend(&mut self, x: i64, y: i64, color: Rgb, alpha: f64) {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return;
+        }
+
+        let pixel = &mut self.pixels[y as usize * self.width + x as usize];
+        *pixel = [0, 1, 2]
+            .map(|c| (pixel[c] as f64 + (color[c] as f64 - pixel[c] as f64) * alpha).round() as u8);
+    }
+
+    /// fills the pixels whose centres are inside the rectangle
+    fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, color: Rgb) {
+        let (x0, x1) = (x.round() as i64, (x + width).round() as i64);
+        let (y0, y1) = (y.round() as i64, (y + height).round() as i64);
+
+        for py in y0..y1 {
+            for px in x0..x1 {
+                self.blend(px, py, color, 1.);
+            }
+        }
+    }
+
+    /// antialiased by the distance from each pixel centre to the segment
+    fn line(&mut self, from: [f64; 2], to: [f64; 2], width: f64, color: Rgb) {
+        let reach = width / 2. + 1.;
+        let [dx, dy] = [to[0] - from[0], to[1] - from[1]];
+        let length2 = dx * dx + dy * dy;
+
+        let x0 = (from[0].min(to[0]) - reach).floor() as i64;
+        let x1 = (from[0].max(to[0]) + reach).ceil() as i64;
+        let y0 = (from[1].min(to[1]) - reach).floor() as i64;
+        let y1 = (from[1].max(to[1]) + reach).ceil() as i64;
+
+        for py in y0..=y1 {
+            for px in x0..=x1 {
+                let [cx, cy] = [px as f64 + 0.5, py as f64 + 0.5];
+
+                let t = if length2 > 0. {
+                    (((cx - from[0]) * dx + (cy - from[1]) * dy) / length2).clamp(0., 1.)
+                } else {
+                    0.
+                };
+
+                let distance = (cx - from[0] - t * dx).hypot(cy - from[1] - t * dy);
+                let coverage = (width / 2. + 0.5 - distance).clamp(0., 1.);
+
+                if coverage > 0. {
+                    self.blend(px, py, color, coverage);
+                }
+            }
+        }
+    }
+
+    fn text(&mut self, at: [f64; 2], text: &str, anchor: Anchor) {
+        let width = text.chars().count() as f64 * CHAR_WIDTH;
+        let left = match anchor {
+            Anchor::Start => at[0],
+            Anchor::Middle => at[0] - width / 2.,
+        };
+        let top = at[1] - CHAR_HEIGHT / 2.;
+
+        for (index, character) in text.chars().enumerate() {
+            let x = left + index as f64 * CHAR_WIDTH;
+
+            for (row, bits) in glyph(character).iter().enumerate() {
+                for column in 0..5 {
+                    if bits & (0x10 >> column) != 0 {
+                        self.rect(
+                            x + column as f64 * FONT_SCALE,
+                            top + row as f64 * FONT_SCALE,
+                            FONT_SCALE,
+                            FONT_SCALE,
+                            BLACK,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn write_png(&self, writer: impl io::Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let data: Vec<u8> = self.pixels.iter().flatten().copied().collect();
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&data)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+}
+
+/// Rows of a 5 by 7 glyph from the top, the highest of the 5 bits on the left. Only the
+/// characters the figures use are drawn, anything else is left blank
+fn glyph(character: char) -> [u8; 7] {
+    match character {
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        '+' => [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00],
+        '=' => [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00],
+        '[' => [0x0E, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0E],
+        ']' => [0x0E, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0E],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'e' => [0x00, 0x00, 0x0E, 0x11, 0x1F, 0x10, 0x0E],
+        'm' => [0x00, 0x00, 0x1A, 0x15, 0x15, 0x11, 0x11],
+        'x' => [0x00, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11],
+        'y' => [0x00, 0x00, 0x11, 0x11, 0x0F, 0x01, 0x0E],
+        'z' => [0x00, 0x00, 0x1F, 0x02, 0x04, 0x08, 0x1F],
+        'r' => [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10],
+        'θ' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x0E],
+        '°' => [0x0C, 0x12, 0x12, 0x0C, 0x00, 0x00, 0x00],
+        _ => [0; 7],
+    }
+}
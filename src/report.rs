@@ -0,0 +1,875 @@
+//! Summary of a finished run, built from its results file
+//!
+//! averages and heat flows weight each node by the area or volume it represents: half a cell
+//! along every axis where it lies on a face of the cube, as the boundary stencils do, the
+//! share of its cells that are solid in a masked geometry, or its control volume in a
+//! cylinder
+
+use crate::anisotropy::Orthotropic;
+use crate::conditions::Condition;
+use crate::cylindrical::Cylinder;
+use crate::dimensions::Dimensions;
+use crate::dump::{ErrorType, Results};
+use crate::flux;
+use crate::geometry::{self, Cell, Mask};
+use crate::input::Conditions;
+use crate::prelude::*;
+use crate::SolverParams;
+
+use serde::Serialize;
+use std::fmt;
+
+/// The hottest or coldest node
+#[derive(Serialize, Clone, Copy, Debug)]
+pub struct Extreme<T> {
+    pub temperature: T,
+    pub index: [usize; 3],
+    /// `[m]`, or `(r, θ, z)` in `[m]` and `[rad]` in a cylinder
+    pub position: [T; 3],
+}
+
+#[derive(Serialize, Clone, Copy)]
+pub struct FaceSummary<T> {
+    pub face: &'static str,
+    pub condition: Condition<T>,
+    /// area weighted `[K]`
+    pub mean_temperature: T,
+    /// heat leaving the cube through the face `[W]`, negative where heat enters. Not known
+    /// for constant temperature faces of results saved without the thermal conductivity
+    pub heat_flow: Option<T>,
+}
+
+/// A face of the cube with its condition
+#[derive(Clone, Copy)]
+struct Face<T> {
+    name: &'static str,
+    /// normal to the face
+    axis: usize,
+    /// on the high end of `axis` rather than at zero
+    high: bool,
+    condition: Condition<T>,
+}
+
+/// How the nodes of a results file are laid out in space
+enum Domain<T> {
+    /// the full cube, or the nodes of a wall or cross-section through it
+    Cube(Dimensions),
+    Masked(Mask<T>),
+    Cylinder(Cylinder<T>),
+}
+
+impl<T: Float> Domain<T> {
+    fn new(results: &Results<T>) -> Self {
+        let n = results.simulation.size;
+        let del = results.solver_params.del();
+
+        match (&results.geometry, &results.cylinder) {
+            (_, Some(cylinder)) => Domain::Cylinder(*cylinder),
+            (Some(geometry), None) => Domain::Masked(
+                geometry
+                    .mask(n, del)
+                    .unwrap_or_else(|error| panic!("{error}")),
+            ),
+            (None, None) => Domain::Cube(Dimensions::of(results.simulation.shape())),
+        }
+    }
+
+    fn mask(&self) -> Option<&Mask<T>> {
+        match self {
+            Domain::Masked(mask) => Some(mask),
+            _ => None,
+        }
+    }
+
+    /// `[m]`, or `(r, θ, z)` in a cylinder
+    fn position(&self, node: [usize; 3], n: usize, del: T) -> [T; 3] {
+        match self {
+            Domain::Cylinder(cylinder) => cylinder.position(node, n),
+            _ => node.map(|i| del * lit(i as f64)),
+        }
+    }
+
+    /// Volume `node` represents `[m^3]`
+    fn volume(&self, node: [usize; 3], n: usize, del: T) -> T {
+        match self {
+            Domain::Cube(dimensions) => dimensions.volume(node, n, del),
+            Domain::Masked(mask) => mask.volume(node) * del * del * del,
+            Domain::Cylinder(cylinder) => cylinder.volume(node, n),
+        }
+    }
+
+    /// Area `node` represents on the face normal to `axis` on its `high` or low side `[m^2]`
+    fn face_area(&self, node: [usize; 3], n: usize, del: T, axis: usize, high: bool) -> T {
+        match self {
+            Domain::Cube(dimensions) => dimensions.face_area(node, n, del, axis, high),
+            Domain::Masked(mask) => mask.face_area(node, axis) * del * del,
+            Domain::Cylinder(cylinder) => cylinder.face_area(node, n, axis, high),
+        }
+    }
+
+    /// Distance between the nodes along `axis` at `node` `[m]`
+    fn spacing(&self, node: [usize; 3], n: usize, del: T, axis: usize) -> T {
+        match self {
+            Domain::Cylinder(cylinder) => {
+                let spacing = cylinder.spacing(n)[axis];
+                // the angle between nodes is an arc of the node's radius
+                match axis {
+                    1 => spacing * cylinder.position(node, n)[0],
+                    _ => spacing,
+                }
+            }
+            _ => del,
+        }
+    }
+
+    /// Conductivity along each axis at `node`, `k` unless the materials of a mask differ
+    fn conductivity(&self, node: [usize; 3], k: [T; 3]) -> [T; 3] {
+        match self {
+            Domain::Masked(mask) => mask.conductivity(node, k),
+            _ => k,
+        }
+    }
+}
+
+/// A surface of the voids of a masked geometry
+#[derive(Serialize, Clone)]
+pub struct SurfaceSummary<T> {
+    pub name: String,
+    pub condition: Condition<T>,
+    /// `[m^2]`
+    pub area: T,
+    /// heat leaving the part through the surface `[W]`, not known for constant temperatures
+    pub heat_flow: Option<T>,
+}
+
+/// A contact resistance of a masked geometry
+#[derive(Serialize, Clone)]
+pub struct ContactSummary<T> {
+    pub name: String,
+    /// `[W/(m^2 K)]`
+    pub conductance: T,
+    /// `[m^2]`
+    pub area: T,
+    /// heat crossing from the first material to the second, or towards the high side of a
+    /// plane `[W]`, the mean of the heat reaching the contact from one side and leaving it to
+    /// the other. Not known for results saved without the thermal conductivity
+    pub heat_flow: Option<T>,
+}
+
+/// The coolant of a channel face
+#[derive(Serialize, Clone)]
+pub struct ChannelSummary<T> {
+    pub face: &'static str,
+    /// `[K]`
+    pub inlet: T,
+    /// `[K]`
+    pub outlet: T,
+    /// heat the coolant carries away from the face `[W]`
+    pub heat_flow: T,
+    /// coolant temperature beside each row of face nodes, by index along the channel `[K]`
+    pub fluid: Vec<T>,
+}
+
+/// The inputs of the run, repeated in the report
+#[derive(Serialize, Clone)]
+pub struct Configuration<T> {
+    /// `[W/(mK)]`
+    pub thermal_conductivity: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orthotropic: Option<Orthotropic<T>>,
+    /// `[W/m^3]`
+    pub energy_generation: Option<T>,
+    pub solver_params: SolverParams<T>,
+    pub conditions: Conditions<T>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct Report<T> {
+    pub precision: Precision,
+    pub divisions: usize,
+    /// `[m]`
+    pub del: T,
+    /// axes of the cube solved, all three for a cylinder
+    pub dimensions: Dimensions,
+    /// the cylinder solved in place of the cube
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cylinder: Option<Cylinder<T>>,
+    pub steps: usize,
+    /// the solve was stopped before the error reached `error_epsilon`
+    pub stopped: bool,
+    /// `[s]`
+    pub wall_time: Option<f64>,
+    pub error_type: ErrorType,
+    pub final_error: Option<T>,
+    /// factor the error falls by each step, fitted to the later half of the recorded errors
+    pub convergence_rate: Option<T>,
+    pub min: Extreme<T>,
+    pub max: Extreme<T>,
+    /// volume weighted `[K]`
+    pub mean: T,
+    pub faces: Vec<FaceSummary<T>>,
+    /// the named surfaces of the voids, empty for the full cube
+    pub surfaces: Vec<SurfaceSummary<T>>,
+    /// the contact resistances of the geometry
+    pub contacts: Vec<ContactSummary<T>>,
+    /// the coolant channels of the faces of the cube
+    pub channels: Vec<ChannelSummary<T>>,
+    /// heat generated inside the part `[W]`
+    pub heat_generated: Option<T>,
+    /// heat generated less the heat leaving through every face and surface `[W]`, zero at
+    /// steady state
+    pub imbalance: Option<T>,
+    pub configuration: Configuration<T>,
+}
+
+impl<T: Float> Report<T> {
+    pub fn new(results: &Results<T>) -> Self {
+        let simulation = &results.simulation;
+        let params = results.solver_params;
+
+        let temps = simulation.temperatures();
+        let n = simulation.size;
+        let del = params.del();
+        // along each axis
+        let k = match (&results.orthotropic, results.thermal_conductivity) {
+            (Some(orthotropic), _) => Some(orthotropic.along_axes()),
+            (None, k) => k.map(|k| [k; 3]),
+        };
+
+        let domain = Domain::new(results);
+
+        // nodes outside of a masked geometry are NaN
+        let extreme = |pick: fn(T, T) -> bool| {
+            let (index, &temperature) = temps
+                .indexed_iter()
+                .filter(|(_, temp)| !temp.is_nan())
+                .reduce(|best, next| if pick(*next.1, *best.1) { next } else { best })
+                .expect("the grid has nodes");
+
+            let index = [index.0, index.1, index.2];
+
+            Extreme {
+                temperature,
+                index,
+                position: domain.position(index, n, del),
+            }
+        };
+
+        let min = extreme(|next, best| next < best);
+        let max = extreme(|next, best| next > best);
+
+        let (total, volume) = temps
+            .indexed_iter()
+            .filter(|(_, temp)| !temp.is_nan())
+            .fold(
+                (T::zero(), T::zero()),
+                |(total, volume), ((x, y, z), &temp)| {
+                    let w = domain.volume([x, y, z], n, del);
+                    (total + w * temp, volume + w)
+                },
+            );
+        let mean = total / volume;
+
+        let conditions = results.conditions;
+        let names = match domain {
+            Domain::Cylinder(_) => ["outer", "inner", "θ end", "θ start", "z end", "z start"],
+            _ => ["right", "left", "top", "bot", "front", "back"],
+        };
+        let cube: Vec<_> = [
+            (0, true, conditions.right_boundary),
+            (0, false, conditions.left_boundary),
+            (1, true, conditions.top_boundary),
+            (1, false, conditions.bot_boundary),
+            (2, true, conditions.front_boundary),
+            (2, false, conditions.back_boundary),
+        ]
+        .into_iter()
+        .zip(names)
+        .map(|((axis, high, condition), name)| Face {
+            name,
+            axis,
+            high,
+            condition,
+        })
+        .collect();
+        let faces: Vec<_> = cube
+            .iter()
+            .filter_map(|&face| face_summary(&temps, &domain, del, k, face))
+            .collect();
+
+        let surfaces = match (domain.mask(), &results.geometry) {
+            (Some(mask), Some(geometry)) => {
+                let names = geometry.surfaces.keys().cloned();
+                surface_summaries(&temps, mask, del, names)
+            }
+            _ => Vec::new(),
+        };
+
+        let contacts = match (domain.mask(), &results.geometry) {
+            (Some(mask), Some(geometry)) => {
+                let s = results.thermal_conductivity.map(|k| {
+                    SolverInfo::builder()
+                        .k(k)
+                        .q_dot(T::zero())
+                        .del(del)
+                        .anisotropy(
+                            results
+                                .orthotropic
+                                .map_or([T::one(); 3], |orthotropic| orthotropic.anisotropy(k)),
+                        )
+                        .build()
+                });
+                let names = geometry.contacts.iter().map(|contact| contact.name.clone());
+                contact_summaries(&temps, mask, s.as_ref(), del, names)
+            }
+            _ => Vec::new(),
+        };
+
+        let channels = match domain {
+            Domain::Cube(Dimensions::Three) => channel_summaries(&temps, del, &cube),
+            _ => Vec::new(),
+        };
+
+        let heat_generated = match &domain {
+            Domain::Masked(mask) => mask.heat_generated(results.energy_generation, del),
+            _ => results.energy_generation.map(|q_dot| {
+                let volume = temps
+                    .indexed_iter()
+                    .fold(T::zero(), |volume, ((x, y, z), _)| {
+                        volume + domain.volume([x, y, z], n, del)
+                    });
+                q_dot * volume
+            }),
+        };
+
+        let heat_out = faces
+            .iter()
+            .map(|face| face.heat_flow)
+            .chain(surfaces.iter().map(|surface| surface.heat_flow))
+            .try_fold(T::zero(), |total, flow| flow.map(|flow| total + flow));
+
+        let imbalance = heat_generated
+            .zip(heat_out)
+            .map(|(generated, out)| generated - out);
+
+        Report {
+            precision: results.precision,
+            divisions: n,
+            del,
+            dimensions: match domain {
+                Domain::Cube(dimensions) => dimensions,
+                _ => Dimensions::Three,
+            },
+            cylinder: results.cylinder,
+            steps: simulation.num_steps,
+            stopped: simulation.stopped,
+            wall_time: simulation.wall_time,
+            error_type: simulation.error_decay.error_type,
+            final_error: simulation.final_error,
+            convergence_rate: convergence_rate(results),
+            min,
+            max,
+            mean,
+            faces,
+            surfaces,
+            contacts,
+            channels,
+            heat_generated,
+            imbalance,
+            configuration: Configuration {
+                thermal_conductivity: results.thermal_conductivity,
+                orthotropic: results.orthotropic,
+                energy_generation: results.energy_generation,
+                solver_params: params,
+                conditions,
+            },
+        }
+    }
+}
+
+/// Mean temperature and heat flow of `face`, `None` when no node has any area on it
+fn face_summary<T: Float>(
+    temps: &Matrix<T>,
+    domain: &Domain<T>,
+    del: T,
+    k: Option<[T; 3]>,
+    face: Face<T>,
+) -> Option<FaceSummary<T>> {
+    let Face {
+        name,
+        axis,
+        high,
+        condition,
+    } = face;
+    let size = temps.shape()[0];
+    let n = temps.shape()[axis];
+    let layer = if high { n - 1 } else { 0 };
+    let [first, second] = match axis {
+        0 => [1, 2],
+        1 => [0, 2],
+        _ => [0, 1],
+    };
+
+    // derivative along the outward normal per node spacing, only needed for constant
+    // temperature faces
+    let outward = match condition {
+        Condition::Temperature(_) => Some(flux::derivative(temps, T::one(), axis)),
+        _ => None,
+    };
+    let sign = if high { T::one() } else { -T::one() };
+
+    let stream = match condition {
+        Condition::Channel(channel) => channel.march(temps, del, axis, high).ok(),
+        _ => None,
+    };
+
+    let mut area = T::zero();
+    let mut total = T::zero();
+    let mut flow = Some(T::zero());
+
+    for i in 0..temps.shape()[first] {
+        for j in 0..temps.shape()[second] {
+            let mut index = [0; 3];
+            index[axis] = layer;
+            index[first] = i;
+            index[second] = j;
+
+            let w = domain.face_area(index, size, del, axis, high);
+
+            // the face of the cube may be partly cut away
+            if w.is_zero() {
+                continue;
+            }
+
+            let temp = temps[index];
+
+            area = area + w;
+            total = total + w * temp;
+
+            let flux_out = match condition {
+                Condition::Convection(c) => Some(c.h * (temp - c.t_inf)),
+                Condition::Correlated(c) => Some(c.h * (temp - c.t_inf)),
+                Condition::Channel(c) => stream
+                    .as_ref()
+                    .map(|stream| c.h * (temp - stream.fluid[index[c.along.index()]])),
+                // `heat_flux` is the heat entering the cube
+                Condition::HeatFlux(c) => Some(-c.heat_flux),
+                Condition::Temperature(_) => k.zip(outward.as_ref()).map(|(k, outward)| {
+                    let k = domain.conductivity(index, k)[axis];
+                    let spacing = domain.spacing(index, size, del, axis);
+                    -k * sign * outward[index] / spacing
+                }),
+                Condition::Symmetry(_) => Some(T::zero()),
+                // central differences across the pair of faces, so the heat leaving through
+                // one is what enters through the other
+                Condition::Periodic(_) => k.map(|k| {
+                    let k = domain.conductivity(index, k)[axis];
+                    let (mut ahead, mut behind) = (index, index);
+                    ahead[axis] = 1;
+                    behind[axis] = n - 2;
+                    -k * sign * (temps[ahead] - temps[behind]) / (lit::<T>(2.) * del)
+                }),
+            };
+
+            flow = flow
+                .zip(flux_out)
+                .map(|(flow, flux_out)| flow + w * flux_out);
+        }
+    }
+
+    (area > T::zero()).then(|| FaceSummary {
+        face: name,
+        condition,
+        mean_temperature: total / area,
+        heat_flow: flow,
+    })
+}
+
+/// Area and heat flow of every named surface of a masked geometry, `names` in the order
+/// of the mask's surfaces after the adiabatic default
+fn surface_summaries<T: Float>(
+    temps: &Matrix<T>,
+    mask: &Mask<T>,
+    del: T,
+    names: impl Iterator<Item = String>,
+) -> Vec<SurfaceSummary<T>> {
+    let quarter = del * del / lit(4.);
+
+    let mut summaries: Vec<SurfaceSummary<T>> = names
+        .zip(mask.surfaces.iter().skip(1))
+        .map(|(name, &condition)| SurfaceSummary {
+            name,
+            condition,
+            area: T::zero(),
+            heat_flow: Some(T::zero()),
+        })
+        .collect();
+
+    for ((x, y, z), &temp) in temps.indexed_iter() {
+        for (_, cell) in geometry::exposed(&mask.octants([x, y, z])) {
+            let Some(Cell::Void(surface)) = cell else {
+                continue;
+            };
+            let Some(summary) = surface.checked_sub(1).and_then(|s| summaries.get_mut(s)) else {
+                continue;
+            };
+
+            let flux_out = match summary.condition {
+                Condition::Convection(c) => Some(c.h * (temp - c.t_inf)),
+                Condition::Correlated(c) => Some(c.h * (temp - c.t_inf)),
+                Condition::HeatFlux(c) => Some(-c.heat_flux),
+                Condition::Symmetry(_) => Some(T::zero()),
+                Condition::Temperature(_) | Condition::Periodic(_) | Condition::Channel(_) => None,
+            };
+
+            summary.area = summary.area + quarter;
+            summary.heat_flow = summary
+                .heat_flow
+                .zip(flux_out)
+                .map(|(flow, flux_out)| flow + quarter * flux_out);
+        }
+    }
+
+    summaries
+}
+
+/// Area and heat flow of every contact of a masked geometry, `names` in the order of the
+/// mask's contacts
+///
+/// each quarter face conducting away from a node on a contact counts half of its flow, with
+/// the flows towards the first side taken as negative, so both sides of the contact are
+/// averaged
+fn contact_summaries<T: Float>(
+    temps: &Matrix<T>,
+    mask: &Mask<T>,
+    s: Option<&SolverInfo<T>>,
+    del: T,
+    names: impl Iterator<Item = String>,
+) -> Vec<ContactSummary<T>> {
+    let half = lit::<T>(0.5);
+
+    let mut summaries: Vec<ContactSummary<T>> = names
+        .zip(&mask.contacts)
+        .map(|(name, junction)| ContactSummary {
+            name,
+            conductance: junction.conductance,
+            area: T::zero(),
+            heat_flow: s.map(|_| T::zero()),
+        })
+        .collect();
+
+    // the crossings only depend on the conductivity through the weights
+    let unit = SolverInfo::builder()
+        .k(T::one())
+        .q_dot(T::zero())
+        .del(del)
+        .build();
+
+    for ((x, y, z), &temp) in temps.indexed_iter() {
+        for (direction, weight, contact, second) in mask.crossings([x, y, z], s.unwrap_or(&unit)) {
+            let summary = &mut summaries[contact];
+            let sign = if second { half } else { -half };
+
+            summary.area = summary.area + half * del * del / lit(4.);
+
+            let mut neighbour = [x, y, z];
+            if direction % 2 == 1 {
+                neighbour[direction / 2] += 1;
+            } else {
+                neighbour[direction / 2] -= 1;
+            }
+
+            summary.heat_flow = summary
+                .heat_flow
+                .zip(s)
+                .map(|(flow, s)| flow + sign * weight * s.k * del * (temp - temps[neighbour]));
+        }
+    }
+
+    summaries
+}
+
+/// The coolant marched along every channel face in `faces`, the six of the full cube
+fn channel_summaries<T: Float>(
+    temps: &Matrix<T>,
+    del: T,
+    faces: &[Face<T>],
+) -> Vec<ChannelSummary<T>> {
+    faces
+        .iter()
+        .filter_map(|face| match face.condition {
+            Condition::Channel(channel) => {
+                let stream = channel.march(temps, del, face.axis, face.high).ok()?;
+
+                Some(ChannelSummary {
+                    face: face.name,
+                    inlet: channel.t_inlet,
+                    outlet: stream.outlet,
+                    heat_flow: channel.heat_flow(&stream),
+                    fluid: stream.fluid,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Factor the error falls by each step, fitted to the later half of the recorded errors
+fn convergence_rate<T: Float>(results: &Results<T>) -> Option<T> {
+    let simulation = &results.simulation;
+    let error_steps = results.solver_params.error_steps;
+
+    let mut points: Vec<(T, T)> = simulation
+        .error_decay
+        .data
+        .iter()
+        .enumerate()
+        .map(|(index, &error)| (index.saturating_mul(error_steps), error))
+        .filter(|&(step, _)| step <= simulation.num_steps)
+        .map(|(step, error)| (lit(step as f64), error))
+        .collect();
+
+    if let Some(error) = simulation.final_error {
+        let step = lit::<T>(simulation.num_steps as f64);
+
+        if points.last().is_none_or(|&(last, _)| last < step) {
+            points.push((step, error));
+        }
+    }
+
+    decay_rate(&points)
+}
+
+/// Fits `ln(error) = a + b * step` to the later half of `(step, error)` pairs and returns
+/// `exp(b)`, the factor the error falls by each step
+pub(crate) fn decay_rate<T: Float>(points: &[(T, T)]) -> Option<T> {
+    let points: Vec<(T, T)> = points
+        .iter()
+        .filter(|&&(_, error)| error > T::zero())
+        .map(|&(step, error)| (step, error.ln()))
+        .collect();
+
+    let later = &points[points.len() / 2..];
+
+    if later.len() < 2 {
+        return None;
+    }
+
+    let count = lit::<T>(later.len() as f64);
+    let mean_step = later.iter().map(|p| p.0).sum::<T>() / count;
+    let mean_log = later.iter().map(|p| p.1).sum::<T>() / count;
+
+    let covariance: T = later
+        .iter()
+        .map(|&(step, log)| (step - mean_step) * (log - mean_log))
+        .sum();
+    let variance: T = later
+        .iter()
+        .map(|&(step, _)| (step - mean_step) * (step - mean_step))
+        .sum();
+
+    if variance.is_zero() {
+        return None;
+    }
+
+    Some((covariance / variance).exp())
+}
+
+fn describe<T: Float>(condition: &Condition<T>) -> String {
+    match condition {
+        Condition::Convection(c) => format!("convection h = {}, t_inf = {}", c.h, c.t_inf),
+        Condition::HeatFlux(c) => format!("heat flux {}", c.heat_flux),
+        Condition::Temperature(c) => format!("temperature {}", c.temperature),
+        Condition::Symmetry(_) => "symmetry".to_string(),
+        Condition::Periodic(_) => "periodic".to_string(),
+        Condition::Correlated(c) => c.to_string(),
+        Condition::Channel(c) => c.to_string(),
+    }
+}
+
+fn position<T: Float>(p: [T; 3], cylindrical: bool) -> String {
+    if cylindrical {
+        format!("r = {:.4} m, θ = {:.4} rad, z = {:.4} m", p[0], p[1], p[2])
+    } else {
+        format!("({:.4}, {:.4}, {:.4}) m", p[0], p[1], p[2])
+    }
+}
+
+impl<T: Float> fmt::Display for Report<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = match self.precision {
+            Precision::Single => "single",
+            Precision::Double => "double",
+        };
+
+        match &self.cylinder {
+            Some(cylinder) => {
+                let [nr, ntheta, nz] = cylinder.shape(self.divisions);
+                writeln!(
+                    f,
+                    "{nr} x {ntheta} x {nz} nodes in a cylinder of radius {:.4} to {:.4} m and \
+                     length {:.4} m, {precision} precision",
+                    cylinder.inner_radius, cylinder.outer_radius, cylinder.length,
+                )?;
+            }
+            None => {
+                let nodes = match self.dimensions {
+                    Dimensions::One => format!("{} nodes along x", self.divisions),
+                    Dimensions::Two => format!("{}^2 nodes over (x, y)", self.divisions),
+                    Dimensions::Three => format!("{}^3 nodes", self.divisions),
+                };
+                writeln!(
+                    f,
+                    "{nodes}, {:.4e} m apart, {precision} precision",
+                    self.del.to_f64().unwrap(),
+                )?
+            }
+        }
+
+        write!(f, "  {} steps", self.steps)?;
+        if let Some(wall_time) = self.wall_time {
+            write!(f, " in {wall_time:.2} s")?;
+        }
+        if self.stopped {
+            write!(f, ", stopped before reaching the error epsilon")?;
+        }
+        writeln!(f)?;
+
+        if let Some(error) = self.final_error {
+            writeln!(
+                f,
+                "  final {:?} error {:.3e}",
+                self.error_type,
+                error.to_f64().unwrap()
+            )?;
+        }
+
+        if let Some(rate) = self.convergence_rate {
+            let rate = rate.to_f64().unwrap();
+            write!(f, "  error falling by a factor of {rate:.6} per step")?;
+
+            if rate < 1. {
+                write!(f, ", {:.0} steps per decade", -(10f64.ln()) / rate.ln())?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Temperature [K]")?;
+        for (name, extreme) in [("min", &self.min), ("max", &self.max)] {
+            writeln!(
+                f,
+                "  {name:<6} {:>10.3} at {}",
+                extreme.temperature,
+                position(extreme.position, self.cylinder.is_some())
+            )?;
+        }
+        writeln!(f, "  {:<6} {:>10.3}", "mean", self.mean)?;
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<8} {:>10} {:>14}  condition",
+            "Face", "mean [K]", "heat out [W]"
+        )?;
+        for face in &self.faces {
+            let flow = face
+                .heat_flow
+                .map_or("-".to_string(), |flow| format!("{:.4}", flow));
+
+            writeln!(
+                f,
+                "  {:<6} {:>10.3} {:>14}  {}",
+                face.face,
+                face.mean_temperature,
+                flow,
+                describe(&face.condition)
+            )?;
+        }
+
+        for surface in &self.surfaces {
+            let flow = surface
+                .heat_flow
+                .map_or("-".to_string(), |flow| format!("{:.4}", flow));
+
+            writeln!(
+                f,
+                "  {:<17} {:>14}  {}, {:.4e} m^2",
+                surface.name,
+                flow,
+                describe(&surface.condition),
+                surface.area.to_f64().unwrap()
+            )?;
+        }
+
+        if let Some(generated) = self.heat_generated {
+            writeln!(f, "  {:<17} {:>14.4}", "generated", generated)?;
+        }
+        if let Some(imbalance) = self.imbalance {
+            writeln!(f, "  {:<17} {:>14.4}", "imbalance", imbalance)?;
+        }
+
+        if !self.contacts.is_empty() {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "{:<17} {:>15}  conductance",
+                "Contact", "heat across [W]"
+            )?;
+        }
+        for contact in &self.contacts {
+            let flow = contact
+                .heat_flow
+                .map_or("-".to_string(), |flow| format!("{:.4}", flow));
+
+            writeln!(
+                f,
+                "  {:<15} {:>15}  {} W/(m^2 K), {:.4e} m^2",
+                contact.name,
+                flow,
+                contact.conductance,
+                contact.area.to_f64().unwrap()
+            )?;
+        }
+
+        if !self.channels.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Channel   inlet [K]  outlet [K]   heat out [W]")?;
+        }
+        for channel in &self.channels {
+            writeln!(
+                f,
+                "  {:<6} {:>10.3} {:>11.3} {:>14.4}",
+                channel.face, channel.inlet, channel.outlet, channel.heat_flow
+            )?;
+        }
+
+        let config = &self.configuration;
+        let params = &config.solver_params;
+
+        writeln!(f)?;
+        writeln!(f, "Configuration")?;
+        if let Some(k) = config.thermal_conductivity {
+            writeln!(f, "  thermal conductivity {k} W/(mK)")?;
+        }
+        if let Some(orthotropic) = &config.orthotropic {
+            let [x, y, z] = orthotropic.along_axes();
+            writeln!(f, "  along x, y and z {x:.4} {y:.4} {z:.4} W/(mK)")?;
+        }
+        if let Some(q_dot) = config.energy_generation {
+            writeln!(f, "  energy generation {q_dot} W/m^3")?;
+        }
+        writeln!(
+            f,
+            "  len {} m, {} divisions, error epsilon {}",
+            params.len, params.divisions, params.error_epsilon
+        )?;
+        writeln!(
+            f,
+            "  data every {} steps, error every {} steps, {:?} sweep",
+            params.data_steps, params.error_steps, params.sweep
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,3 @@
-use crate::conditions::*;
 use crate::corner::*;
 use crate::internal;
 
@@ -11,33 +10,25 @@ use super::top::TopSurface;
 
 use super::edge;
 
-#[derive(serde::Serialize, Clone, Copy)]
-pub(crate) struct SetupConditions<A, B, C, D, E, F>
-where
-    A: BoundaryCondition + Clone,
-    B: BoundaryCondition + Clone,
-    C: BoundaryCondition + Clone,
-    D: BoundaryCondition + Clone,
-    E: BoundaryCondition + Clone,
-    F: BoundaryCondition + Clone,
-{
-    pub(crate) right_boundary: A,
-    pub(crate) left_boundary: B,
-    pub(crate) top_boundary: C,
-    pub(crate) bot_boundary: D,
-    pub(crate) front_boundary: E,
-    pub(crate) back_boundary: F,
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy)]
+pub struct SetupConditions<A, B, C, D, E, F> {
+    pub right_boundary: A,
+    pub left_boundary: B,
+    pub top_boundary: C,
+    pub bot_boundary: D,
+    pub front_boundary: E,
+    pub back_boundary: F,
 }
 impl<A, B, C, D, E, F> SetupConditions<A, B, C, D, E, F>
 where
-    A: BoundaryCondition + Copy,
-    B: BoundaryCondition + Copy,
-    C: BoundaryCondition + Copy,
-    D: BoundaryCondition + Copy,
-    E: BoundaryCondition + Copy,
-    F: BoundaryCondition + Copy,
+    A: Copy,
+    B: Copy,
+    C: Copy,
+    D: Copy,
+    E: Copy,
+    F: Copy,
 {
-    pub(crate) fn make_boundaries(self) -> BoundaryConditions<A, B, C, D, E, F> {
+    pub fn make_boundaries(self) -> BoundaryConditions<A, B, C, D, E, F> {
         let front_boundary = self.front_boundary;
         let back_boundary = self.back_boundary;
         let right_boundary = self.right_boundary;
@@ -142,14 +133,12 @@ where
         );
         let internal = internal::InternalConduction;
 
-        let bcs = BoundaryConditions {
+        BoundaryConditions {
             edges,
             walls,
             internal,
             corners,
-        };
-
-        bcs
+        }
     }
 }
 
@@ -162,12 +151,12 @@ fn make_corners<A, B, C, D, E, F>(
     back_condition: F,
 ) -> CornerConditions<A, B, C, D, E, F>
 where
-    A: BoundaryCondition + Copy,
-    B: BoundaryCondition + Copy,
-    C: BoundaryCondition + Copy,
-    D: BoundaryCondition + Copy,
-    E: BoundaryCondition + Copy,
-    F: BoundaryCondition + Copy,
+    A: Copy,
+    B: Copy,
+    C: Copy,
+    D: Copy,
+    E: Copy,
+    F: Copy,
 {
     let right_top_back = RightTopBack {
         right_condition,
@@ -227,76 +216,44 @@ where
     }
 }
 
-pub(crate) struct BoundaryConditions<A, B, C, D, E, F>
-where
-    A: BoundaryCondition,
-    B: BoundaryCondition,
-    C: BoundaryCondition,
-    D: BoundaryCondition,
-    E: BoundaryCondition,
-    F: BoundaryCondition,
-{
-    pub(crate) walls: WallConditions<A, B, C, D, E, F>,
-    pub(crate) edges: EdgeConditions<A, B, C, D, E, F>,
-    pub(crate) corners: CornerConditions<A, B, C, D, E, F>,
-    pub(crate) internal: internal::InternalConduction,
+pub struct BoundaryConditions<A, B, C, D, E, F> {
+    pub walls: WallConditions<A, B, C, D, E, F>,
+    pub edges: EdgeConditions<A, B, C, D, E, F>,
+    pub corners: CornerConditions<A, B, C, D, E, F>,
+    pub internal: internal::InternalConduction,
 }
 
-pub(crate) struct WallConditions<A, B, C, D, E, F>
-where
-    A: BoundaryCondition,
-    B: BoundaryCondition,
-    C: BoundaryCondition,
-    D: BoundaryCondition,
-    E: BoundaryCondition,
-    F: BoundaryCondition,
-{
-    pub(crate) right: RightSurface<A>,
-    pub(crate) left: LeftSurface<B>,
-    pub(crate) top: TopSurface<C>,
-    pub(crate) bot: BottomSurface<D>,
-    pub(crate) front: FrontSurface<E>,
-    pub(crate) back: BackSurface<F>,
+pub struct WallConditions<A, B, C, D, E, F> {
+    pub right: RightSurface<A>,
+    pub left: LeftSurface<B>,
+    pub top: TopSurface<C>,
+    pub bot: BottomSurface<D>,
+    pub front: FrontSurface<E>,
+    pub back: BackSurface<F>,
 }
 
-pub(crate) struct EdgeConditions<A, B, C, D, E, F>
-where
-    A: BoundaryCondition,
-    B: BoundaryCondition,
-    C: BoundaryCondition,
-    D: BoundaryCondition,
-    E: BoundaryCondition,
-    F: BoundaryCondition,
-{
-    pub(crate) right_top: edge::RightTop<A, C>,
-    pub(crate) right_bot: edge::RightBot<A, D>,
-    pub(crate) left_top: edge::LeftTop<B, C>,
-    pub(crate) left_bot: edge::LeftBot<B, D>,
-    pub(crate) front_top: edge::FrontTop<E, C>,
-    pub(crate) front_bot: edge::FrontBot<E, D>,
-    pub(crate) back_bot: edge::BackBot<F, D>,
-    pub(crate) back_top: edge::BackTop<F, C>,
-    pub(crate) back_left: edge::BackLeft<F, B>,
-    pub(crate) back_right: edge::BackRight<F, A>,
-    pub(crate) front_left: edge::FrontLeft<E, B>,
-    pub(crate) front_right: edge::FrontRight<E, A>,
+pub struct EdgeConditions<A, B, C, D, E, F> {
+    pub right_top: edge::RightTop<A, C>,
+    pub right_bot: edge::RightBot<A, D>,
+    pub left_top: edge::LeftTop<B, C>,
+    pub left_bot: edge::LeftBot<B, D>,
+    pub front_top: edge::FrontTop<E, C>,
+    pub front_bot: edge::FrontBot<E, D>,
+    pub back_bot: edge::BackBot<F, D>,
+    pub back_top: edge::BackTop<F, C>,
+    pub back_left: edge::BackLeft<F, B>,
+    pub back_right: edge::BackRight<F, A>,
+    pub front_left: edge::FrontLeft<E, B>,
+    pub front_right: edge::FrontRight<E, A>,
 }
 
-pub(crate) struct CornerConditions<A, B, C, D, E, F>
-where
-    A: BoundaryCondition,
-    B: BoundaryCondition,
-    C: BoundaryCondition,
-    D: BoundaryCondition,
-    E: BoundaryCondition,
-    F: BoundaryCondition,
-{
-    pub(crate) right_top_back: RightTopBack<A, C, F>,
-    pub(crate) right_top_front: RightTopFront<A, C, E>,
-    pub(crate) right_bot_back: RightBottomBack<A, D, F>,
-    pub(crate) right_bot_front: RightBottomFront<A, D, E>,
-    pub(crate) left_top_back: LeftTopBack<B, C, F>,
-    pub(crate) left_top_front: LeftTopFront<B, C, E>,
-    pub(crate) left_bot_back: LeftBottomBack<B, D, F>,
-    pub(crate) left_bot_front: LeftBottomFront<B, D, E>,
+pub struct CornerConditions<A, B, C, D, E, F> {
+    pub right_top_back: RightTopBack<A, C, F>,
+    pub right_top_front: RightTopFront<A, C, E>,
+    pub right_bot_back: RightBottomBack<A, D, F>,
+    pub right_bot_front: RightBottomFront<A, D, E>,
+    pub left_top_back: LeftTopBack<B, C, F>,
+    pub left_top_front: LeftTopFront<B, C, E>,
+    pub left_bot_back: LeftBottomBack<B, D, F>,
+    pub left_bot_front: LeftBottomFront<B, D, E>,
 }
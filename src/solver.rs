@@ -1,23 +1,106 @@
-use crate::dump::{ErrorData, SimulationResult, StepData};
-use crate::prelude;
+use crate::dump::{ErrorData, ProbeHistory, SimulationResult, StepData};
+use crate::observer::{Control, Observer, Progress};
 use crate::prelude::*;
+use crate::probe::Probe;
 use crate::setup::BoundaryConditions;
+use crate::stencil::{self, Coefficients};
 use crate::SolverParams;
 
-pub(crate) fn solver<A, B, C, D, E, F, ErrCalc>(
-    s: SolverInfo,
-    params: SolverParams,
+/// Solves until the error between steps drops below `params.error_epsilon`, or `observer`
+/// asks to stop, failing when the stencils of the conditions cannot be built
+pub fn solver<T, A, B, C, D, E, F, ErrCalc, O>(
+    s: SolverInfo<T>,
+    params: SolverParams<T>,
     conditions: BoundaryConditions<A, B, C, D, E, F>,
     error_type: ErrCalc,
-) -> SimulationResult
+    probes: &[Probe<T>],
+    observer: O,
+) -> Result<SimulationResult<T>, stencil::Error>
 where
-    A: BoundaryCondition + Sync,
-    B: BoundaryCondition + Sync,
-    C: BoundaryCondition + Sync,
-    D: BoundaryCondition + Sync,
-    E: BoundaryCondition + Sync,
-    F: BoundaryCondition + Sync,
-    ErrCalc: CalculateError,
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
+    D: BoundaryCondition<T>,
+    E: BoundaryCondition<T>,
+    F: BoundaryCondition<T>,
+    ErrCalc: CalculateError<T> + Sync,
+    O: Observer<T>,
+{
+    let coefficients = tracing::info_span!("setup", divisions = params.divisions)
+        .in_scope(|| Coefficients::new(&conditions, &s, params.divisions, params.sweep))?;
+
+    Ok(solve(&coefficients, params, &error_type, probes, observer))
+}
+
+/// Solves with coefficients that have already been calculated, e.g. ones with per node
+/// constants from `Coefficients::with_constants`
+///
+/// the temperature at every probe is recorded after each step. Runs inside a `solve` span,
+/// with the progress logged at `debug` every thousand steps and the outcome at `info`
+pub fn solve<T, ErrCalc, O>(
+    coefficients: &Coefficients<T>,
+    params: SolverParams<T>,
+    error_type: &ErrCalc,
+    probes: &[Probe<T>],
+    observer: O,
+) -> SimulationResult<T>
+where
+    T: Float,
+    ErrCalc: CalculateError<T> + Sync,
+    O: Observer<T>,
+{
+    solve_from(coefficients, None, params, error_type, probes, observer)
+}
+
+/// Solves as `solve` does, starting from the temperatures `initial` when given, e.g. those of
+/// a previous solve of nearly the same conditions, rather than from 273 K everywhere
+///
+/// panics when `initial` is not on the grid of `coefficients`
+pub fn solve_from<T, ErrCalc, O>(
+    coefficients: &Coefficients<T>,
+    initial: Option<&Matrix<T>>,
+    params: SolverParams<T>,
+    error_type: &ErrCalc,
+    probes: &[Probe<T>],
+    observer: O,
+) -> SimulationResult<T>
+where
+    T: Float,
+    ErrCalc: CalculateError<T> + Sync,
+    O: Observer<T>,
+{
+    let span = tracing::info_span!(
+        "solve",
+        divisions = params.divisions,
+        threads = params.threads
+    );
+    let iterate =
+        || span.in_scope(|| iterate(coefficients, initial, params, error_type, probes, observer));
+
+    match params.threads {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("failed to build the solver thread pool")
+            .install(iterate),
+        None => iterate(),
+    }
+}
+
+/// Steps the solution until the error drops below `params.error_epsilon`
+fn iterate<T, ErrCalc, O>(
+    coefficients: &Coefficients<T>,
+    initial: Option<&Matrix<T>>,
+    params: SolverParams<T>,
+    error_type: &ErrCalc,
+    probes: &[Probe<T>],
+    mut observer: O,
+) -> SimulationResult<T>
+where
+    T: Float,
+    ErrCalc: CalculateError<T> + Sync,
+    O: Observer<T>,
 {
     let step_estimation = 10_000;
 
@@ -25,37 +108,51 @@ where
         error_type: error_type.to_error_type(),
         data: Vec::with_capacity(step_estimation),
     };
-    let mut step_data: Vec<StepData> = Vec::with_capacity(step_estimation);
-
-    let matrix_shape = (params.divisions, params.divisions, params.divisions);
-
-    let mut previous_temps: ndarray::Array3<f64> = ndarray::Array3::ones(matrix_shape) * 273.;
+    let mut step_data: Vec<StepData<T>> = Vec::with_capacity(step_estimation);
+    let mut probe_data: Vec<ProbeHistory<T>> = probes
+        .iter()
+        .map(|probe| ProbeHistory {
+            point: probe.point,
+            temperatures: Vec::with_capacity(step_estimation),
+        })
+        .collect();
+
+    let [nx, ny, nz] = coefficients.shape(params.divisions);
+    let matrix_shape = (nx, ny, nz);
+
+    // both buffers are allocated once and swapped after every step, `previous_temps`
+    // always holds the latest complete step once the swap has happened
+    let mut previous_temps: Matrix<T> = match initial {
+        Some(initial) => {
+            assert_eq!(
+                initial.dim(),
+                matrix_shape,
+                "the initial temperatures are not on the grid of the coefficients"
+            );
+            // the voids of a masked solve are NaN, and start cold as in any other
+            initial.mapv(|temp| if temp.is_nan() { lit(273.) } else { temp })
+        }
+        None => ndarray::Array3::from_elem(matrix_shape, lit(273.)),
+    };
+    let mut current_temps: Matrix<T> = previous_temps.clone();
 
+    let start = std::time::Instant::now();
     let mut i = 0;
-    let points = init_matrix(params.divisions);
 
     loop {
-        let mut current_temps: ndarray::Array3<f64> = ndarray::Array3::ones(matrix_shape);
-
-        ndarray::Zip::from(&mut current_temps)
-            .and(&points)
-            .par_map_collect(|temps, point| {
-                *temps = step(&previous_temps, &conditions, &params, &s, point)
-            });
+        let curr_error = coefficients.sweep(&previous_temps, &mut current_temps, error_type);
 
-        if i % 1_000 == 0 {
-            println! {"i:{}", i}
+        for (probe, history) in probes.iter().zip(probe_data.iter_mut()) {
+            history.temperatures.push(probe.sample(&current_temps));
         }
 
-        let curr_error = error_type.calculate_error(&previous_temps, &current_temps);
-
         if i % params.error_steps == 0 {
             error_decay.add_error(curr_error);
         }
 
         // check if we need to record this data for plotting
         if i % params.data_steps == 0 {
-            let raw_data = current_temps.clone().into_raw_vec();
+            let raw_data = current_temps.iter().copied().collect();
             let new_data = StepData {
                 step: i,
                 data: raw_data,
@@ -64,258 +161,65 @@ where
             step_data.push(new_data)
         }
 
-        if curr_error < params.error_epsilon {
+        let control = observer.observe(Progress {
+            step: i,
+            error: curr_error,
+            temperatures: &current_temps,
+        });
+
+        if i % 1_000 == 0 {
+            tracing::debug!(step = i, error = curr_error.to_f64(), "progress");
+        }
+
+        let converged = curr_error < params.error_epsilon;
+
+        if converged || control == Control::Stop {
             // record the current data
-            let raw_data = current_temps.clone().into_raw_vec();
+            let raw_data = current_temps.iter().copied().collect();
             let new_data = StepData {
                 step: i,
                 data: raw_data,
             };
             step_data.push(new_data);
 
-            // we are below the threshold for error right now, we can quit here
+            // we are below the threshold for error right now, or were asked to stop
+
+            let wall_time = start.elapsed().as_secs_f64();
+
+            if converged {
+                tracing::info!(
+                    steps = i,
+                    error = curr_error.to_f64(),
+                    wall_time,
+                    "converged"
+                );
+            } else {
+                tracing::warn!(
+                    steps = i,
+                    error = curr_error.to_f64(),
+                    wall_time,
+                    "stopped before reaching the error epsilon"
+                );
+            }
 
             let result = SimulationResult {
                 step_data,
                 error_decay,
                 size: params.divisions,
+                shape: (matrix_shape != (params.divisions, params.divisions, params.divisions))
+                    .then_some([nx, ny, nz]),
                 num_steps: i,
+                probes: probe_data,
+                final_error: Some(curr_error),
+                wall_time: Some(wall_time),
+                stopped: !converged,
             };
 
             return result;
         }
 
-        previous_temps = current_temps;
+        std::mem::swap(&mut previous_temps, &mut current_temps);
 
         i += 1
     } // loop
 }
-
-#[derive(Default, Debug)]
-pub(crate) struct Point {
-    x: usize,
-    y: usize,
-    z: usize,
-}
-
-/// Performs a single temperature calculation at a single step
-fn step<A, B, C, D, E, F>(
-    previous_temps: &Matrix,
-    conditions: &BoundaryConditions<A, B, C, D, E, F>,
-    params: &SolverParams,
-    s: &SolverInfo,
-    point: &Point,
-) -> T
-where
-    A: BoundaryCondition,
-    B: BoundaryCondition,
-    C: BoundaryCondition,
-    D: BoundaryCondition,
-    E: BoundaryCondition,
-    F: BoundaryCondition,
-{
-    let x = point.x;
-    let y = point.y;
-    let z = point.z;
-
-    let i_back;
-    let j_back;
-    let k_back;
-
-    let i_front;
-    let j_front;
-    let k_front;
-
-    /*
-     * check the backward conditions
-     */
-    if x == 0 {
-        i_back = 0.
-    } else {
-        i_back = previous_temps[[x - 1, y, z]];
-    }
-
-    if y == 0 {
-        j_back = 0.;
-    } else {
-        j_back = previous_temps[[x, y - 1, z]];
-    }
-
-    if z == 0 {
-        k_back = 0.;
-    } else {
-        k_back = previous_temps[[x, y, z - 1]];
-    }
-
-    //println!("finished backs");
-
-    /*
-     * check the forward conditions
-     */
-    if x == params.div_end() {
-        i_front = 0.
-    } else {
-        i_front = previous_temps[[x + 1, y, z]];
-    }
-
-    //println!("x forward");
-
-    if y == params.div_end() {
-        j_front = 0.;
-    } else {
-        j_front = previous_temps[[x, y + 1, z]];
-    }
-    //println!("y forward");
-
-    if z == params.div_end() {
-        k_front = 0.;
-    } else {
-        //dbg! {x,y,z+1};
-        k_front = previous_temps[[x, y, z + 1]];
-    }
-
-    //println!("finished forwards");
-
-    let information = prelude::Information {
-        i_back,
-        i_front,
-        j_back,
-        j_front,
-        k_back,
-        k_front,
-    };
-
-    let div_end = params.div_end();
-    let temp = match (x, y, z) {
-        /*
-         * Start with corners
-         *
-         * */
-        (x_, y_, z_) if x_ == 0 && y_ == 0 && z_ == 0 => conditions
-            .corners
-            .left_bot_back
-            .calculate_temperature(information, &s),
-        (x_, y_, z_) if x_ == 0 && y_ == 0 && z_ == div_end => conditions
-            .corners
-            .left_bot_front
-            .calculate_temperature(information, &s),
-        (x_, y_, z_) if x_ == 0 && y_ == div_end && z_ == 0 => conditions
-            .corners
-            .left_top_back
-            .calculate_temperature(information, &s),
-        (x_, y_, z_) if x_ == 0 && y_ == div_end && z_ == div_end => conditions
-            .corners
-            .left_top_front
-            .calculate_temperature(information, &s),
-
-        (x_, y_, z_) if x_ == div_end && y_ == 0 && z_ == 0 => conditions
-            .corners
-            .right_bot_back
-            .calculate_temperature(information, &s),
-        (x_, y_, z_) if x_ == div_end && y_ == 0 && z_ == div_end => conditions
-            .corners
-            .right_bot_front
-            .calculate_temperature(information, &s),
-        (x_, y_, z_) if x_ == div_end && y_ == div_end && z_ == 0 => conditions
-            .corners
-            .right_top_back
-            .calculate_temperature(information, &s),
-        (x_, y_, z_) if x_ == div_end && y_ == div_end && z_ == div_end => conditions
-            .corners
-            .right_top_front
-            .calculate_temperature(information, &s),
-
-        /*
-         * Do edges
-         *
-         * */
-        (x_, y_, _) if x_ == div_end && y_ == div_end => conditions
-            .edges
-            .right_top
-            .calculate_temperature(information, &s),
-        (x_, y_, _) if x_ == 0 && y_ == div_end => conditions
-            .edges
-            .left_top
-            .calculate_temperature(information, &s),
-        (x_, y_, _) if x_ == 0 && y_ == 0 => conditions
-            .edges
-            .left_bot
-            .calculate_temperature(information, &s),
-        (x_, y_, _) if x_ == div_end && y_ == 0 => conditions
-            .edges
-            .right_bot
-            .calculate_temperature(information, &s),
-
-        (_, y_, z_) if z_ == div_end && y_ == div_end => conditions
-            .edges
-            .front_top
-            .calculate_temperature(information, &s),
-        (_, y_, z_) if z_ == 0 && y_ == div_end => conditions
-            .edges
-            .back_top
-            .calculate_temperature(information, &s),
-        (_, y_, z_) if z_ == 0 && y_ == 0 => conditions
-            .edges
-            .back_bot
-            .calculate_temperature(information, &s),
-        (_, y_, z_) if z_ == div_end && y_ == 0 => conditions
-            .edges
-            .front_bot
-            .calculate_temperature(information, &s),
-
-        (x_, _, z_) if x_ == 0 && z_ == 0 => conditions
-            .edges
-            .back_left
-            .calculate_temperature(information, &s),
-        (x_, _, z_) if x_ == div_end && z_ == 0 => conditions
-            .edges
-            .back_right
-            .calculate_temperature(information, &s),
-        (x_, _, z_) if x_ == 0 && z_ == div_end => conditions
-            .edges
-            .front_left
-            .calculate_temperature(information, &s),
-        (x_, _, z_) if x_ == div_end && z_ == div_end => conditions
-            .edges
-            .front_right
-            .calculate_temperature(information, &s),
-
-        /*
-         * Do Walls
-         *
-         * */
-        (0, _, _) => conditions.walls.left.calculate_temperature(information, &s),
-        (x_, _, _) if x_ == div_end => conditions
-            .walls
-            .right
-            .calculate_temperature(information, &s),
-        (_, 0, _) => conditions.walls.bot.calculate_temperature(information, &s),
-        (_, y_, _) if y_ == div_end => conditions.walls.top.calculate_temperature(information, &s),
-        (_, _, 0) => conditions.walls.back.calculate_temperature(information, &s),
-        (_, _, z_) if z_ == div_end => conditions
-            .walls
-            .front
-            .calculate_temperature(information, &s),
-        /*
-         * General internal conduction
-         *
-         * */
-        (_, _, _) => conditions.internal.calculate_temperature(information, &s),
-    };
-    temp
-}
-
-pub(crate) fn init_matrix(size: usize) -> ndarray::Array3<Point> {
-    let mut matrix = ndarray::Array3::<Point>::default([size, size, size]);
-
-    for x in 0..size {
-        for y in 0..size {
-            for z in 0..size {
-                let point = Point { x, y, z };
-                matrix[[x, y, z]] = point;
-            }
-        }
-    }
-
-    matrix
-}
@@ -0,0 +1,1084 @@
+use crate::conditions::Condition;
+use crate::geometry::{self, Cell, Mask, Material};
+use crate::prelude::*;
+use crate::setup::BoundaryConditions;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+/// Precomputed update for a node: the new temperature is `constant` plus the
+/// weighted sum of the six neighbouring temperatures
+///
+/// weights are ordered `i_back, i_front, j_back, j_front, k_back, k_front`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stencil<T: Float> {
+    pub weights: [T; 6],
+    pub constant: T,
+}
+
+impl<T: Float> Stencil<T> {
+    /// Recovers the coefficients of a calculator
+    ///
+    /// every calculator is affine in the neighbouring temperatures, so evaluating it with
+    /// all neighbours at zero gives the constant and evaluating it with a single neighbour
+    /// at one gives that neighbour's weight
+    pub fn new<V: CalculateTemperature<T>>(calculator: &V, s: &SolverInfo<T>) -> Self {
+        let evaluate = |neighbours: [T; 6]| {
+            let info = Information {
+                i_back: neighbours[0],
+                i_front: neighbours[1],
+                j_back: neighbours[2],
+                j_front: neighbours[3],
+                k_back: neighbours[4],
+                k_front: neighbours[5],
+            };
+            calculator.calculate_temperature(info, s)
+        };
+
+        let constant = evaluate([T::zero(); 6]);
+        let mut weights = [T::zero(); 6];
+
+        for (index, weight) in weights.iter_mut().enumerate() {
+            let mut unit = [T::zero(); 6];
+            unit[index] = T::one();
+            *weight = evaluate(unit) - constant;
+        }
+
+        Stencil { weights, constant }
+    }
+
+    /// Weighted sum of the neighbours of `node`, the stencil without its constant
+    ///
+    /// neighbours outside of the domain are clamped onto the node itself, the calculators
+    /// never depend on them so their weight is always zero. Along a `periodic` axis the first
+    /// and last nodes are the same plane, so their neighbours outside are the second and the
+    /// second to last nodes
+    fn neighbours(&self, temps: &Matrix<T>, node: [usize; 3], periodic: [bool; 3]) -> T {
+        let end = temps.shape()[0] - 1;
+
+        let neighbour = |direction: usize| {
+            let axis = direction / 2;
+            let mut neighbour = node;
+
+            neighbour[axis] = match (direction % 2, node[axis], periodic[axis]) {
+                (0, 0, true) => end - 1,
+                (0, i, _) => i.saturating_sub(1),
+                (_, i, true) if i == end => 1,
+                (_, i, _) => (i + 1).min(end),
+            };
+            temps[neighbour]
+        };
+
+        self.weights
+            .iter()
+            .enumerate()
+            .fold(T::zero(), |total, (direction, &weight)| {
+                total + weight * neighbour(direction)
+            })
+    }
+
+    /// Weighted sum of the neighbours of `node` that it is connected to
+    ///
+    /// the neighbours across a void have no weight and may not have a temperature at all,
+    /// so they are skipped rather than multiplied by zero. Along a `periodic` axis the
+    /// neighbours of the first and last nodes wrap around to each other
+    fn connected(&self, temps: &Matrix<T>, node: [usize; 3], periodic: [bool; 3]) -> T {
+        let shape = temps.shape();
+
+        let neighbour = |direction: usize| {
+            let axis = direction / 2;
+            let n = shape[axis];
+            let mut neighbour = node;
+
+            neighbour[axis] = match (direction % 2, periodic[axis]) {
+                (0, true) => (node[axis] + n - 1) % n,
+                (0, false) => node[axis].wrapping_sub(1),
+                (_, true) => (node[axis] + 1) % n,
+                (_, false) => node[axis] + 1,
+            };
+            neighbour
+        };
+
+        self.weights
+            .iter()
+            .enumerate()
+            .filter(|(_, weight)| !weight.is_zero())
+            .fold(T::zero(), |total, (direction, &weight)| {
+                total + weight * temps[neighbour(direction)]
+            })
+    }
+}
+
+/// Control volume of a node of a masked geometry, the part of the cell sized box around
+/// the node that is solid
+///
+/// areas are fractions of a full cell's face and the volume a fraction of a full cell, so a
+/// node in the middle of a solid region of the input's material has the same coefficients as
+/// `InternalConduction`
+struct ControlVolume<'a, T: Float> {
+    /// area conducting to each neighbour, in the order of `Stencil::weights`, scaled by the
+    /// conductivity of its cells relative to the input's, with any contacts in series
+    weights: [T; 6],
+    /// heat generated per the volume of a full cell `[W/m^3]`
+    generation: T,
+    /// faces exposed to a void or the outside of the cube, with their conditions
+    faces: Vec<(T, &'a dyn BoundaryCondition<T>)>,
+}
+
+impl<'a, T: Float> ControlVolume<'a, T> {
+    /// `weights` are those of `Mask::conductances` and `outer` the conditions of the faces of
+    /// the cube, ordered like them
+    fn new(
+        octants: &[Option<Cell>; 8],
+        weights: [T; 6],
+        outer: [&'a dyn BoundaryCondition<T>; 6],
+        surfaces: &'a [Condition<T>],
+        materials: &[Material<T>],
+        s: &SolverInfo<T>,
+    ) -> Self {
+        let quarter = lit::<T>(0.25);
+
+        // each solid cell generates an eighth of a full cell's heat
+        let generation = octants
+            .iter()
+            .flatten()
+            .fold(T::zero(), |generation, cell| match cell {
+                Cell::Solid(material) => generation + materials[*material].q_dot(s) * lit(0.125),
+                Cell::Void(_) => generation,
+            });
+
+        // the faces between a solid and a void cell through the node are a quarter each
+        let faces = geometry::exposed(octants)
+            .into_iter()
+            .map(|(outward, cell)| {
+                let condition = match cell {
+                    Some(Cell::Void(surface)) => &surfaces[surface],
+                    _ => outer[outward],
+                };
+
+                (quarter, condition)
+            })
+            .collect();
+
+        ControlVolume {
+            weights,
+            generation,
+            faces,
+        }
+    }
+}
+
+impl<T: Float> CalculateTemperature<T> for ControlVolume<'_, T> {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
+        for (_, condition) in &self.faces {
+            if let Some(temperature) = condition.constant_temperature() {
+                return temperature;
+            }
+        }
+
+        let neighbours = [
+            info.i_back,
+            info.i_front,
+            info.j_back,
+            info.j_front,
+            info.k_back,
+            info.k_front,
+        ];
+
+        let mut m = self.generation * s.del2() / s.k;
+        let mut div = T::zero();
+
+        for (&weight, neighbour) in self.weights.iter().zip(neighbours) {
+            m = m + weight * neighbour;
+            div = div + weight;
+        }
+
+        for &(area, condition) in &self.faces {
+            let area = area * s.del2();
+            m = m + condition.lhs_constant(&info, s, area);
+            div = div + condition.rhs_constant(&info, s, area);
+        }
+
+        m / div
+    }
+}
+
+/// Heat balance of the control volume of one node, for grids whose nodes are not a cell apart
+/// along every axis
+///
+/// every term is in absolute units rather than fractions of a cell
+pub(crate) struct Balance<'a, T: Float> {
+    /// conductance to each neighbour divided by the conductivity `[m]`, ordered like the
+    /// weights of a stencil and zero where there is none, scaled by `SolverInfo::anisotropy`
+    /// along each axis when the balance is evaluated
+    pub(crate) conductances: [T; 6],
+    /// `[m^3]`
+    pub(crate) volume: T,
+    /// faces on the surface of the domain with their areas `[m^2]`
+    pub(crate) faces: Vec<(T, &'a dyn BoundaryCondition<T>)>,
+}
+
+impl<T: Float> Balance<'_, T> {
+    /// Conductances with the conductivity along each axis relative to `s.k`
+    fn scaled(&self, s: &SolverInfo<T>) -> [T; 6] {
+        let mut scaled = self.conductances;
+        for (direction, conductance) in scaled.iter_mut().enumerate() {
+            *conductance = *conductance * s.anisotropy[direction / 2];
+        }
+        scaled
+    }
+
+    /// Coefficient of the node's own temperature, every other term of its balance is divided
+    /// by it
+    pub(crate) fn diagonal(&self, s: &SolverInfo<T>) -> T {
+        let info = Information::builder()
+            .i_front(T::zero())
+            .i_back(T::zero())
+            .j_front(T::zero())
+            .j_back(T::zero())
+            .k_front(T::zero())
+            .k_back(T::zero())
+            .build();
+
+        self.faces
+            .iter()
+            .filter(|(_, condition)| condition.constant_temperature().is_none())
+            .fold(
+                self.scaled(s).iter().fold(T::zero(), |sum, &c| sum + c) / s.del,
+                |sum, &(area, condition)| sum + condition.rhs_constant(&info, s, area),
+            )
+    }
+}
+
+impl<T: Float> CalculateTemperature<T> for Balance<'_, T> {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
+        for (_, condition) in &self.faces {
+            if let Some(temperature) = condition.constant_temperature() {
+                return temperature;
+            }
+        }
+
+        let neighbours = [
+            info.i_back,
+            info.i_front,
+            info.j_back,
+            info.j_front,
+            info.k_back,
+            info.k_front,
+        ];
+
+        // every term is divided by `k del` like the conditions' constants
+        let mut m = s.q_dot * self.volume / (s.k * s.del);
+        let mut div = T::zero();
+
+        for (conductance, neighbour) in self.scaled(s).into_iter().zip(neighbours) {
+            m = m + conductance * neighbour / s.del;
+            div = div + conductance / s.del;
+        }
+
+        for &(area, condition) in &self.faces {
+            m = m + condition.lhs_constant(&info, s, area);
+            div = div + condition.rhs_constant(&info, s, area);
+        }
+
+        m / div
+    }
+}
+
+/// Index of the nodes of a masked geometry without any solid cell around them
+pub const INACTIVE: u32 = u32::MAX;
+
+/// Stencils of every node of a masked geometry or a grid of another shape
+///
+/// nodes with the same cells around them share a stencil
+pub struct Nodes<T: Float> {
+    pub stencils: Vec<Stencil<T>>,
+    /// index into `stencils` of every node, `INACTIVE` for nodes outside of the geometry
+    pub index: ndarray::Array3<u32>,
+    /// axes whose last node neighbours the first, e.g. the angle around a full cylinder
+    pub periodic: [bool; 3],
+    /// the nodes at `x = 0` are a single point for each `z`, such as the axis of a cylinder,
+    /// and all take the mean of their updates weighted by these, one per `y`
+    pub axis: Option<Vec<T>>,
+}
+
+/// A box of nodes sharing a single stencil
+pub struct Region<T: Float> {
+    pub x: Range<usize>,
+    pub y: Range<usize>,
+    pub z: Range<usize>,
+    pub stencil: Stencil<T>,
+    /// per node constants of the box replacing the stencil's, see `with_boundary_constants`
+    pub constants: Option<Matrix<T>>,
+}
+
+#[derive(Clone, Copy)]
+enum Side {
+    Low,
+    Mid,
+    High,
+}
+
+impl Side {
+    fn span(self, divisions: usize) -> Range<usize> {
+        let end = divisions - 1;
+        match self {
+            Side::Low => 0..1,
+            Side::Mid => 1..end,
+            Side::High => end..divisions,
+        }
+    }
+
+    fn of(index: usize, divisions: usize) -> Self {
+        if index == 0 {
+            Side::Low
+        } else if index == divisions - 1 {
+            Side::High
+        } else {
+            Side::Mid
+        }
+    }
+}
+
+/// How the interior nodes are divided up between threads
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(tag = "type")]
+pub enum Sweep {
+    /// one x slab of the cube per task
+    #[default]
+    Slab,
+    /// blocks of at most `tile` nodes along (x, y, z) per task
+    Tiled {
+        #[serde(default = "default_tile")]
+        tile: [usize; 3],
+    },
+}
+
+/// Fastest tile measured at 128^3, rows along z are kept whole
+pub const DEFAULT_TILE: [usize; 3] = [8, 32, 1024];
+
+fn default_tile() -> [usize; 3] {
+    DEFAULT_TILE
+}
+
+/// Conditions the stencils of the cube cannot be built from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// a periodic face whose opposite face along the axis is not periodic
+    UnpairedPeriodic(usize),
+    /// a periodic pair of faces on a masked geometry, which only supports them on the full cube
+    MaskedPeriodic(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnpairedPeriodic(axis) => write!(
+                f,
+                "the periodic face along {} has no periodic face opposite it",
+                ["x", "y", "z"][*axis]
+            ),
+            Error::MaskedPeriodic(axis) => write!(
+                f,
+                "the faces along {} are periodic, which a masked geometry does not support",
+                ["x", "y", "z"][*axis]
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// All the coefficients the solver needs, calculated once before iterating
+pub struct Coefficients<T: Float> {
+    pub interior: Stencil<T>,
+    /// the 6 walls, 12 edges and 8 corners of the cube
+    pub boundaries: Vec<Region<T>>,
+    /// per node constants replacing those of the stencils, see `with_constants`
+    pub constants: Option<Matrix<T>>,
+    /// stencils of a masked geometry, which replace all of the above, see `masked`
+    pub nodes: Option<Nodes<T>>,
+    /// axes whose faces are both periodic, see `neighbours`
+    pub periodic: [bool; 3],
+    pub sweep: Sweep,
+}
+
+impl<T: Float> Coefficients<T> {
+    /// Coefficients of the full cube, failing when a periodic face is not opposite another
+    pub fn new<A, B, C, D, E, F>(
+        conditions: &BoundaryConditions<A, B, C, D, E, F>,
+        s: &SolverInfo<T>,
+        divisions: usize,
+        sweep: Sweep,
+    ) -> Result<Self, Error>
+    where
+        A: BoundaryCondition<T>,
+        B: BoundaryCondition<T>,
+        C: BoundaryCondition<T>,
+        D: BoundaryCondition<T>,
+        E: BoundaryCondition<T>,
+        F: BoundaryCondition<T>,
+    {
+        use Side::*;
+
+        let periodic = paired(conditions)?;
+        let interior = stencil_at(conditions, s, [Mid, Mid, Mid]);
+
+        let mut boundaries = Vec::with_capacity(26);
+
+        for x in [Low, Mid, High] {
+            for y in [Low, Mid, High] {
+                for z in [Low, Mid, High] {
+                    if let (Mid, Mid, Mid) = (x, y, z) {
+                        continue;
+                    }
+
+                    boundaries.push(Region {
+                        x: x.span(divisions),
+                        y: y.span(divisions),
+                        z: z.span(divisions),
+                        stencil: stencil_at(conditions, s, [x, y, z]),
+                        constants: None,
+                    });
+                }
+            }
+        }
+
+        Ok(Coefficients {
+            interior,
+            boundaries,
+            constants: None,
+            nodes: None,
+            periodic,
+            sweep,
+        })
+    }
+
+    /// Coefficients of the solid cells of `mask`, with the faces they expose to its voids
+    /// taking the void's surface condition and those on the faces of the cube the condition
+    /// of that face
+    ///
+    /// every stencil is derived from which of the eight cells around a node are solid, so the
+    /// full cube gives the same coefficients as `new`. Nodes without a solid cell around
+    /// them are set to NaN. The nodes are swept one at a time, which is slower than the
+    /// row sweeps of the full cube, and `with_constants` does not apply.
+    /// Fails on periodic faces, which only the full cube supports
+    pub fn masked<A, B, C, D, E, F>(
+        conditions: &BoundaryConditions<A, B, C, D, E, F>,
+        s: &SolverInfo<T>,
+        mask: &Mask<T>,
+        sweep: Sweep,
+    ) -> Result<Self, Error>
+    where
+        A: BoundaryCondition<T>,
+        B: BoundaryCondition<T>,
+        C: BoundaryCondition<T>,
+        D: BoundaryCondition<T>,
+        E: BoundaryCondition<T>,
+        F: BoundaryCondition<T>,
+    {
+        if let Some(axis) = paired(conditions)?.iter().position(|&periodic| periodic) {
+            return Err(Error::MaskedPeriodic(axis));
+        }
+
+        let walls = &conditions.walls;
+        let outer: [&dyn BoundaryCondition<T>; 6] = [
+            &walls.left.left_boundary,
+            &walls.right.right_boundary,
+            &walls.bot.bot_boundary,
+            &walls.top.top_boundary,
+            &walls.back.back_boundary,
+            &walls.front.front_boundary,
+        ];
+
+        let divisions = mask.divisions();
+        let mut stencils = Vec::new();
+        // contacts make the weights differ between nodes with the same cells around them
+        let mut shared: HashMap<([Option<Cell>; 8], [u64; 6]), u32> = HashMap::new();
+
+        let index = ndarray::Array3::from_shape_fn((divisions, divisions, divisions), |node| {
+            let node = [node.0, node.1, node.2];
+            let octants = mask.octants(node);
+
+            if !octants.into_iter().flatten().any(Cell::is_solid) {
+                return INACTIVE;
+            }
+
+            let weights = mask.conductances(node, s);
+            let key = weights.map(|weight| weight.to_f64().unwrap().to_bits());
+
+            *shared.entry((octants, key)).or_insert_with(|| {
+                let volume = ControlVolume::new(
+                    &octants,
+                    weights,
+                    outer,
+                    &mask.surfaces,
+                    &mask.materials,
+                    s,
+                );
+                stencils.push(Stencil::new(&volume, s));
+                (stencils.len() - 1) as u32
+            })
+        });
+
+        Ok(Coefficients {
+            interior: Stencil {
+                weights: [T::zero(); 6],
+                constant: T::zero(),
+            },
+            boundaries: Vec::new(),
+            constants: None,
+            nodes: Some(Nodes {
+                stencils,
+                index,
+                periodic: [false; 3],
+                axis: None,
+            }),
+            periodic: [false; 3],
+            sweep,
+        })
+    }
+
+    /// Coefficients of a grid with a stencil for every node, such as the cylindrical one
+    ///
+    /// the interior and boundary regions of the cube are unused
+    pub fn from_nodes(nodes: Nodes<T>, sweep: Sweep) -> Self {
+        Coefficients {
+            interior: Stencil {
+                weights: [T::zero(); 6],
+                constant: T::zero(),
+            },
+            boundaries: Vec::new(),
+            constants: None,
+            nodes: Some(nodes),
+            periodic: [false; 3],
+            sweep,
+        }
+    }
+
+    /// Nodes along each axis, `divisions` for the cube
+    pub fn shape(&self, divisions: usize) -> [usize; 3] {
+        match &self.nodes {
+            Some(nodes) => {
+                let shape = nodes.index.shape();
+                [shape[0], shape[1], shape[2]]
+            }
+            None => [divisions; 3],
+        }
+    }
+
+    /// Gives every node its own constant, for sources and boundary data that vary in space
+    ///
+    /// `node` is called with the index of every node and returns the conditions and solver
+    /// information there. Only the constants of the resulting stencils are kept, so the
+    /// weights have to be the same as those of the uniform conditions
+    pub fn with_constants<A, B, C, D, E, F, G>(mut self, divisions: usize, node: G) -> Self
+    where
+        A: BoundaryCondition<T>,
+        B: BoundaryCondition<T>,
+        C: BoundaryCondition<T>,
+        D: BoundaryCondition<T>,
+        E: BoundaryCondition<T>,
+        F: BoundaryCondition<T>,
+        G: Fn([usize; 3]) -> (BoundaryConditions<A, B, C, D, E, F>, SolverInfo<T>),
+    {
+        let shape = (divisions, divisions, divisions);
+
+        let constants = Matrix::from_shape_fn(shape, |(x, y, z)| {
+            let (conditions, s) = node([x, y, z]);
+            let sides = [x, y, z].map(|index| Side::of(index, divisions));
+
+            stencil_at(&conditions, &s, sides).constant
+        });
+
+        self.constants = Some(constants);
+        self
+    }
+
+    /// Gives every node on the faces of the cube its own constant, as `with_constants` does,
+    /// for boundary data that varies along the faces
+    ///
+    /// `node` is only called with the nodes of the walls, edges and corners, whose constants
+    /// are kept with their region, so nothing is stored for the interior. They take the
+    /// place of those of `with_constants` there
+    pub fn with_boundary_constants<A, B, C, D, E, F, G>(mut self, divisions: usize, node: G) -> Self
+    where
+        A: BoundaryCondition<T>,
+        B: BoundaryCondition<T>,
+        C: BoundaryCondition<T>,
+        D: BoundaryCondition<T>,
+        E: BoundaryCondition<T>,
+        F: BoundaryCondition<T>,
+        G: Fn([usize; 3]) -> (BoundaryConditions<A, B, C, D, E, F>, SolverInfo<T>),
+    {
+        for region in &mut self.boundaries {
+            let shape = (region.x.len(), region.y.len(), region.z.len());
+            let offset = [region.x.start, region.y.start, region.z.start];
+
+            region.constants = Some(Matrix::from_shape_fn(shape, |(i, j, k)| {
+                let index = [i + offset[0], j + offset[1], k + offset[2]];
+                let (conditions, s) = node(index);
+                let sides = index.map(|index| Side::of(index, divisions));
+
+                stencil_at(&conditions, &s, sides).constant
+            }));
+        }
+
+        self
+    }
+
+    /// Calculates every node of `current_temps` from `previous_temps` and returns the
+    /// error between the two steps, reduced in the same passes
+    pub fn sweep<ErrCalc>(
+        &self,
+        previous_temps: &Matrix<T>,
+        current_temps: &mut Matrix<T>,
+        error_type: &ErrCalc,
+    ) -> T
+    where
+        ErrCalc: CalculateError<T> + Sync,
+    {
+        if let Some(nodes) = &self.nodes {
+            let total = sweep_nodes(nodes, previous_temps, current_temps, error_type);
+            return error_type.finish(total);
+        }
+
+        let interior = match self.sweep {
+            Sweep::Slab => self.sweep_slabs(previous_temps, current_temps, error_type),
+            Sweep::Tiled { tile } => {
+                self.sweep_tiled(previous_temps, current_temps, tile, error_type)
+            }
+        };
+
+        let boundaries = self
+            .boundaries
+            .iter()
+            .map(|region| {
+                let constants = self.constants.as_ref();
+                sweep_region(
+                    region,
+                    constants,
+                    self.periodic,
+                    previous_temps,
+                    current_temps,
+                    error_type,
+                )
+            })
+            .fold(interior, |left, right| error_type.combine(left, right));
+
+        error_type.finish(boundaries)
+    }
+
+    /// Interior sweep with one x slab per task
+    fn sweep_slabs<ErrCalc>(
+        &self,
+        previous_temps: &Matrix<T>,
+        current_temps: &mut Matrix<T>,
+        error_type: &ErrCalc,
+    ) -> T
+    where
+        ErrCalc: CalculateError<T> + Sync,
+    {
+        use rayon::prelude::*;
+
+        let n = previous_temps.shape()[0];
+        let nn = n * n;
+
+        if n < 3 {
+            return T::zero();
+        }
+
+        let previous = previous_temps
+            .as_slice()
+            .expect("temperature matrix is in standard layout");
+        let current = current_temps
+            .as_slice_mut()
+            .expect("temperature matrix is in standard layout");
+
+        let len = n - 2;
+
+        current
+            .par_chunks_mut(nn)
+            .enumerate()
+            .skip(1)
+            .take(len)
+            .map(|(x, slab)| {
+                let mut total = T::zero();
+
+                for y in 1..n - 1 {
+                    // index of the first interior node in this row
+                    let start = (x * n + y) * n + 1;
+                    let out = &mut slab[y * n + 1..y * n + 1 + len];
+
+                    total = self.sweep_row(previous, n, start, out, total, error_type);
+                }
+
+                total
+            })
+            .reduce(T::zero, |left, right| error_type.combine(left, right))
+    }
+
+    /// Interior sweep over blocks of at most `tile` nodes, so that the rows of the
+    /// previous step a block reads stay in cache while it is being calculated
+    ///
+    /// the interior is bisected recursively, each half becoming its own task, until
+    /// every block fits inside a tile
+    fn sweep_tiled<ErrCalc>(
+        &self,
+        previous_temps: &Matrix<T>,
+        current_temps: &mut Matrix<T>,
+        tile: [usize; 3],
+        error_type: &ErrCalc,
+    ) -> T
+    where
+        ErrCalc: CalculateError<T> + Sync,
+    {
+        let n = previous_temps.shape()[0];
+
+        if n < 3 {
+            return T::zero();
+        }
+
+        let previous = previous_temps
+            .as_slice()
+            .expect("temperature matrix is in standard layout");
+
+        let interior = current_temps.slice_mut(ndarray::s![1..n - 1, 1..n - 1, 1..n - 1]);
+        let tile = tile.map(|size| size.max(1));
+
+        self.sweep_block(previous, n, interior, [1, 1, 1], tile, error_type)
+    }
+
+    fn sweep_block<ErrCalc>(
+        &self,
+        previous: &[T],
+        n: usize,
+        mut block: ndarray::ArrayViewMut3<T>,
+        origin: [usize; 3],
+        tile: [usize; 3],
+        error_type: &ErrCalc,
+    ) -> T
+    where
+        ErrCalc: CalculateError<T> + Sync,
+    {
+        let shape = [block.shape()[0], block.shape()[1], block.shape()[2]];
+
+        // split along the axis that spans the most tiles
+        let axis = (0..3)
+            .filter(|&axis| shape[axis] > tile[axis])
+            .max_by_key(|&axis| shape[axis] / tile[axis]);
+
+        if let Some(axis) = axis {
+            let tiles = shape[axis].div_ceil(tile[axis]);
+            let middle = (tiles / 2) * tile[axis];
+
+            let (low, high) = block.split_at(ndarray::Axis(axis), middle);
+            let mut high_origin = origin;
+            high_origin[axis] += middle;
+
+            let (low, high) = rayon::join(
+                || self.sweep_block(previous, n, low, origin, tile, error_type),
+                || self.sweep_block(previous, n, high, high_origin, tile, error_type),
+            );
+
+            return error_type.combine(low, high);
+        }
+
+        let mut total = T::zero();
+
+        for x in 0..shape[0] {
+            for y in 0..shape[1] {
+                let start = ((origin[0] + x) * n + origin[1] + y) * n + origin[2];
+                let mut row = block.slice_mut(ndarray::s![x, y, ..]);
+                let out = row
+                    .as_slice_mut()
+                    .expect("rows of the temperature matrix are contiguous");
+
+                total = self.sweep_row(previous, n, start, out, total, error_type);
+            }
+        }
+
+        total
+    }
+
+    /// Branch free update of `out.len()` interior nodes along z, starting at the flat
+    /// index `start` of the previous step
+    #[inline(always)]
+    fn sweep_row<ErrCalc>(
+        &self,
+        previous: &[T],
+        n: usize,
+        start: usize,
+        out: &mut [T],
+        mut total: T,
+        error_type: &ErrCalc,
+    ) -> T
+    where
+        ErrCalc: CalculateError<T>,
+    {
+        let [w0, w1, w2, w3, w4, w5] = self.interior.weights;
+        let constant = self.interior.constant;
+
+        let nn = n * n;
+        let len = out.len();
+
+        let old = &previous[start..start + len];
+        let i_back = &previous[start - nn..start - nn + len];
+        let i_front = &previous[start + nn..start + nn + len];
+        let j_back = &previous[start - n..start - n + len];
+        let j_front = &previous[start + n..start + n + len];
+        let k_back = &previous[start - 1..start - 1 + len];
+        let k_front = &previous[start + 1..start + 1 + len];
+
+        let update = |z: usize, constant: T| {
+            constant
+                + w0 * i_back[z]
+                + w1 * i_front[z]
+                + w2 * j_back[z]
+                + w3 * j_front[z]
+                + w4 * k_back[z]
+                + w5 * k_front[z]
+        };
+
+        // the two loops keep the uniform case free of the extra load per node
+        match &self.constants {
+            Some(constants) => {
+                let constants = constants
+                    .as_slice()
+                    .expect("constant matrix is in standard layout");
+
+                for (z, &constant) in constants[start..start + len].iter().enumerate() {
+                    let new = update(z, constant);
+                    out[z] = new;
+                    total = error_type.accumulate(total, new - old[z]);
+                }
+            }
+            None => {
+                for z in 0..len {
+                    let new = update(z, constant);
+                    out[z] = new;
+                    total = error_type.accumulate(total, new - old[z]);
+                }
+            }
+        }
+
+        total
+    }
+}
+
+/// Axes along which both faces of the cube are periodic
+fn periodic<T, A, B, C, D, E, F>(conditions: &BoundaryConditions<A, B, C, D, E, F>) -> [bool; 3]
+where
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
+    D: BoundaryCondition<T>,
+    E: BoundaryCondition<T>,
+    F: BoundaryCondition<T>,
+{
+    let walls = &conditions.walls;
+
+    [
+        walls.left.left_boundary.is_periodic() && walls.right.right_boundary.is_periodic(),
+        walls.bot.bot_boundary.is_periodic() && walls.top.top_boundary.is_periodic(),
+        walls.back.back_boundary.is_periodic() && walls.front.front_boundary.is_periodic(),
+    ]
+}
+
+/// Axes whose faces are both periodic, failing on one that is periodic on a single face
+fn paired<T, A, B, C, D, E, F>(
+    conditions: &BoundaryConditions<A, B, C, D, E, F>,
+) -> Result<[bool; 3], Error>
+where
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
+    D: BoundaryCondition<T>,
+    E: BoundaryCondition<T>,
+    F: BoundaryCondition<T>,
+{
+    let walls = &conditions.walls;
+    let faces = [
+        (
+            walls.left.left_boundary.is_periodic(),
+            walls.right.right_boundary.is_periodic(),
+        ),
+        (
+            walls.bot.bot_boundary.is_periodic(),
+            walls.top.top_boundary.is_periodic(),
+        ),
+        (
+            walls.back.back_boundary.is_periodic(),
+            walls.front.front_boundary.is_periodic(),
+        ),
+    ];
+
+    let mut periodic = [false; 3];
+    for (axis, (low, high)) in faces.into_iter().enumerate() {
+        if low != high {
+            return Err(Error::UnpairedPeriodic(axis));
+        }
+        periodic[axis] = low;
+    }
+
+    Ok(periodic)
+}
+
+/// Stencil of the nodes on the given sides of the cube
+///
+/// the nodes on the faces of a periodic axis take the stencil of the ones between them
+fn stencil_at<T, A, B, C, D, E, F>(
+    conditions: &BoundaryConditions<A, B, C, D, E, F>,
+    s: &SolverInfo<T>,
+    sides: [Side; 3],
+) -> Stencil<T>
+where
+    T: Float,
+    A: BoundaryCondition<T>,
+    B: BoundaryCondition<T>,
+    C: BoundaryCondition<T>,
+    D: BoundaryCondition<T>,
+    E: BoundaryCondition<T>,
+    F: BoundaryCondition<T>,
+{
+    use Side::*;
+
+    let periodic = periodic(conditions);
+    let sides: [Side; 3] =
+        std::array::from_fn(|axis| if periodic[axis] { Mid } else { sides[axis] });
+
+    // x: left / right, y: bot / top, z: back / front
+    match (sides[0], sides[1], sides[2]) {
+        (Mid, Mid, Mid) => Stencil::new(&conditions.internal, s),
+
+        /*
+         * Corners
+         */
+        (Low, Low, Low) => Stencil::new(&conditions.corners.left_bot_back, s),
+        (Low, Low, High) => Stencil::new(&conditions.corners.left_bot_front, s),
+        (Low, High, Low) => Stencil::new(&conditions.corners.left_top_back, s),
+        (Low, High, High) => Stencil::new(&conditions.corners.left_top_front, s),
+        (High, Low, Low) => Stencil::new(&conditions.corners.right_bot_back, s),
+        (High, Low, High) => Stencil::new(&conditions.corners.right_bot_front, s),
+        (High, High, Low) => Stencil::new(&conditions.corners.right_top_back, s),
+        (High, High, High) => Stencil::new(&conditions.corners.right_top_front, s),
+
+        /*
+         * Edges
+         */
+        (High, High, Mid) => Stencil::new(&conditions.edges.right_top, s),
+        (Low, High, Mid) => Stencil::new(&conditions.edges.left_top, s),
+        (Low, Low, Mid) => Stencil::new(&conditions.edges.left_bot, s),
+        (High, Low, Mid) => Stencil::new(&conditions.edges.right_bot, s),
+
+        (Mid, High, High) => Stencil::new(&conditions.edges.front_top, s),
+        (Mid, High, Low) => Stencil::new(&conditions.edges.back_top, s),
+        (Mid, Low, Low) => Stencil::new(&conditions.edges.back_bot, s),
+        (Mid, Low, High) => Stencil::new(&conditions.edges.front_bot, s),
+
+        (Low, Mid, Low) => Stencil::new(&conditions.edges.back_left, s),
+        (High, Mid, Low) => Stencil::new(&conditions.edges.back_right, s),
+        (Low, Mid, High) => Stencil::new(&conditions.edges.front_left, s),
+        (High, Mid, High) => Stencil::new(&conditions.edges.front_right, s),
+
+        /*
+         * Walls
+         */
+        (Low, Mid, Mid) => Stencil::new(&conditions.walls.left, s),
+        (High, Mid, Mid) => Stencil::new(&conditions.walls.right, s),
+        (Mid, Low, Mid) => Stencil::new(&conditions.walls.bot, s),
+        (Mid, High, Mid) => Stencil::new(&conditions.walls.top, s),
+        (Mid, Mid, Low) => Stencil::new(&conditions.walls.back, s),
+        (Mid, Mid, High) => Stencil::new(&conditions.walls.front, s),
+    }
+}
+
+/// Sweeps a single wall, edge or corner
+fn sweep_region<T, ErrCalc>(
+    region: &Region<T>,
+    constants: Option<&Matrix<T>>,
+    periodic: [bool; 3],
+    previous_temps: &Matrix<T>,
+    current_temps: &mut Matrix<T>,
+    error_type: &ErrCalc,
+) -> T
+where
+    T: Float,
+    ErrCalc: CalculateError<T> + Sync,
+{
+    let (x, y, z) = (region.x.clone(), region.y.clone(), region.z.clone());
+    let offset = (x.start, y.start, z.start);
+    let shape = (x.len(), y.len(), z.len());
+
+    // without per node constants the stencil's own is broadcast over the region
+    let uniform = ndarray::arr0(region.stencil.constant);
+    let constants = match (&region.constants, constants) {
+        (Some(own), _) => own.view(),
+        (None, Some(constants)) => constants.slice(ndarray::s![x.clone(), y.clone(), z.clone()]),
+        (None, None) => uniform
+            .broadcast(shape)
+            .expect("a scalar broadcasts to any shape"),
+    };
+
+    ndarray::Zip::indexed(current_temps.slice_mut(ndarray::s![x.clone(), y.clone(), z.clone()]))
+        .and(previous_temps.slice(ndarray::s![x, y, z]))
+        .and(constants)
+        .par_fold(
+            T::zero,
+            |total, (i, j, k), current, previous, &constant| {
+                let (x, y, z) = (i + offset.0, j + offset.1, k + offset.2);
+
+                let neighbours = region
+                    .stencil
+                    .neighbours(previous_temps, [x, y, z], periodic);
+                *current = constant + neighbours;
+                error_type.accumulate(total, *current - *previous)
+            },
+            |left, right| error_type.combine(left, right),
+        )
+}
+
+/// Sweeps every node of a masked geometry with its own stencil
+fn sweep_nodes<T, ErrCalc>(
+    nodes: &Nodes<T>,
+    previous_temps: &Matrix<T>,
+    current_temps: &mut Matrix<T>,
+    error_type: &ErrCalc,
+) -> T
+where
+    T: Float,
+    ErrCalc: CalculateError<T> + Sync,
+{
+    let update = |node: [usize; 3]| {
+        let stencil = &nodes.stencils[nodes.index[node] as usize];
+        stencil.constant + stencil.connected(previous_temps, node, nodes.periodic)
+    };
+
+    ndarray::Zip::indexed(current_temps)
+        .and(previous_temps)
+        .and(&nodes.index)
+        .par_fold(
+            T::zero,
+            |total, (x, y, z), current, &previous, &index| {
+                if index == INACTIVE {
+                    *current = T::nan();
+                    return total;
+                }
+
+                *current = match &nodes.axis {
+                    Some(weights) if x == 0 => weights
+                        .iter()
+                        .enumerate()
+                        .fold(T::zero(), |sum, (y, &weight)| {
+                            sum + weight * update([0, y, z])
+                        }),
+                    _ => update([x, y, z]),
+                };
+                error_type.accumulate(total, *current - previous)
+            },
+            |left, right| error_type.combine(left, right),
+        )
+}
@@ -0,0 +1,372 @@
+//! Triangle meshes read from STL files, and their voxelization onto the solver's grid
+//!
+//! ASCII files may hold several `solid`s, each becoming a named group of triangles whose
+//! surface can be given its own condition. Binary files have no names and are a single group
+//! named after the file
+
+use std::fmt;
+use std::path::Path;
+
+/// A triangle of a mesh, and the index of the group it belongs to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Triangle {
+    pub vertices: [[f64; 3]; 3],
+    pub group: usize,
+}
+
+/// The triangles of one or more closed surfaces
+#[derive(Clone, Debug, Default)]
+pub struct Mesh {
+    pub triangles: Vec<Triangle>,
+    /// names of the solids of an ASCII file, in the order they appear
+    pub groups: Vec<String>,
+}
+
+/// Why an STL file could not be read
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    /// a binary file shorter than its triangle count says
+    Truncated,
+    /// an ASCII file with an unexpected token, and the line it is on
+    Syntax(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "{error}"),
+            Error::Truncated => write!(f, "the binary STL is shorter than its triangle count"),
+            Error::Syntax(line) => write!(f, "the ASCII STL is malformed on line {line}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl Mesh {
+    /// Reads an ASCII or binary STL file
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)?;
+
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Self::parse(&bytes, &name)
+    }
+
+    /// Parses the contents of an STL file, `name` is the group of a binary file
+    pub fn parse(bytes: &[u8], name: &str) -> Result<Self, Error> {
+        // binary files may start with `solid` too, but only they have a size matching the
+        // triangle count after their header
+        let binary_size = bytes
+            .get(80..84)
+            .map(|count| 84 + 50 * u32::from_le_bytes(count.try_into().unwrap()) as usize);
+
+        if bytes.starts_with(b"solid") && binary_size != Some(bytes.len()) {
+            Self::parse_ascii(&String::from_utf8_lossy(bytes))
+        } else {
+            Self::parse_binary(bytes, name)
+        }
+    }
+
+    fn parse_binary(bytes: &[u8], name: &str) -> Result<Self, Error> {
+        let count = bytes.get(80..84).ok_or(Error::Truncated)?;
+        let count = u32::from_le_bytes(count.try_into().unwrap()) as usize;
+
+        let float = |offset: usize| {
+            f32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as f64
+        };
+
+        if bytes.len() < 84 + 50 * count {
+            return Err(Error::Truncated);
+        }
+
+        let triangles = (0..count)
+            .map(|index| {
+                // each record is a normal, three vertices and two bytes of attributes
+                let start = 84 + 50 * index + 12;
+
+                Triangle {
+                    vertices: std::array::from_fn(|vertex| {
+                        std::array::from_fn(|axis| float(start + 12 * vertex + 4 * axis))
+                    }),
+                    group: 0,
+                }
+            })
+            .collect();
+
+        Ok(Mesh {
+            triangles,
+            groups: vec![name.to_string()],
+        })
+    }
+
+    fn parse_ascii(text: &str) -> Result<Self, Error> {
+        let mut mesh = Mesh::default();
+        let mut vertices = Vec::with_capacity(3);
+
+        for (number, line) in text.lines().enumerate() {
+            let mut tokens = line.split_whitespace();
+            let syntax = || Error::Syntax(number + 1);
+
+            match tokens.next() {
+                Some("solid") => {
+                    let name = tokens.collect::<Vec<_>>().join(" ");
+                    mesh.groups.push(name);
+                }
+                Some("vertex") => {
+                    let mut vertex = [0.; 3];
+                    for value in vertex.iter_mut() {
+                        let token = tokens.next().ok_or_else(syntax)?;
+                        *value = token.parse().map_err(|_| syntax())?;
+                    }
+                    vertices.push(vertex);
+                }
+                Some("endloop") => {
+                    let vertices: [[f64; 3]; 3] = std::mem::take(&mut vertices)
+                        .try_into()
+                        .map_err(|_| syntax())?;
+                    let group = mesh.groups.len().checked_sub(1).ok_or_else(syntax)?;
+
+                    mesh.triangles.push(Triangle { vertices, group });
+                }
+                Some("facet" | "outer" | "endfacet" | "endsolid") | None => (),
+                Some(_) => return Err(syntax()),
+            }
+        }
+
+        Ok(mesh)
+    }
+
+    /// Scales every vertex by `scale` and then moves it by `offset`
+    pub fn transform(&mut self, scale: f64, offset: [f64; 3]) {
+        for triangle in &mut self.triangles {
+            for vertex in &mut triangle.vertices {
+                for axis in 0..3 {
+                    vertex[axis] = vertex[axis] * scale + offset[axis];
+                }
+            }
+        }
+    }
+
+    /// Smallest and largest coordinates of the vertices
+    pub fn bounds(&self) -> [[f64; 3]; 2] {
+        self.triangles
+            .iter()
+            .flat_map(|triangle| triangle.vertices)
+            .fold(
+                [[f64::INFINITY; 3], [f64::NEG_INFINITY; 3]],
+                |[low, high], vertex| {
+                    [
+                        std::array::from_fn(|axis| low[axis].min(vertex[axis])),
+                        std::array::from_fn(|axis| high[axis].max(vertex[axis])),
+                    ]
+                },
+            )
+    }
+
+    /// Which of the `cells^3` cells of side `del` have their centre inside the surface
+    ///
+    /// a ray is cast along z through the centre of every column of cells, and a cell is
+    /// inside when an odd number of triangles are crossed below its centre. Rays through an
+    /// edge or vertex shared by several triangles cross exactly one of them
+    pub fn voxelize(&self, cells: usize, del: f64) -> ndarray::Array3<bool> {
+        let mut crossings = vec![Vec::new(); cells * cells];
+        let centre = |index: usize| (index as f64 + 0.5) * del;
+        // the columns a coordinate range covers
+        let columns = |low: f64, high: f64| {
+            let first = (low / del - 0.5).ceil().max(0.) as usize;
+            let last = ((high / del - 0.5).floor() + 1.).clamp(0., cells as f64) as usize;
+            first..last
+        };
+
+        for triangle in &self.triangles {
+            let [a, b, c] = triangle.vertices;
+
+            // counter clockwise seen from above, vertical triangles are never crossed
+            let area = cross(a, b, [c[0], c[1]]);
+            let (b, c) = if area < 0. { (c, b) } else { (b, c) };
+            let area = area.abs();
+
+            if area == 0. {
+                continue;
+            }
+
+            let xs = columns(a[0].min(b[0]).min(c[0]), a[0].max(b[0]).max(c[0]));
+            let ys = columns(a[1].min(b[1]).min(c[1]), a[1].max(b[1]).max(c[1]));
+
+            for i in xs {
+                for j in ys.clone() {
+                    let p = [centre(i), centre(j)];
+                    let weights = [cross(b, c, p), cross(c, a, p), cross(a, b, p)];
+                    let edges = [(b, c), (c, a), (a, b)];
+
+                    let inside = weights.iter().zip(edges).all(|(&weight, (from, to))| {
+                        weight > 0. || (weight == 0. && owns(from, to))
+                    });
+
+                    if inside {
+                        let z = (weights[0] * a[2] + weights[1] * b[2] + weights[2] * c[2]) / area;
+                        crossings[i * cells + j].push(z);
+                    }
+                }
+            }
+        }
+
+        for column in &mut crossings {
+            column.sort_by(f64::total_cmp);
+        }
+
+        ndarray::Array3::from_shape_fn((cells, cells, cells), |(i, j, k)| {
+            let z = centre(k);
+            let below = crossings[i * cells + j].partition_point(|&crossing| crossing < z);
+            below % 2 == 1
+        })
+    }
+
+    /// Group of the closest triangle to the centre of each of the `cells^3` cells of side
+    /// `del` that `wanted` is true for, `None` for the rest
+    ///
+    /// each triangle only looks at the cells within one cell of its bounding box, which holds
+    /// the cells next to the surface. Any wanted cell further from every triangle falls back
+    /// to a search of the whole mesh
+    pub fn nearest_groups(
+        &self,
+        cells: usize,
+        del: f64,
+        wanted: impl Fn([usize; 3]) -> bool,
+    ) -> ndarray::Array3<Option<usize>> {
+        let centre = |index: [usize; 3]| index.map(|i| (i as f64 + 0.5) * del);
+        let mut nearest = ndarray::Array3::from_elem((cells, cells, cells), (f64::INFINITY, None));
+
+        for triangle in &self.triangles {
+            let range = |axis: usize| {
+                let coordinates = triangle.vertices.map(|vertex| vertex[axis] / del);
+                let low = coordinates.into_iter().fold(f64::INFINITY, f64::min);
+                let high = coordinates.into_iter().fold(f64::NEG_INFINITY, f64::max);
+
+                let first = (low.floor() - 1.).max(0.) as usize;
+                let last = (high.ceil() + 1.).clamp(0., cells as f64) as usize;
+                first..last
+            };
+
+            for i in range(0) {
+                for j in range(1) {
+                    for k in range(2) {
+                        if !wanted([i, j, k]) {
+                            continue;
+                        }
+
+                        let distance = distance_squared(triangle, centre([i, j, k]));
+                        let best = &mut nearest[[i, j, k]];
+
+                        if distance < best.0 {
+                            *best = (distance, Some(triangle.group));
+                        }
+                    }
+                }
+            }
+        }
+
+        ndarray::Array3::from_shape_fn((cells, cells, cells), |(i, j, k)| {
+            let index = [i, j, k];
+
+            match nearest[index].1 {
+                Some(group) => Some(group),
+                None if wanted(index) => self.nearest_group(centre(index)),
+                None => None,
+            }
+        })
+    }
+
+    /// Group of the triangle closest to `point`, `None` for an empty mesh
+    pub fn nearest_group(&self, point: [f64; 3]) -> Option<usize> {
+        self.triangles
+            .iter()
+            .map(|triangle| (distance_squared(triangle, point), triangle.group))
+            .min_by(|left, right| left.0.total_cmp(&right.0))
+            .map(|(_, group)| group)
+    }
+}
+
+/// Twice the signed area of `(a, b, p)` projected onto the xy plane
+fn cross(a: [f64; 3], b: [f64; 3], p: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+}
+
+/// Whether points exactly on the edge `from -> to` of a counter clockwise triangle belong to
+/// it. The neighbouring triangle runs along the edge the other way and does not own it
+fn owns(from: [f64; 3], to: [f64; 3]) -> bool {
+    let (dx, dy) = (to[0] - from[0], to[1] - from[1]);
+    dy > 0. || (dy == 0. && dx < 0.)
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Squared distance from `p` to the closest point of the triangle, following the regions
+/// of Ericson's Real-Time Collision Detection 5.1.5
+fn distance_squared(triangle: &Triangle, p: [f64; 3]) -> f64 {
+    let [a, b, c] = triangle.vertices;
+    let (ab, ac, ap) = (sub(b, a), sub(c, a), sub(p, a));
+
+    let at = |point: [f64; 3]| {
+        let offset = sub(p, point);
+        dot(offset, offset)
+    };
+    let along = |from: [f64; 3], edge: [f64; 3], t: f64| {
+        at([0, 1, 2].map(|axis| from[axis] + edge[axis] * t))
+    };
+
+    let (d1, d2) = (dot(ab, ap), dot(ac, ap));
+    if d1 <= 0. && d2 <= 0. {
+        return at(a);
+    }
+
+    let bp = sub(p, b);
+    let (d3, d4) = (dot(ab, bp), dot(ac, bp));
+    if d3 >= 0. && d4 <= d3 {
+        return at(b);
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0. && d1 >= 0. && d3 <= 0. {
+        return along(a, ab, d1 / (d1 - d3));
+    }
+
+    let cp = sub(p, c);
+    let (d5, d6) = (dot(ab, cp), dot(ac, cp));
+    if d6 >= 0. && d5 <= d6 {
+        return at(c);
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0. && d2 >= 0. && d6 <= 0. {
+        return along(a, ac, d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0. && (d4 - d3) >= 0. && (d5 - d6) >= 0. {
+        return along(b, sub(c, b), (d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    // inside the face
+    let denominator = 1. / (va + vb + vc);
+    let (v, w) = (vb * denominator, vc * denominator);
+    at([0, 1, 2].map(|axis| a[axis] + ab[axis] * v + ac[axis] * w))
+}
@@ -0,0 +1,113 @@
+//! Half, quarter and eighth models of parts that are symmetric about planes through the faces
+//! of the cube
+//!
+//! no heat crosses the plane through a symmetric part, so a `Symmetry` face is insulated. The
+//! temperatures of the model are mirrored back across those faces to give the whole part, once
+//! along each axis with a symmetry face and across its low face where both are. `Periodic`
+//! faces are instead solved by the stencils, see `Coefficients::periodic`
+
+use crate::conditions::{BoundaryCondition, Condition};
+use crate::input::Conditions;
+use crate::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+/// Temperatures of the whole part, mirrored across the symmetry faces of the cube
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(bound(deserialize = "T: Float"))]
+pub struct Mirrored<T> {
+    /// nodes along each axis
+    pub shape: [usize; 3],
+    /// index of the solved node `[0, 0, 0]`
+    pub origin: [usize; 3],
+    /// flattened in the same order as `StepData::data`
+    #[serde(deserialize_with = "crate::dump::nullable")]
+    pub temperatures: Vec<T>,
+}
+
+impl<T: Float> Mirrored<T> {
+    /// `temps` mirrored across the faces of `conditions` that are symmetry planes, `None`
+    /// when there are none
+    pub fn new(temps: &Matrix<T>, conditions: &Conditions<T>) -> Option<Self> {
+        let faces = faces(conditions);
+
+        if faces.iter().all(Option::is_none) {
+            return None;
+        }
+
+        let mirrored = mirror(temps, faces);
+        let shape = mirrored.shape();
+
+        Some(Mirrored {
+            shape: [shape[0], shape[1], shape[2]],
+            origin: std::array::from_fn(|axis| match faces[axis] {
+                Some(false) => temps.shape()[axis] - 1,
+                _ => 0,
+            }),
+            temperatures: mirrored.into_iter().collect(),
+        })
+    }
+
+    pub fn temperatures(&self) -> Matrix<T> {
+        Matrix::from_shape_vec(self.shape, self.temperatures.clone())
+            .expect("the mirrored temperatures hold every node")
+    }
+}
+
+/// Face of each axis that `conditions` make a symmetry plane, `Some(true)` for the high face
+/// and the low one where both are
+pub fn faces<T>(conditions: &Conditions<T>) -> [Option<bool>; 3] {
+    let symmetry = |condition: &Condition<T>| matches!(condition, Condition::Symmetry(_));
+
+    [
+        (&conditions.left_boundary, &conditions.right_boundary),
+        (&conditions.bot_boundary, &conditions.top_boundary),
+        (&conditions.back_boundary, &conditions.front_boundary),
+    ]
+    .map(|(low, high)| match (symmetry(low), symmetry(high)) {
+        (true, _) => Some(false),
+        (false, true) => Some(true),
+        (false, false) => None,
+    })
+}
+
+/// Axes whose faces are both periodic, `None` when a periodic face is not opposite another
+pub fn periodic<T: Float>(conditions: &Conditions<T>) -> Option<[bool; 3]> {
+    let axes = [
+        (&conditions.left_boundary, &conditions.right_boundary),
+        (&conditions.bot_boundary, &conditions.top_boundary),
+        (&conditions.back_boundary, &conditions.front_boundary),
+    ];
+
+    let mut periodic = [false; 3];
+    for (axis, (low, high)) in axes.into_iter().enumerate() {
+        if low.is_periodic() != high.is_periodic() {
+            return None;
+        }
+        periodic[axis] = low.is_periodic();
+    }
+
+    Some(periodic)
+}
+
+/// Mirrors `temps` across the face of each axis in `faces`, `Some(true)` for its high face,
+/// with the nodes on the face appearing once
+pub fn mirror<T: Float>(temps: &Matrix<T>, faces: [Option<bool>; 3]) -> Matrix<T> {
+    let shape = temps.shape();
+    let end: [usize; 3] = std::array::from_fn(|axis| shape[axis] - 1);
+    let mirrored: [usize; 3] = std::array::from_fn(|axis| match faces[axis] {
+        Some(_) => 2 * end[axis] + 1,
+        None => shape[axis],
+    });
+
+    Matrix::from_shape_fn(mirrored, |(x, y, z)| {
+        let index = [x, y, z];
+        let solved: [usize; 3] = std::array::from_fn(|axis| match faces[axis] {
+            Some(false) => index[axis].abs_diff(end[axis]),
+            Some(true) if index[axis] > end[axis] => 2 * end[axis] - index[axis],
+            _ => index[axis],
+        });
+
+        temps[solved]
+    })
+}
@@ -1,25 +1,27 @@
 use crate::prelude::*;
 
-pub struct TopSurface<V: BoundaryCondition> {
+pub struct TopSurface<V> {
     pub top_boundary: V,
 }
 
-impl<V> CalculateTemperature for TopSurface<V>
+impl<T, V> CalculateTemperature<T> for TopSurface<V>
 where
-    V: BoundaryCondition,
+    T: Float,
+    V: BoundaryCondition<T>,
 {
-    fn calculate_temperature(&self, info: Information, s: &SolverInfo) -> T {
+    fn calculate_temperature(&self, info: Information<T>, s: &SolverInfo<T>) -> T {
         constant_temperature!(self.top_boundary);
 
+        let [ax, ay, az] = s.anisotropy;
         let area = s.del2();
 
-        let m = (info.i_front + info.i_back + info.k_front + info.k_back) / 2.
-            + info.j_back
-            + self.top_boundary.lhs_constant(&info, &s, area);
+        let m = (ax * (info.i_front + info.i_back) + az * (info.k_front + info.k_back)) / lit(2.)
+            + ay * info.j_back
+            + self.top_boundary.lhs_constant(&info, s, area);
 
-        let numerator = m + (s.q_dot * s.del2() / (2.0 * s.k));
+        let numerator = m + (s.q_dot * s.del2() / (lit::<T>(2.0) * s.k));
 
-        let div = 3. + self.top_boundary.rhs_constant(&info, s, area);
+        let div = ax + ay + az + self.top_boundary.rhs_constant(&info, s, area);
 
         numerator / div
     }
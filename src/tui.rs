@@ -0,0 +1,464 @@
+//! Live terminal view of a solve, built with the `tui` feature
+//!
+//! the solver runs on a worker thread and sends the view a snapshot a few times a second: the
+//! errors recorded since the previous one, the step it has reached and the selected slice of
+//! the temperatures. The view charts the error history, estimates when the error will reach
+//! `error_epsilon` from the rate it has been falling at, and draws the slice as a heatmap
+
+use crate::dump::{ErrorType, SimulationResult};
+use crate::observer::{self, Cancel, Control, Observer, Progress};
+use crate::prelude::*;
+use crate::probe::Axis;
+use crate::render::Colormap;
+use crate::report::decay_rate;
+use crate::SolverParams;
+
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::symbols::Marker;
+use ratatui::text::Line;
+use ratatui::widgets::{self, Block, Chart, Dataset, GraphType, Paragraph, Widget};
+use ratatui::Frame;
+
+use std::io;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::ScopedJoinHandle;
+use std::time::{Duration, Instant};
+
+/// time between snapshots from the solver
+const REFRESH: Duration = Duration::from_millis(200);
+/// time the view waits for a key press before drawing again
+const POLL: Duration = Duration::from_millis(50);
+
+/// The slice on show, chosen in the view and read by the solver when it takes a snapshot
+///
+/// the methods take the `shape` of the temperatures, the nodes along each axis, which are
+/// `divisions` along every axis only for a 3D cube
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Selection {
+    pub axis: Axis,
+    pub index: usize,
+}
+
+impl Selection {
+    /// The middle slice normal to `axis`
+    pub fn middle(axis: Axis, shape: [usize; 3]) -> Self {
+        Selection {
+            axis,
+            index: shape[axis.index()] / 2,
+        }
+    }
+
+    /// Moves to the middle slice normal to `axis`, staying put when the grid has a single node
+    /// along it and so no slice across it to show
+    pub fn select(&mut self, axis: Axis, shape: [usize; 3]) {
+        if shape[axis.index()] > 1 {
+            *self = Selection::middle(axis, shape);
+        }
+    }
+
+    /// Moves `nodes` along the axis, stopping at either end of the grid
+    pub fn step(&mut self, nodes: isize, shape: [usize; 3]) {
+        let index = self.within(shape).index.saturating_add_signed(nodes);
+        self.index = index.min(shape[self.axis.index()].saturating_sub(1));
+    }
+
+    /// The same slice, or the last along its axis when the grid ends before it
+    pub fn within(self, shape: [usize; 3]) -> Self {
+        Selection {
+            index: self.index.min(shape[self.axis.index()].saturating_sub(1)),
+            ..self
+        }
+    }
+}
+
+struct Snapshot {
+    taken: Instant,
+    step: usize,
+    error: f64,
+    /// `(step, error)` recorded every `error_steps` since the previous snapshot
+    errors: Vec<(f64, f64)>,
+    selection: Selection,
+    /// nodes along each axis of the temperatures
+    shape: [usize; 3],
+    slice: ndarray::Array2<f64>,
+}
+
+/// Solves on a worker thread while drawing its progress in the terminal
+///
+/// `solve` runs the solver with the observer it is handed. Stopping with `s` ends the solve
+/// early with the temperatures it has reached, closing the view with `q` leaves it to finish
+/// without the view
+pub fn run<T, S>(params: SolverParams<T>, error_type: ErrorType, solve: S) -> SimulationResult<T>
+where
+    T: Float,
+    S: FnOnce(&mut dyn Observer<T>) -> SimulationResult<T> + Send,
+{
+    // the temperatures of a cylinder or of fewer dimensions have a different shape, which the
+    // worker corrects the selection against
+    let divisions = params.divisions;
+    let selection = Arc::new(Mutex::new(Selection::middle(Axis::X, [divisions; 3])));
+    let (sender, receiver) = mpsc::channel();
+    let cancel = Cancel::new();
+
+    let watch = {
+        let selection = Arc::clone(&selection);
+        let mut errors = Vec::new();
+        let mut last: Option<Instant> = None;
+
+        observer::from_fn(move |progress: Progress<T>| {
+            let step = progress.step;
+            let error = progress.error.to_f64().unwrap();
+
+            if step.is_multiple_of(params.error_steps) {
+                errors.push((step as f64, error));
+            }
+
+            if last.is_some_and(|last| last.elapsed() < REFRESH) {
+                return Control::Continue;
+            }
+            last = Some(Instant::now());
+
+            let shape = progress.temperatures.dim().into();
+            let selection = selection.lock().unwrap().within(shape);
+            let slice = progress
+                .temperatures
+                .index_axis(ndarray::Axis(selection.axis.index()), selection.index)
+                .mapv(|temp| temp.to_f64().unwrap());
+
+            // the view may already have been closed
+            let _ = sender.send(Snapshot {
+                taken: Instant::now(),
+                step,
+                error,
+                errors: std::mem::take(&mut errors),
+                selection,
+                shape,
+                slice,
+            });
+
+            Control::Continue
+        })
+    };
+    let mut observer = (watch, cancel.clone());
+
+    let view = View {
+        error_type,
+        epsilon: params.error_epsilon.to_f64().unwrap(),
+        del: params.del().to_f64().unwrap(),
+        errors: Vec::new(),
+        latest: None,
+        speed: None,
+    };
+
+    std::thread::scope(|scope| {
+        let worker = scope.spawn(move || solve(&mut observer));
+
+        if let Err(error) = view.show(&receiver, &selection, &cancel, &worker) {
+            tracing::error!(%error, "the terminal view failed");
+        }
+
+        worker
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+struct View {
+    error_type: ErrorType,
+    epsilon: f64,
+    del: f64,
+    /// every `(step, error)` recorded so far
+    errors: Vec<(f64, f64)>,
+    latest: Option<Snapshot>,
+    /// steps per second, smoothed over the last few snapshots
+    speed: Option<f64>,
+}
+
+impl View {
+    fn show<R>(
+        mut self,
+        receiver: &mpsc::Receiver<Snapshot>,
+        selection: &Mutex<Selection>,
+        cancel: &Cancel,
+        worker: &ScopedJoinHandle<R>,
+    ) -> io::Result<()> {
+        let mut terminal = ratatui::try_init()?;
+
+        let mut event_loop = || -> io::Result<()> {
+            while !worker.is_finished() {
+                while let Ok(snapshot) = receiver.try_recv() {
+                    self.update(snapshot);
+                }
+
+                terminal.draw(|frame| self.draw(frame))?;
+
+                if !event::poll(POLL)? {
+                    continue;
+                }
+
+                let Event::Key(key) = event::read()? else {
+                    continue;
+                };
+
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+
+                let mut selection = selection.lock().unwrap();
+                // the slice can only be moved once the shape of the grid is known
+                let shape = self.latest.as_ref().map(|snapshot| snapshot.shape);
+
+                match (key.code, shape) {
+                    (KeyCode::Char('q') | KeyCode::Esc, _) => break,
+                    (KeyCode::Char('s'), _) => cancel.cancel(),
+                    (KeyCode::Char(axis @ ('x' | 'y' | 'z')), Some(shape)) => {
+                        selection.select(axis.to_string().parse().unwrap(), shape)
+                    }
+                    (KeyCode::Up | KeyCode::Char('+'), Some(shape)) => selection.step(1, shape),
+                    (KeyCode::Down | KeyCode::Char('-'), Some(shape)) => selection.step(-1, shape),
+                    _ => (),
+                }
+            }
+
+            Ok(())
+        };
+
+        let outcome = event_loop();
+        ratatui::restore();
+
+        outcome
+    }
+
+    fn update(&mut self, snapshot: Snapshot) {
+        if let Some(previous) = &self.latest {
+            let steps = snapshot.step.saturating_sub(previous.step) as f64;
+            let elapsed = snapshot.taken.duration_since(previous.taken);
+            let speed = steps / elapsed.as_secs_f64().max(1e-9);
+
+            self.speed = Some(match self.speed {
+                Some(smoothed) => 0.7 * smoothed + 0.3 * speed,
+                None => speed,
+            });
+        }
+
+        self.errors.extend_from_slice(&snapshot.errors);
+        self.latest = Some(snapshot);
+    }
+
+    /// Time until the error reaches `epsilon` if it keeps falling at the same rate
+    fn eta(&self, error: f64) -> Option<Duration> {
+        let rate = decay_rate(&self.errors)?;
+        let speed = self.speed?;
+
+        if error < self.epsilon {
+            return Some(Duration::ZERO);
+        }
+
+        if rate >= 1. || speed <= 0. {
+            return None;
+        }
+
+        let steps = (self.epsilon / error).ln() / rate.ln();
+        Duration::try_from_secs_f64(steps / speed).ok()
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [left, right] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(frame.area());
+        let [chart, progress] =
+            Layout::vertical([Constraint::Min(8), Constraint::Length(6)]).areas(left);
+
+        let points = self.error_points();
+        let last_step = points.last().map_or(1., |&(step, _)| step.max(1.));
+        let target = self.epsilon.log10();
+        let epsilon = [(0., target), (last_step, target)];
+
+        frame.render_widget(self.chart(&points, &epsilon), chart);
+        frame.render_widget(self.progress(), progress);
+
+        let Some(snapshot) = &self.latest else {
+            frame.render_widget(Block::bordered().title("slice"), right);
+            return;
+        };
+
+        let name = ["x", "y", "z"][snapshot.selection.axis.index()];
+        let title = format!(
+            "{name} = {:.4} m (node {})",
+            self.del * snapshot.selection.index as f64,
+            snapshot.selection.index
+        );
+
+        let block = Block::bordered().title(title);
+        let inner = block.inner(right);
+        frame.render_widget(block, right);
+        frame.render_widget(
+            Heatmap {
+                values: &snapshot.slice,
+                colormap: Colormap::Coolwarm,
+            },
+            inner,
+        );
+    }
+
+    /// `(step, log10(error))` of the recorded errors, thinned to about as many as the chart
+    /// has room for
+    fn error_points(&self) -> Vec<(f64, f64)> {
+        let stride = (self.errors.len() / 400).max(1);
+
+        self.errors
+            .iter()
+            .step_by(stride)
+            .chain(self.errors.last())
+            .filter(|&&(_, error)| error > 0.)
+            .map(|&(step, error)| (step, error.log10()))
+            .collect()
+    }
+
+    /// `epsilon` is the target as a horizontal line across the whole chart
+    fn chart<'a>(&self, points: &'a [(f64, f64)], epsilon: &'a [(f64, f64); 2]) -> Chart<'a> {
+        let title = format!("{:?} error", self.error_type);
+        let (last_step, target) = (epsilon[1].0, epsilon[1].1);
+
+        let (low, high) = points
+            .iter()
+            .fold((target, target), |(low, high), &(_, log)| {
+                (low.min(log), high.max(log))
+            });
+        let (low, high) = (low.floor(), high.ceil().max(low.floor() + 1.));
+
+        Chart::new(vec![
+            Dataset::default()
+                .name("error")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Cyan))
+                .data(points),
+            Dataset::default()
+                .name("epsilon")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Red))
+                .data(epsilon),
+        ])
+        .block(Block::bordered().title(title))
+        .x_axis(
+            widgets::Axis::default()
+                .title("step")
+                .bounds([0., last_step])
+                .labels(["0".to_string(), format!("{last_step:.0}")]),
+        )
+        .y_axis(
+            widgets::Axis::default()
+                .bounds([low, high])
+                .labels([format!("1e{low:.0}"), format!("1e{high:.0}")]),
+        )
+    }
+
+    fn progress(&self) -> Paragraph<'_> {
+        let mut lines = Vec::new();
+
+        match &self.latest {
+            Some(snapshot) => {
+                lines.push(Line::from(format!(
+                    "step {}   error {:.3e}   target {:.1e}",
+                    snapshot.step, snapshot.error, self.epsilon
+                )));
+
+                let speed = self
+                    .speed
+                    .map_or("-".to_string(), |speed| format!("{speed:.1}"));
+                let eta = self
+                    .eta(snapshot.error)
+                    .map_or("-".to_string(), format_duration);
+                lines.push(Line::from(format!("{speed} steps/s   ETA {eta}")));
+
+                let (low, high) = snapshot
+                    .slice
+                    .iter()
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), &t| {
+                        (low.min(t), high.max(t))
+                    });
+                lines.push(Line::from(format!("slice {low:.3} K to {high:.3} K")));
+            }
+            None => lines.push(Line::from("waiting for the first step")),
+        }
+
+        lines.push(Line::from(
+            "x y z: slice axis   up down: move slice   s: stop   q: close",
+        ));
+
+        Paragraph::new(lines).block(Block::bordered().title("progress"))
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+
+    match seconds {
+        0..=59 => format!("{seconds}s"),
+        60..=3599 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {:02}m", seconds / 3600, seconds / 60 % 60),
+    }
+}
+
+/// Slice drawn with half block characters, two nodes to a character cell so nodes are about
+/// square, the first in plane axis to the right and the second upwards
+struct Heatmap<'a> {
+    values: &'a ndarray::Array2<f64>,
+    colormap: Colormap,
+}
+
+impl Widget for Heatmap<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (nu, nv) = self.values.dim();
+
+        if nu == 0 || nv == 0 || area.is_empty() {
+            return;
+        }
+
+        let (low, high) = self
+            .values
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), &t| {
+                (low.min(t), high.max(t))
+            });
+        let span = if high > low { high - low } else { 1. };
+
+        // character cells per node across, half cells per node down
+        let scale = (area.width as f64 / nu as f64).min(2. * area.height as f64 / nv as f64);
+
+        let color = |column: u16, half_row: u16| {
+            let i = ((column as f64 + 0.5) / scale) as usize;
+            let from_top = ((half_row as f64 + 0.5) / scale) as usize;
+
+            (i < nu && from_top < nv).then(|| {
+                let value = self.values[[i, nv - 1 - from_top]];
+
+                // nodes outside of a masked geometry are left blank
+                if value.is_nan() {
+                    return Color::Reset;
+                }
+
+                let [r, g, b] = self.colormap.color((value - low) / span);
+                Color::Rgb(r, g, b)
+            })
+        };
+
+        for row in 0..area.height {
+            for column in 0..area.width {
+                let Some(upper) = color(column, 2 * row) else {
+                    continue;
+                };
+                let lower = color(column, 2 * row + 1).unwrap_or(Color::Reset);
+
+                if let Some(cell) = buf.cell_mut((area.x + column, area.y + row)) {
+                    cell.set_char('▀').set_fg(upper).set_bg(lower);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Compares `solver::solver` against analytic solutions of the steady heat equation
+//!
+//! the slab cases are polynomial in y and are reproduced exactly by the stencils, the
+//! series solutions are checked for second order convergence as the grid is refined
+//!
+//! nodes are placed at `i * del` for `i` in `0..divisions`, so a cube of side `LEN` uses
+//! `del = LEN / (divisions - 1)`
+
+use finite_difference::conditions::{Condition, Convection, HeatFlux, Temperature};
+use finite_difference::error::InfinityNorm;
+use finite_difference::input::Conditions;
+use finite_difference::prelude::{Matrix, SolverInfo};
+use finite_difference::setup::SetupConditions;
+use finite_difference::solver;
+use finite_difference::SolverParams;
+
+use std::f64::consts::PI;
+
+const LEN: f64 = 0.1;
+
+/// grids whose spacing halves each time, every node of the coarsest is shared by all of them
+const GRIDS: [usize; 3] = [9, 17, 33];
+
+fn temperature(temperature: f64) -> Condition<f64> {
+    Condition::Temperature(Temperature { temperature })
+}
+
+fn convection(h: f64, t_inf: f64) -> Condition<f64> {
+    Condition::Convection(Convection { h, t_inf })
+}
+
+fn adiabatic() -> Condition<f64> {
+    Condition::HeatFlux(HeatFlux { heat_flux: 0. })
+}
+
+/// Runs the solver to a tight tolerance and returns the final temperature field
+fn solve(conditions: Conditions<f64>, k: f64, q_dot: f64, divisions: usize) -> Matrix<f64> {
+    let del = LEN / (divisions - 1) as f64;
+
+    let s = SolverInfo::builder().k(k).q_dot(q_dot).del(del).build();
+
+    let params = SolverParams {
+        len: LEN,
+        divisions,
+        error_epsilon: 1e-11,
+        data_steps: usize::MAX,
+        error_steps: usize::MAX,
+        sweep: Default::default(),
+        threads: None,
+    };
+
+    solver::solver(
+        s,
+        params,
+        conditions.make_boundaries(),
+        InfinityNorm,
+        &[],
+        (),
+    )
+    .unwrap()
+    .temperatures()
+}
+
+/// Largest difference between the field and `exact` at any node
+fn max_error(field: &Matrix<f64>, exact: impl Fn(f64, f64, f64) -> f64) -> f64 {
+    let del = LEN / (field.shape()[0] - 1) as f64;
+
+    field
+        .indexed_iter()
+        .map(|((x, y, z), temp)| {
+            (temp - exact(x as f64 * del, y as f64 * del, z as f64 * del)).abs()
+        })
+        .fold(0., f64::max)
+}
+
+/// Largest difference between the field and `exact` at fixed fractions of `LEN`
+fn probe_error(
+    field: &Matrix<f64>,
+    probes: &[[f64; 3]],
+    exact: impl Fn(f64, f64, f64) -> f64,
+) -> f64 {
+    let end = (field.shape()[0] - 1) as f64;
+
+    probes
+        .iter()
+        .map(|probe| {
+            let [x, y, z] = probe.map(|fraction| (fraction * end).round() as usize);
+            let temp = field[[x, y, z]];
+
+            (temp - exact(probe[0] * LEN, probe[1] * LEN, probe[2] * LEN)).abs()
+        })
+        .fold(0., f64::max)
+}
+
+/// Asserts that the error drops by at least a factor of ~4 each time the spacing halves
+fn assert_second_order(errors: &[f64]) {
+    for pair in errors.windows(2) {
+        let order = (pair[0] / pair[1]).log2();
+
+        assert!(
+            order > 1.8,
+            "observed order {order:.3} from errors {errors:?}, expected 2"
+        );
+    }
+}
+
+/// roots of `beta * tan(beta) = biot`, one per branch of tan
+fn eigenvalues(biot: f64, count: usize) -> Vec<f64> {
+    (0..count)
+        .map(|branch| {
+            let mut low = branch as f64 * PI;
+            let mut high = low + PI / 2. - 1e-12;
+
+            for _ in 0..200 {
+                let middle = (low + high) / 2.;
+
+                if middle * middle.tan() < biot {
+                    low = middle;
+                } else {
+                    high = middle;
+                }
+            }
+
+            (low + high) / 2.
+        })
+        .collect()
+}
+
+/// coefficient of `cos(beta x / a)` in the expansion of 1 over `-a < x < a`
+fn series_coefficient(beta: f64) -> f64 {
+    2. * beta.sin() / (beta + beta.sin() * beta.cos())
+}
+
+#[test]
+fn slab_fixed_temperatures() {
+    let (t_bot, t_top) = (300., 400.);
+
+    let conditions = SetupConditions {
+        right_boundary: adiabatic(),
+        left_boundary: adiabatic(),
+        top_boundary: temperature(t_top),
+        bot_boundary: temperature(t_bot),
+        front_boundary: adiabatic(),
+        back_boundary: adiabatic(),
+    };
+
+    let field = solve(conditions, 20., 0., 17);
+    let error = max_error(&field, |_, y, _| t_bot + (t_top - t_bot) * y / LEN);
+
+    assert!(error < 1e-6, "max error {error}");
+}
+
+#[test]
+fn slab_uniform_generation() {
+    // every face but the bottom is insulated, so the corners and edges at the top are
+    // calculated from their energy balance including the generated heat
+    let (t_wall, k, q_dot) = (300., 20., 1e5);
+
+    let conditions = SetupConditions {
+        right_boundary: adiabatic(),
+        left_boundary: adiabatic(),
+        top_boundary: adiabatic(),
+        bot_boundary: temperature(t_wall),
+        front_boundary: adiabatic(),
+        back_boundary: adiabatic(),
+    };
+
+    let field = solve(conditions, k, q_dot, 17);
+    let error = max_error(&field, |_, y, _| {
+        t_wall + q_dot / k * (LEN * y - y * y / 2.)
+    });
+
+    assert!(error < 1e-6, "max error {error}");
+}
+
+#[test]
+fn convective_fin() {
+    // a square pin fin: the base is held at `t_base`, the four sides convect to `t_inf` and
+    // the tip is insulated
+    let (t_base, t_inf, h, k) = (400., 300., 200., 20.);
+
+    let half_width = LEN / 2.;
+    let betas = eigenvalues(h * half_width / k, 40);
+
+    let exact = |x: f64, y: f64, z: f64| {
+        let mut theta = 0.;
+
+        for &beta_m in &betas {
+            for &beta_n in &betas {
+                let (lambda_m, lambda_n) = (beta_m / half_width, beta_n / half_width);
+                let gamma = (lambda_m * lambda_m + lambda_n * lambda_n).sqrt();
+
+                theta += series_coefficient(beta_m)
+                    * series_coefficient(beta_n)
+                    * (lambda_m * (x - half_width)).cos()
+                    * (lambda_n * (z - half_width)).cos()
+                    * (gamma * (LEN - y)).cosh()
+                    / (gamma * LEN).cosh();
+            }
+        }
+
+        t_inf + (t_base - t_inf) * theta
+    };
+
+    let conditions = SetupConditions {
+        right_boundary: convection(h, t_inf),
+        left_boundary: convection(h, t_inf),
+        top_boundary: adiabatic(),
+        bot_boundary: temperature(t_base),
+        front_boundary: convection(h, t_inf),
+        back_boundary: convection(h, t_inf),
+    };
+
+    let probes = [
+        [0.5, 0.25, 0.5],
+        [0.5, 0.5, 0.5],
+        [0.25, 0.75, 0.5],
+        [0.5, 1., 0.5],
+        [0., 0.5, 0.],
+    ];
+
+    let errors: Vec<f64> = GRIDS
+        .iter()
+        .map(|&divisions| probe_error(&solve(conditions, k, 0., divisions), &probes, exact))
+        .collect();
+
+    assert_second_order(&errors);
+}
+
+#[test]
+fn separable_laplace() {
+    // top and bottom held at fixed temperatures, the left and back faces convect and the
+    // right and front faces are insulated. Each term of the series is a separable solution
+    // `X(x) Z(z) Y(y)` of Laplace's equation
+    let (t_bot, t_top, t_inf, h, k) = (300., 350., 280., 100., 20.);
+
+    // `cos(lambda (LEN - x))` is insulated at x = LEN and convective at x = 0 when
+    // lambda LEN tan(lambda LEN) = h LEN / k
+    let betas = eigenvalues(h * LEN / k, 40);
+
+    let exact = |x: f64, y: f64, z: f64| {
+        let mut theta = 0.;
+
+        for &beta_m in &betas {
+            for &beta_n in &betas {
+                let (lambda_m, lambda_n) = (beta_m / LEN, beta_n / LEN);
+                let gamma = (lambda_m * lambda_m + lambda_n * lambda_n).sqrt();
+
+                let shape = series_coefficient(beta_m)
+                    * series_coefficient(beta_n)
+                    * (lambda_m * (LEN - x)).cos()
+                    * (lambda_n * (LEN - z)).cos();
+
+                let bot = (t_bot - t_inf) * (gamma * (LEN - y)).sinh();
+                let top = (t_top - t_inf) * (gamma * y).sinh();
+
+                theta += shape * (bot + top) / (gamma * LEN).sinh();
+            }
+        }
+
+        t_inf + theta
+    };
+
+    let conditions = SetupConditions {
+        right_boundary: adiabatic(),
+        left_boundary: convection(h, t_inf),
+        top_boundary: temperature(t_top),
+        bot_boundary: temperature(t_bot),
+        front_boundary: adiabatic(),
+        back_boundary: convection(h, t_inf),
+    };
+
+    let probes = [
+        [0.5, 0.5, 0.5],
+        [0.25, 0.5, 0.25],
+        [0., 0.5, 0.],
+        [1., 0.25, 1.],
+        [0.75, 0.75, 0.],
+    ];
+
+    let errors: Vec<f64> = GRIDS
+        .iter()
+        .map(|&divisions| probe_error(&solve(conditions, k, 0., divisions), &probes, exact))
+        .collect();
+
+    assert_second_order(&errors);
+}
@@ -0,0 +1,228 @@
+//! Checks the conductivities of a turned material frame, the profile of a slab conducting
+//! along a single axis, and that the cube's stencils agree with the control volumes of the
+//! masked and reduced grids
+
+mod common;
+
+use common::{adiabatic, assert_close, convection, params, DIVISIONS, K, LEN, Q_DOT};
+use finite_difference::anisotropy::{Error, Orthotropic};
+use finite_difference::conditions::{Condition, HeatFlux, Temperature};
+use finite_difference::dimensions::{self, Dimensions};
+use finite_difference::dump::SaveFile;
+use finite_difference::error::L2Norm;
+use finite_difference::geometry::Mask;
+use finite_difference::input::Conditions;
+use finite_difference::prelude::{Precision, SolverInfo};
+use finite_difference::report::Report;
+use finite_difference::solver;
+use finite_difference::stencil::{Coefficients, Sweep};
+
+/// A laminate conducting well along x and z and poorly through its thickness along y
+const LAMINATE: [f64; 3] = [45., 1., 7.];
+
+fn info(q_dot: f64) -> SolverInfo<f64> {
+    SolverInfo::builder()
+        .k(K)
+        .q_dot(q_dot)
+        .del(params().del())
+        .anisotropy(LAMINATE.map(|k| k / K))
+        .build()
+}
+
+#[test]
+fn a_turned_frame_mixes_the_conductivities() {
+    let aligned = Orthotropic {
+        k: LAMINATE,
+        rotation: None,
+    };
+    assert_eq!(aligned.along_axes(), LAMINATE);
+    assert_eq!(aligned.cross_terms(), 0.);
+
+    // a quarter turn about z swaps x and y
+    let quarter = Orthotropic {
+        rotation: Some([0., 0., 90.]),
+        ..aligned
+    };
+    for (along, expected) in quarter.along_axes().into_iter().zip([1., 45., 7.]) {
+        assert_close(along, expected, 1e-12);
+    }
+    assert!(quarter.cross_terms() < 1e-12);
+    assert_eq!(quarter.check(), Ok(()));
+
+    // an eighth turn shares them equally, with a cross term of half their difference
+    let eighth = Orthotropic {
+        rotation: Some([0., 0., 45.]),
+        ..aligned
+    };
+    let tensor = eighth.tensor();
+    assert_close(tensor[0][0], 23., 1e-12);
+    assert_close(tensor[1][1], 23., 1e-12);
+    assert_close(tensor[0][1], 22., 1e-12);
+    assert_close(tensor[2][2], 7., 1e-12);
+    assert_close(eighth.cross_terms(), 22. / 23., 1e-12);
+
+    // which the six point stencils cannot solve
+    assert!(matches!(eighth.check(), Err(Error::CrossTerms(share)) if share > 0.9));
+
+    // written without a rotation when aligned
+    let json = serde_json::to_string(&aligned).unwrap();
+    assert_eq!(json, r#"{"k":[45.0,1.0,7.0]}"#);
+}
+
+#[test]
+fn a_slab_conducts_with_its_conductivity_along_x() {
+    // cooled on either side along x, insulated elsewhere
+    let conditions = Conditions {
+        right_boundary: convection(40.),
+        left_boundary: convection(40.),
+        top_boundary: adiabatic(),
+        bot_boundary: adiabatic(),
+        front_boundary: adiabatic(),
+        back_boundary: adiabatic(),
+    };
+
+    let coefficients = Coefficients::new(
+        &conditions.make_boundaries(),
+        &info(Q_DOT),
+        DIVISIONS,
+        Sweep::default(),
+    )
+    .unwrap();
+    let temps = solver::solve(&coefficients, params(), &L2Norm, &[], ()).temperatures();
+
+    let [kx, _, _] = LAMINATE;
+    let half = LEN / 2.;
+    let slab =
+        |x: f64| 290. + Q_DOT * half / 40. + Q_DOT * (half * half - (x - half).powi(2)) / (2. * kx);
+
+    for ((x, _, _), &temp) in temps.indexed_iter() {
+        assert_close(temp, slab(params().del() * x as f64), 1e-6);
+    }
+}
+
+#[test]
+fn a_quarter_turned_slab_conducts_with_its_conductivity_along_y() {
+    // the laminate turned about z so that its conductivity along x lies along y
+    let quarter = Orthotropic {
+        k: LAMINATE,
+        rotation: Some([0., 0., 90.]),
+    };
+    quarter.check().unwrap();
+
+    // cooled on either side along y, insulated elsewhere
+    let conditions = Conditions {
+        right_boundary: adiabatic(),
+        left_boundary: adiabatic(),
+        top_boundary: convection(40.),
+        bot_boundary: convection(40.),
+        front_boundary: adiabatic(),
+        back_boundary: adiabatic(),
+    };
+    let info = SolverInfo::builder()
+        .k(K)
+        .q_dot(Q_DOT)
+        .del(params().del())
+        .anisotropy(quarter.anisotropy(K))
+        .build();
+
+    let coefficients = Coefficients::new(
+        &conditions.make_boundaries(),
+        &info,
+        DIVISIONS,
+        Sweep::default(),
+    )
+    .unwrap();
+    let temps = solver::solve(&coefficients, params(), &L2Norm, &[], ()).temperatures();
+
+    let [kx, _, _] = LAMINATE;
+    let half = LEN / 2.;
+    let slab =
+        |y: f64| 290. + Q_DOT * half / 40. + Q_DOT * (half * half - (y - half).powi(2)) / (2. * kx);
+
+    for ((_, y, _), &temp) in temps.indexed_iter() {
+        assert_close(temp, slab(params().del() * y as f64), 1e-6);
+    }
+}
+
+#[test]
+fn every_grid_weights_the_axes_alike() {
+    let conditions = Conditions {
+        right_boundary: convection(40.),
+        left_boundary: Condition::Temperature(Temperature { temperature: 300. }),
+        top_boundary: convection(10.),
+        bot_boundary: Condition::HeatFlux(HeatFlux { heat_flux: 2000. }),
+        front_boundary: adiabatic(),
+        back_boundary: convection(25.),
+    };
+    let bcs = conditions.make_boundaries();
+    let info = info(Q_DOT);
+
+    let solve = |coefficients: Coefficients<f64>| {
+        solver::solve(&coefficients, params(), &L2Norm, &[], ()).temperatures()
+    };
+
+    let cube = solve(Coefficients::new(&bcs, &info, DIVISIONS, Sweep::default()).unwrap());
+    let masked = solve(
+        Coefficients::masked(&bcs, &info, &Mask::solid(DIVISIONS), Sweep::default()).unwrap(),
+    );
+    let nodes = solve(dimensions::coefficients(
+        Dimensions::Three,
+        &bcs,
+        &info,
+        DIVISIONS,
+        Sweep::default(),
+    ));
+
+    for ((cube, masked), nodes) in cube.iter().zip(&masked).zip(&nodes) {
+        assert_close(*cube, *masked, 1e-6);
+        assert_close(*cube, *nodes, 1e-6);
+    }
+}
+
+#[test]
+fn the_report_conducts_with_the_conductivity_along_each_face() {
+    let conditions = Conditions {
+        right_boundary: Condition::Temperature(Temperature { temperature: 400. }),
+        left_boundary: Condition::Temperature(Temperature { temperature: 300. }),
+        top_boundary: adiabatic(),
+        bot_boundary: adiabatic(),
+        front_boundary: adiabatic(),
+        back_boundary: adiabatic(),
+    };
+
+    let coefficients = Coefficients::new(
+        &conditions.make_boundaries(),
+        &info(0.),
+        DIVISIONS,
+        Sweep::default(),
+    )
+    .unwrap();
+
+    let results = SaveFile {
+        conditions,
+        simulation: solver::solve(&coefficients, params(), &L2Norm, &[], ()),
+        solver_params: params(),
+        precision: Precision::Double,
+        thermal_conductivity: Some(K),
+        energy_generation: Some(0.),
+        samples: None,
+        heat_flux: None,
+        geometry: None,
+        cylinder: None,
+        orthotropic: Some(Orthotropic {
+            k: LAMINATE,
+            rotation: None,
+        }),
+        mirrored: None,
+    };
+    let report = Report::new(&results);
+
+    // Fourier's law across the cube with the conductivity along x
+    let [kx, _, _] = LAMINATE;
+    let conducted = kx * LEN * LEN * 100. / LEN;
+
+    let right: f64 = report.faces[0].heat_flow.unwrap();
+    let left: f64 = report.faces[1].heat_flow.unwrap();
+    assert_close(right, -conducted, 1e-6 * conducted);
+    assert_close(left, conducted, 1e-6 * conducted);
+}
@@ -0,0 +1,230 @@
+//! Checks the coolant marched past a face at one temperature against the exponential
+//! approach of a stream to a wall, and that a cold plate carries away the heat generated in
+//! the cube
+
+mod common;
+
+use common::{adiabatic, assert_close, info, params, DIVISIONS, K, LEN, Q_DOT};
+use finite_difference::channel::{self, Channel, Coolant, Error};
+use finite_difference::conditions::{Condition, Convection};
+use finite_difference::dump::{SaveFile, SimulationResult};
+use finite_difference::error::L2Norm;
+use finite_difference::geometry::{self, Geometry};
+use finite_difference::input::Conditions;
+use finite_difference::observer::Cancel;
+use finite_difference::prelude::{Matrix, Precision};
+use finite_difference::probe::Axis;
+use finite_difference::report::Report;
+use finite_difference::solver;
+use finite_difference::stencil::{Coefficients, Sweep};
+
+/// Water along x
+fn water(mass_flow: f64) -> Channel<f64> {
+    Channel {
+        mass_flow,
+        cp: 4180.,
+        t_inlet: 290.,
+        h: 2500.,
+        along: Axis::X,
+        reversed: false,
+    }
+}
+
+/// Cooled by `bottom`, insulated elsewhere
+fn conditions(bottom: Condition<f64>) -> Conditions<f64> {
+    Conditions {
+        right_boundary: adiabatic(),
+        left_boundary: adiabatic(),
+        top_boundary: adiabatic(),
+        bot_boundary: bottom,
+        front_boundary: adiabatic(),
+        back_boundary: adiabatic(),
+    }
+}
+
+/// Settles the coolant of `conditions`, solving with `observer`
+fn settle(conditions: &Conditions<f64>, observer: Cancel) -> (SimulationResult<f64>, usize) {
+    let info = info(Q_DOT);
+
+    let mut solves = 0;
+    let mut coolant = Coolant::inlet(conditions, DIVISIONS).unwrap();
+    let simulation = channel::settle(
+        conditions,
+        params().del(),
+        &mut coolant,
+        |coolant, initial| {
+            // every solve but the first starts from the one before
+            assert_eq!(initial.is_some(), solves > 0);
+            solves += 1;
+
+            let coefficients = coolant.constants(
+                Coefficients::new(
+                    &conditions.make_boundaries(),
+                    &info,
+                    DIVISIONS,
+                    Sweep::default(),
+                )
+                .unwrap(),
+                conditions,
+                &info,
+                DIVISIONS,
+            );
+            solver::solve_from(
+                &coefficients,
+                initial,
+                params(),
+                &L2Norm,
+                &[],
+                observer.clone(),
+            )
+        },
+    )
+    .unwrap();
+
+    (simulation, solves)
+}
+
+fn solve(conditions: &Conditions<f64>) -> (Matrix<f64>, Report<f64>) {
+    let (simulation, _) = settle(conditions, Cancel::new());
+    let temps = simulation.temperatures();
+
+    let results = SaveFile {
+        conditions: *conditions,
+        simulation,
+        solver_params: params(),
+        precision: Precision::Double,
+        thermal_conductivity: Some(K),
+        energy_generation: Some(Q_DOT),
+        samples: None,
+        heat_flux: None,
+        geometry: None,
+        cylinder: None,
+        orthotropic: None,
+        mirrored: None,
+    };
+
+    (temps, Report::new(&results))
+}
+
+#[test]
+fn the_coolant_approaches_a_face_at_one_temperature_exponentially() {
+    let temps = Matrix::from_elem((DIVISIONS, DIVISIONS, DIVISIONS), 350.);
+    let channel = water(0.002);
+
+    let stream = channel.march(&temps, params().del(), 1, false).unwrap();
+    let units = channel.h * LEN * LEN / (channel.mass_flow * channel.cp);
+    assert_close(stream.outlet, 350. - 60. * (-units).exp(), 1e-9);
+
+    // warming along the flow, and convecting what the stream carries away
+    assert!(stream.fluid.windows(2).all(|pair| pair[0] < pair[1]));
+    let row = |x: usize| {
+        if x == 0 || x == DIVISIONS - 1 {
+            0.5
+        } else {
+            1.
+        }
+    };
+    let convected: f64 = (0..DIVISIONS)
+        .map(|x| channel.h * row(x) * params().del() * LEN * (350. - stream.fluid[x]))
+        .sum();
+    assert_close(convected, channel.heat_flow(&stream), 1e-9);
+
+    // the same stream the other way along the face
+    let reversed = Channel {
+        reversed: true,
+        ..channel
+    }
+    .march(&temps, params().del(), 1, false)
+    .unwrap();
+    assert_close(reversed.outlet, stream.outlet, 1e-9);
+    for (x, fluid) in reversed.fluid.iter().enumerate() {
+        assert_close(*fluid, stream.fluid[DIVISIONS - 1 - x], 1e-9);
+    }
+
+    let json = r#"{"type": "Channel", "mass_flow": 0.02, "cp": 4180.0, "t_inlet": 293.0,
+                   "h": 2500.0, "along": "z"}"#;
+    let Condition::Channel(parsed) = serde_json::from_str::<Condition<f64>>(json).unwrap() else {
+        panic!("not a channel");
+    };
+    assert_eq!(parsed.along, Axis::Z);
+    assert!(!parsed.reversed);
+}
+
+#[test]
+fn a_cold_plate_carries_away_the_heat_generated() {
+    let (temps, report) = solve(&conditions(Condition::Channel(water(0.002))));
+
+    // every watt generated in the cube leaves with the coolant
+    let generated = Q_DOT * LEN * LEN * LEN;
+    let plate = &report.channels[0];
+    assert_eq!(plate.face, "bot");
+    assert_close(plate.heat_flow, generated, 1e-3 * generated);
+    assert_close(report.faces[3].heat_flow.unwrap(), plate.heat_flow, 1e-9);
+    assert!(report.imbalance.unwrap().abs() < 1e-3 * generated);
+
+    // the coolant warms along x, and the cube with it
+    assert!(plate.fluid.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(temps[[DIVISIONS - 1, 0, 0]] > temps[[0, 0, 0]] + 1.);
+    assert!(report.to_string().contains("Channel"));
+
+    // a stopped solve ends the settling
+    let cancel = Cancel::new();
+    cancel.cancel();
+    let (stopped, solves) = settle(&conditions(Condition::Channel(water(0.002))), cancel);
+    assert!(stopped.stopped);
+    assert_eq!(solves, 1);
+
+    // the coolant only keeps constants for the nodes on the faces of the cube
+    let plate = conditions(Condition::Channel(water(0.002)));
+    let info = info(Q_DOT);
+    let coefficients = Coolant::inlet(&plate, DIVISIONS).unwrap().constants(
+        Coefficients::new(&plate.make_boundaries(), &info, DIVISIONS, Sweep::default()).unwrap(),
+        &plate,
+        &info,
+        DIVISIONS,
+    );
+    assert!(coefficients.constants.is_none());
+    for region in &coefficients.boundaries {
+        let shape = [region.x.len(), region.y.len(), region.z.len()];
+        assert_eq!(region.constants.as_ref().unwrap().shape(), shape);
+    }
+
+    // a coolant flowing fast enough to stay at its inlet temperature is plain convection
+    let (fast, _) = solve(&conditions(Condition::Channel(water(1e3))));
+    let (convection, _) = solve(&conditions(Condition::Convection(Convection {
+        h: 2500.,
+        t_inf: 290.,
+    })));
+    for (fast, convection) in fast.iter().zip(&convection) {
+        assert_close(*fast, *convection, 1e-6);
+    }
+}
+
+#[test]
+fn a_channel_flows_along_a_face_of_the_cube() {
+    let temps = Matrix::from_elem((DIVISIONS, DIVISIONS, DIVISIONS), 350.);
+
+    // along x is through the left and right faces
+    assert_eq!(
+        water(0.002).march(&temps, params().del(), 0, true),
+        Err(Error::AlongNormal(Axis::X))
+    );
+    let through = Conditions {
+        left_boundary: Condition::Channel(water(0.002)),
+        ..conditions(adiabatic())
+    };
+    assert!(matches!(
+        Coolant::inlet(&through, DIVISIONS),
+        Err(Error::AlongNormal(Axis::X))
+    ));
+
+    // a void has no direction to flow along
+    let mut geometry = Geometry::default();
+    geometry
+        .surfaces
+        .insert("plate".to_string(), Condition::Channel(water(0.002)));
+    assert!(matches!(
+        geometry.mask(DIVISIONS, params().del()),
+        Err(geometry::Error::UnsupportedSurface(name)) if name == "plate"
+    ));
+}
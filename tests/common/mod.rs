@@ -0,0 +1,53 @@
+//! Setup shared between the tests, most of which solve a cube of side `LEN` with `DIVISIONS`
+//! nodes along each side and a conductivity of `K`
+
+#![allow(dead_code)]
+
+use finite_difference::conditions::{Condition, Convection, HeatFlux};
+use finite_difference::prelude::SolverInfo;
+use finite_difference::stencil::Sweep;
+use finite_difference::SolverParams;
+
+/// `[W/(mK)]`
+pub const K: f64 = 15.;
+/// `[W/m^3]`
+pub const Q_DOT: f64 = 1e5;
+/// `[m]`
+pub const LEN: f64 = 0.08;
+pub const DIVISIONS: usize = 9;
+
+/// solver parameters converging the cube well past the tolerances of the tests, without
+/// recording any steps but the last
+pub fn params() -> SolverParams<f64> {
+    SolverParams {
+        len: LEN,
+        divisions: DIVISIONS,
+        error_epsilon: 1e-12,
+        data_steps: usize::MAX,
+        error_steps: usize::MAX,
+        sweep: Sweep::default(),
+        threads: None,
+    }
+}
+
+/// conducting with `K` and generating `q_dot` between the nodes of `params`
+pub fn info(q_dot: f64) -> SolverInfo<f64> {
+    SolverInfo::builder()
+        .k(K)
+        .q_dot(q_dot)
+        .del(params().del())
+        .build()
+}
+
+pub fn adiabatic() -> Condition<f64> {
+    Condition::HeatFlux(HeatFlux { heat_flux: 0. })
+}
+
+/// to air at 290 K
+pub fn convection(h: f64) -> Condition<f64> {
+    Condition::Convection(Convection { h, t_inf: 290. })
+}
+
+pub fn assert_close(a: f64, b: f64, tolerance: f64) {
+    assert!((a - b).abs() < tolerance, "{a} against {b}");
+}
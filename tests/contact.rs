@@ -0,0 +1,184 @@
+//! Checks contact resistances across a plane and between two materials against the series
+//! resistance of a bar, and the heat flow across them in the report
+
+mod common;
+
+use common::{adiabatic, assert_close, info, params, DIVISIONS, K, LEN};
+use finite_difference::conditions::{Condition, Temperature};
+use finite_difference::csg::Shape;
+use finite_difference::dump::SaveFile;
+use finite_difference::error::L2Norm;
+use finite_difference::geometry::{Contact, Error, Geometry, Interface, Material, Solid};
+use finite_difference::input::Conditions;
+use finite_difference::prelude::{Matrix, Precision};
+use finite_difference::probe::Axis;
+use finite_difference::report::Report;
+use finite_difference::solver;
+use finite_difference::stencil::{Coefficients, Sweep};
+
+use std::collections::BTreeMap;
+
+const CONDUCTANCE: f64 = 2000.;
+
+/// Held at 400 K on the left and 300 K on the right, insulated elsewhere
+fn conditions() -> Conditions<f64> {
+    Conditions {
+        right_boundary: Condition::Temperature(Temperature { temperature: 300. }),
+        left_boundary: Condition::Temperature(Temperature { temperature: 400. }),
+        top_boundary: adiabatic(),
+        bot_boundary: adiabatic(),
+        front_boundary: adiabatic(),
+        back_boundary: adiabatic(),
+    }
+}
+
+fn contact(interface: Interface<f64>) -> Contact<f64> {
+    Contact {
+        name: "joint".to_string(),
+        conductance: CONDUCTANCE,
+        interface,
+    }
+}
+
+fn report(geometry: Geometry<f64>) -> (Report<f64>, Matrix<f64>) {
+    let mask = geometry.mask(DIVISIONS, params().del()).unwrap();
+    let coefficients = Coefficients::masked(
+        &conditions().make_boundaries(),
+        &info(0.),
+        &mask,
+        Sweep::default(),
+    )
+    .unwrap();
+    let simulation = solver::solve(&coefficients, params(), &L2Norm, &[], ());
+    let temps = simulation.temperatures();
+
+    let results = SaveFile {
+        conditions: conditions(),
+        simulation,
+        solver_params: params(),
+        precision: Precision::Double,
+        thermal_conductivity: Some(K),
+        energy_generation: Some(0.),
+        samples: None,
+        heat_flux: None,
+        geometry: Some(geometry),
+        cylinder: None,
+        orthotropic: None,
+        mirrored: None,
+    };
+
+    (Report::new(&results), temps)
+}
+
+#[test]
+fn a_plane_contact_makes_the_temperature_jump() {
+    let geometry = Geometry {
+        contacts: vec![contact(Interface::Plane {
+            axis: Axis::X,
+            position: LEN / 2.,
+        })],
+        ..Geometry::default()
+    };
+    let (report, temps) = report(geometry);
+
+    // the contact is in series with the conduction along the bar
+    let flux = 100. / (LEN / K + 1. / CONDUCTANCE);
+    let half = LEN / 2.;
+    let hot = |x: f64| 400. - flux * x / K;
+    let cold = |x: f64| 300. + flux * (LEN - x) / K;
+
+    for ((x, _, _), &temp) in temps.indexed_iter() {
+        let x = params().del() * x as f64;
+        let expected = match x {
+            x if x < half - 1e-9 => hot(x),
+            x if x > half + 1e-9 => cold(x),
+            // the nodes on the contact sit between its two faces
+            _ => (hot(x) + cold(x)) / 2.,
+        };
+        assert_close(temp, expected, 1e-6);
+    }
+    assert_close(hot(half) - cold(half), flux / CONDUCTANCE, 1e-9);
+
+    let joint = &report.contacts[0];
+    assert_eq!(joint.name, "joint");
+    assert_close(joint.area, LEN * LEN, 1e-12);
+    assert_close(joint.heat_flow.unwrap(), flux * LEN * LEN, 1e-6);
+
+    let imbalance = report.imbalance.unwrap();
+    assert!(imbalance.abs() < 1e-6, "{imbalance}");
+}
+
+#[test]
+fn a_contact_between_materials_conducts_from_the_first_to_the_second() {
+    let slab = |min: f64, max: f64, material: &str| Solid {
+        shape: Shape::Box {
+            min: [min, -1., -1.],
+            max: [max, 1., 1.],
+        },
+        material: Some(material.to_string()),
+        surface: None,
+    };
+    let material = |k: f64| Material {
+        k: Some(k),
+        q_dot: Some(0.),
+    };
+
+    let geometry = Geometry {
+        materials: BTreeMap::from([
+            ("copper".to_string(), material(400.)),
+            ("steel".to_string(), material(K)),
+        ]),
+        solids: vec![slab(-1., LEN / 2., "copper"), slab(LEN / 2., 1., "steel")],
+        contacts: vec![contact(Interface::Materials([
+            "steel".to_string(),
+            "copper".to_string(),
+        ]))],
+        ..Geometry::default()
+    };
+    let (report, _) = report(geometry);
+
+    let resistance = LEN / 2. / 400. + 1. / CONDUCTANCE + LEN / 2. / K;
+    let conducted = 100. / resistance * LEN * LEN;
+
+    let left: f64 = report.faces[1].heat_flow.unwrap();
+    assert_close(left, -conducted, 1e-6);
+
+    // heat flows from the copper into the steel, against the contact's order
+    let joint: f64 = report.contacts[0].heat_flow.unwrap();
+    assert_close(joint, -conducted, 1e-6);
+}
+
+#[test]
+fn a_contact_must_be_inside_the_cube() {
+    let outside = Geometry {
+        contacts: vec![contact(Interface::Plane {
+            axis: Axis::Z,
+            position: LEN,
+        })],
+        ..Geometry::default()
+    };
+    assert!(matches!(
+        outside.mask(DIVISIONS, params().del()),
+        Err(Error::ContactOutside(name)) if name == "joint"
+    ));
+
+    let unknown = Geometry {
+        contacts: vec![contact(Interface::Materials([
+            "copper".to_string(),
+            "steel".to_string(),
+        ]))],
+        ..Geometry::default()
+    };
+    assert!(matches!(
+        unknown.mask(DIVISIONS, params().del()),
+        Err(Error::UnknownMaterial(name)) if name == "copper"
+    ));
+
+    let json = r#"{"name": "pad", "conductance": 800.0,
+                   "interface": {"plane": {"axis": "y", "position": 0.02}}}"#;
+    let parsed: Contact<f64> = serde_json::from_str(json).unwrap();
+    assert!(matches!(
+        parsed.interface,
+        Interface::Plane { axis: Axis::Y, .. }
+    ));
+}
@@ -0,0 +1,104 @@
+//! Checks the Richardson extrapolation and the interpolation onto probes against values
+//! constructed with a known error
+
+use finite_difference::convergence::{estimate, Grid, Study, Summary};
+use finite_difference::prelude::Matrix;
+use finite_difference::probe::interpolate;
+use finite_difference::SolverParams;
+
+fn close(left: f64, right: f64) -> bool {
+    (left - right).abs() < 1e-8
+}
+
+#[test]
+fn recovers_order_and_limit() {
+    // phi(h) = 300 + 5 h^p is exactly what the extrapolation assumes
+    for (del, order) in [([0.04, 0.02, 0.01], 2.), ([0.03, 0.02, 0.01], 1.5)] {
+        let values = del.map(|h: f64| 300. + 5. * h.powf(order));
+        let estimate = estimate(&del, &values);
+
+        assert!(close(estimate.order.unwrap(), order), "{estimate:?}");
+        assert!(close(estimate.extrapolated.unwrap(), 300.), "{estimate:?}");
+    }
+}
+
+#[test]
+fn two_grids_assume_formal_order() {
+    let del = [0.02, 0.01];
+    let values = del.map(|h: f64| 300. + 5. * h * h);
+    let estimate = estimate(&del, &values);
+
+    assert_eq!(estimate.order, None);
+    assert!(close(estimate.extrapolated.unwrap(), 300.));
+
+    // safety factor of 3 on the relative difference, over 2^2 - 1
+    let relative = (values[1] - values[0]).abs() / values[1];
+    assert!(close(estimate.gci.unwrap(), relative));
+}
+
+#[test]
+fn unchanged_values_are_converged() {
+    let estimate = estimate(&[0.04, 0.02, 0.01], &[300., 300., 300.]);
+
+    assert_eq!(estimate.order, None);
+    assert_eq!(estimate.extrapolated, Some(300.));
+    assert_eq!(estimate.gci, Some(0.));
+}
+
+#[test]
+fn summary_orders_grids() {
+    let probes = [[0.5, 0.5, 0.5]];
+    let grid = |divisions: usize, del: f64| Grid {
+        divisions,
+        del,
+        values: vec![300. + del * del],
+    };
+
+    let summary = Summary::new(
+        &probes,
+        vec![grid(41, 0.025), grid(11, 0.1), grid(21, 0.05)],
+    );
+
+    assert_eq!(summary.divisions, [11, 21, 41]);
+    assert!(close(summary.probes[0].estimate.order.unwrap(), 2.));
+}
+
+#[test]
+fn grids_need_a_node_on_each_end() {
+    let params = |divisions: usize| {
+        format!(
+            r#"{{"len": 0.1, "divisions": {divisions}, "error_epsilon": 1e-6,
+                "data_steps": 100, "error_steps": 10}}"#
+        )
+    };
+
+    for divisions in [0, 1] {
+        assert!(serde_json::from_str::<SolverParams<f64>>(&params(divisions)).is_err());
+    }
+    let two: SolverParams<f64> = serde_json::from_str(&params(2)).unwrap();
+    assert!(close(two.del(), 0.1));
+
+    assert!(serde_json::from_str::<Study<f64>>(r#"{"divisions": [11, 1]}"#).is_err());
+    assert!(serde_json::from_str::<Study<f64>>(r#"{"divisions": [11, 21]}"#).is_ok());
+}
+
+#[test]
+fn interpolation_is_exact_for_trilinear_fields() {
+    let del = 0.1;
+    let field = |x: f64, y: f64, z: f64| 3. + 2. * x - y + 0.5 * z + 4. * x * y * z;
+
+    let temps = Matrix::from_shape_fn((5, 5, 5), |(i, j, k)| {
+        field(i as f64 * del, j as f64 * del, k as f64 * del)
+    });
+
+    for point in [[0.13, 0.27, 0.38], [0., 0.4, 0.05], [0.4, 0.4, 0.4]] {
+        let value = interpolate(&temps, del, point).unwrap();
+        assert!(
+            close(value, field(point[0], point[1], point[2])),
+            "{point:?}"
+        );
+    }
+
+    assert_eq!(interpolate(&temps, del, [0.41, 0.2, 0.2]), None);
+    assert_eq!(interpolate(&temps, del, [0.2, -0.01, 0.2]), None);
+}
@@ -0,0 +1,175 @@
+//! Checks the plate correlations against hand calculations and that settling a cube leaves
+//! every correlated coefficient at the one of its solved face temperature
+
+mod common;
+
+use common::{adiabatic, assert_close, info, params, DIVISIONS, K, LEN};
+use finite_difference::conditions::{Condition, Temperature};
+use finite_difference::correlation::{
+    self, Correlated, Flow, Fluid, Orientation, PASSES, TOLERANCE,
+};
+use finite_difference::dump::SaveFile;
+use finite_difference::error::L2Norm;
+use finite_difference::input::Conditions;
+use finite_difference::observer::Cancel;
+use finite_difference::prelude::{Matrix, Precision};
+use finite_difference::report::Report;
+use finite_difference::solver;
+use finite_difference::stencil::{Coefficients, Sweep};
+
+fn air(flow: Flow<f64>, t_inf: f64) -> Correlated<f64> {
+    Correlated {
+        fluid: Fluid::Air,
+        flow,
+        t_inf,
+        h: 10.,
+    }
+}
+
+#[test]
+fn the_correlations_match_hand_calculations() {
+    let properties = Fluid::Air.properties(300.);
+    assert_close(properties.k, 0.0263, 1e-12);
+    assert_close(properties.nu, 15.89e-6, 1e-15);
+    assert_close(properties.beta, 1. / 300., 1e-12);
+
+    // halfway between the rows at 300 and 325 K
+    let water = Fluid::Water.properties(312.5);
+    assert_close(water.k, 0.629, 1e-12);
+
+    // laminar, with a Reynolds number of 1.57e5 at a film temperature of 300 K
+    let forced = air(Flow::Forced { velocity: 5. }, 290.);
+    let h = forced.coefficient(310., Orientation::Vertical, 0.5);
+    assert_close(h, 12.342, 1e-3);
+
+    // Churchill and Chu with a Rayleigh number of 4.6e8
+    let natural = air(Flow::Natural, 280.);
+    let h = natural.coefficient(320., Orientation::Vertical, 0.5);
+    assert_close(h, 5.081, 1e-3);
+
+    // a hot face loses more heat facing up than down, and a cold one the other way around
+    let up = natural.coefficient(320., Orientation::Up, 0.5);
+    let down = natural.coefficient(320., Orientation::Down, 0.5);
+    assert!(up > down, "{up} against {down}");
+    let up = natural.coefficient(250., Orientation::Up, 0.5);
+    let down = natural.coefficient(250., Orientation::Down, 0.5);
+    assert!(down > up, "{down} against {up}");
+
+    // a face facing up at the temperature of still air convects nothing, which is settled
+    let still = Condition::Correlated(Correlated {
+        h: 0.,
+        ..air(Flow::Natural, 300.)
+    });
+    let insulated = adiabatic();
+    let mut conditions = Conditions {
+        right_boundary: insulated,
+        left_boundary: insulated,
+        top_boundary: still,
+        bot_boundary: insulated,
+        front_boundary: insulated,
+        back_boundary: insulated,
+    };
+    let temps = Matrix::from_elem((DIVISIONS, DIVISIONS, DIVISIONS), 300.);
+    assert_eq!(correlation::update(&mut conditions, &temps, LEN), Some(0.));
+
+    let json = r#"{"type": "Correlated", "fluid": "Air", "t_inf": 293.0,
+                   "flow": {"type": "Forced", "velocity": 3.0}}"#;
+    let Condition::Correlated(parsed) = serde_json::from_str::<Condition<f64>>(json).unwrap()
+    else {
+        panic!("not a correlated condition");
+    };
+    assert_eq!(parsed.flow, Flow::Forced { velocity: 3. });
+    assert_eq!(parsed.h, 10.);
+}
+
+#[test]
+fn settling_evaluates_each_face_at_its_temperature() {
+    let natural = Condition::Correlated(air(Flow::Natural, 290.));
+    let conditions = Conditions {
+        right_boundary: natural,
+        left_boundary: natural,
+        top_boundary: natural,
+        bot_boundary: Condition::Temperature(Temperature { temperature: 350. }),
+        front_boundary: natural,
+        back_boundary: natural,
+    };
+
+    let info = info(0.);
+
+    let solve = |conditions: Conditions<f64>, initial: Option<&Matrix<f64>>, observer: Cancel| {
+        let coefficients = Coefficients::new(
+            &conditions.make_boundaries(),
+            &info,
+            DIVISIONS,
+            Sweep::default(),
+        )
+        .unwrap();
+        solver::solve_from(&coefficients, initial, params(), &L2Norm, &[], observer)
+    };
+
+    // every solve but the first starts from the one before
+    let mut solves = 0;
+    let (settled, simulation) = correlation::settle(conditions, LEN, |conditions, initial| {
+        assert_eq!(initial.is_some(), solves > 0);
+        solves += 1;
+        solve(conditions, initial, Cancel::new())
+    });
+    assert!(solves > 1 && solves < PASSES, "{solves}");
+
+    // a stopped solve ends the settling
+    let cancel = Cancel::new();
+    cancel.cancel();
+    let mut stopped = 0;
+    let (_, result) = correlation::settle(conditions, LEN, |conditions, initial| {
+        stopped += 1;
+        solve(conditions, initial, cancel.clone())
+    });
+    assert!(result.stopped);
+    assert_eq!(stopped, 1);
+
+    // evaluating again at the settled temperatures barely changes them
+    let temps = simulation.temperatures();
+    let mut again = settled;
+    let change = correlation::update(&mut again, &temps, LEN).unwrap();
+    assert!(change < TOLERANCE, "{change}");
+
+    let Condition::Correlated(top) = settled.top_boundary else {
+        panic!("the top is correlated");
+    };
+    let Condition::Correlated(right) = settled.right_boundary else {
+        panic!("the right is correlated");
+    };
+    assert!(top.h != 10. && right.h != 10.);
+    let surface = correlation::face_temperature(&temps, 1, true);
+    assert_close(
+        top.h,
+        top.coefficient(surface, Orientation::Up, LEN),
+        TOLERANCE * top.h,
+    );
+
+    // the report convects with the settled coefficients, so what the bottom conducts in leaves
+    // through the other faces, up to the one sided difference at the held face
+    let results = SaveFile {
+        conditions: settled,
+        simulation,
+        solver_params: params(),
+        precision: Precision::Double,
+        thermal_conductivity: Some(K),
+        energy_generation: Some(0.),
+        samples: None,
+        heat_flux: None,
+        geometry: None,
+        cylinder: None,
+        orthotropic: None,
+        mirrored: None,
+    };
+    let report = Report::new(&results);
+    let bottom: f64 = report.faces[3].heat_flow.unwrap();
+    let imbalance = report.imbalance.unwrap();
+    assert!(bottom < 0., "{bottom}");
+    assert!(
+        imbalance.abs() < 0.02 * bottom.abs(),
+        "{imbalance} against {bottom}"
+    );
+    assert!(report.to_string().contains("natural convection to Air"));
+}
@@ -0,0 +1,267 @@
+//! Checks the primitives and their combinations, a bar of two materials in series and the heat
+//! balance of a resistor on a board
+
+mod common;
+
+use common::{adiabatic, convection, info, params, DIVISIONS};
+use finite_difference::conditions::{Condition, Temperature};
+use finite_difference::csg::Shape;
+use finite_difference::dump::{Results, SaveFile};
+use finite_difference::error::L2Norm;
+use finite_difference::geometry::{Cell, Error, Geometry, Material, Solid};
+use finite_difference::input::Conditions;
+use finite_difference::prelude::{Precision, SolverInfo};
+use finite_difference::probe::Axis;
+use finite_difference::report::Report;
+use finite_difference::solver;
+use finite_difference::stencil::{Coefficients, Sweep};
+
+use std::collections::BTreeMap;
+
+fn cuboid(min: [f64; 3], max: [f64; 3]) -> Shape<f64> {
+    Shape::Box { min, max }
+}
+
+fn solid(shape: Shape<f64>, material: &str) -> Solid<f64> {
+    Solid {
+        shape,
+        material: Some(material.to_string()),
+        surface: None,
+    }
+}
+
+#[test]
+fn shapes_contain_the_points_inside_them() {
+    let sphere = Shape::Sphere {
+        centre: [0.; 3],
+        radius: 1.,
+    };
+    assert!(sphere.contains([0.5, 0.5, 0.5]));
+    assert!(!sphere.contains([0.6, 0.6, 0.6]));
+
+    // along the diagonal of the unit cube
+    let cylinder = Shape::Cylinder {
+        start: [0.; 3],
+        end: [1.; 3],
+        radius: 0.1,
+    };
+    assert!(cylinder.contains([0.5, 0.55, 0.5]));
+    assert!(!cylinder.contains([0.5, 0.65, 0.5]));
+    assert!(!cylinder.contains([1.05, 1.05, 1.05]));
+
+    // an L in the x-z plane, swept along y
+    let extrusion = Shape::Extrusion {
+        axis: Axis::Y,
+        points: vec![[0., 0.], [2., 0.], [2., 1.], [1., 1.], [1., 2.], [0., 2.]],
+        from: 1.,
+        to: -1.,
+    };
+    assert!(extrusion.contains([0.5, 0., 1.5]));
+    assert!(extrusion.contains([1.5, 0.5, 0.5]));
+    assert!(!extrusion.contains([1.5, 0., 1.5]));
+    assert!(!extrusion.contains([0.5, 1.5, 0.5]));
+
+    let plate = cuboid([-2., -2., -0.1], [2., 2., 0.1]);
+    let with_hole = Shape::Difference {
+        shape: Box::new(plate.clone()),
+        minus: vec![sphere.clone()],
+    };
+    assert!(with_hole.contains([1.5, 0., 0.]));
+    assert!(!with_hole.contains([0.5, 0., 0.]));
+
+    let disc = Shape::Intersection {
+        shapes: vec![plate.clone(), sphere.clone()],
+    };
+    assert!(disc.contains([0.5, 0., 0.]));
+    assert!(!disc.contains([0., 0., 0.5]));
+
+    let union = Shape::Union {
+        shapes: vec![plate, sphere],
+    };
+    assert!(union.contains([1.5, 0., 0.]) && union.contains([0., 0., 0.5]));
+
+    // written in an input file with the name of each shape in its `type`
+    let json = r#"{
+        "type": "Difference",
+        "shape": {"type": "Box", "min": [0, 0, 0], "max": [1, 1, 1]},
+        "minus": [{"type": "Cylinder", "start": [0.5, 0.5, 0], "end": [0.5, 0.5, 1], "radius": 0.2}]
+    }"#;
+    let tube: Shape<f64> = serde_json::from_str(json).unwrap();
+    assert!(tube.contains([0.1, 0.1, 0.5]) && !tube.contains([0.5, 0.5, 0.5]));
+}
+
+#[test]
+fn a_bar_of_two_materials_conducts_in_series() {
+    // left at 300 K and right at 400 K, a third of the conductivity over the left half
+    let conditions = Conditions {
+        right_boundary: Condition::Temperature(Temperature { temperature: 400. }),
+        left_boundary: Condition::Temperature(Temperature { temperature: 300. }),
+        top_boundary: adiabatic(),
+        bot_boundary: adiabatic(),
+        front_boundary: adiabatic(),
+        back_boundary: adiabatic(),
+    };
+
+    let mut materials = BTreeMap::new();
+    materials.insert(
+        "steel".to_string(),
+        Material {
+            k: Some(45.),
+            q_dot: None,
+        },
+    );
+
+    let geometry = Geometry {
+        materials,
+        solids: vec![
+            // the input's material everywhere, then the right half replaced
+            Solid {
+                shape: cuboid([-1.; 3], [1.; 3]),
+                material: None,
+                surface: None,
+            },
+            solid(cuboid([0.04, -1., -1.], [1.; 3]), "steel"),
+        ],
+        ..Geometry::default()
+    };
+
+    let mask = geometry.mask(DIVISIONS, params().del()).unwrap();
+    assert_eq!(mask.cells[[3, 0, 0]], Cell::Solid(0));
+    assert_eq!(mask.cells[[4, 0, 0]], Cell::Solid(1));
+
+    let info = info(0.);
+    let coefficients = Coefficients::masked(
+        &conditions.make_boundaries(),
+        &info,
+        &mask,
+        Sweep::default(),
+    )
+    .unwrap();
+    let temps = solver::solve(&coefficients, params(), &L2Norm, &[], ()).temperatures();
+
+    // the same flux through both halves, so the drop across each is inverse to its k
+    let joint = 300. + 100. * 45. / (15. + 45.);
+    for ((x, _, _), &temp) in temps.indexed_iter() {
+        let expected = if x <= 4 {
+            300. + (joint - 300.) * x as f64 / 4.
+        } else {
+            joint + (400. - joint) * (x - 4) as f64 / 4.
+        };
+        assert!((temp - expected).abs() < 1e-6, "{temp} against {expected}");
+    }
+}
+
+#[test]
+fn a_resistor_on_a_board_balances_its_heat() {
+    // a board across the bottom of the cube cooled from below, with a resistor on top
+    let conditions = Conditions {
+        right_boundary: adiabatic(),
+        left_boundary: adiabatic(),
+        top_boundary: adiabatic(),
+        bot_boundary: convection(20.),
+        front_boundary: adiabatic(),
+        back_boundary: adiabatic(),
+    };
+
+    let mut materials = BTreeMap::new();
+    materials.insert(
+        "fr4".to_string(),
+        Material {
+            k: Some(0.3),
+            q_dot: Some(0.),
+        },
+    );
+    materials.insert(
+        "resistor".to_string(),
+        Material {
+            k: Some(2.),
+            q_dot: Some(1e6),
+        },
+    );
+    let mut surfaces = BTreeMap::new();
+    surfaces.insert("air".to_string(), convection(10.));
+
+    let geometry = Geometry {
+        surfaces,
+        materials,
+        solids: vec![
+            Solid {
+                surface: Some("air".to_string()),
+                ..solid(cuboid([-1., -1., -1.], [1., 0.02, 1.]), "fr4")
+            },
+            Solid {
+                surface: Some("air".to_string()),
+                ..solid(cuboid([0.03, 0.02, 0.02], [0.05, 0.03, 0.06]), "resistor")
+            },
+        ],
+        ..Geometry::default()
+    };
+
+    let mask = geometry.mask(DIVISIONS, params().del()).unwrap();
+    // above the board and beside the resistor, and far from both
+    assert_eq!(mask.cells[[0, 2, 0]], Cell::Void(1));
+    assert_eq!(mask.cells[[2, 2, 3]], Cell::Void(1));
+    assert_eq!(mask.cells[[0, 5, 0]], Cell::Void(0));
+
+    // the input's `energy_generation` is not used by any cell
+    let info = SolverInfo::builder()
+        .k(0.3)
+        .q_dot(5e7)
+        .del(params().del())
+        .build();
+    let coefficients = Coefficients::masked(
+        &conditions.make_boundaries(),
+        &info,
+        &mask,
+        Sweep::default(),
+    )
+    .unwrap();
+    let simulation = solver::solve(&coefficients, params(), &L2Norm, &[], ());
+
+    let results = SaveFile {
+        conditions,
+        simulation,
+        solver_params: params(),
+        precision: Precision::Double,
+        thermal_conductivity: Some(0.3),
+        energy_generation: Some(5e7),
+        samples: None,
+        heat_flux: None,
+        geometry: Some(geometry),
+        cylinder: None,
+        orthotropic: None,
+        mirrored: None,
+    };
+    let json = serde_json::to_string(&results).unwrap();
+    let results: Results<f64> = serde_json::from_str(&json).unwrap();
+    let report = Report::new(&results);
+
+    let generated: f64 = report.heat_generated.unwrap();
+    assert!(
+        (generated - 1e6 * 0.02 * 0.01 * 0.04).abs() < 1e-9,
+        "{generated}"
+    );
+
+    let air: f64 = report.surfaces[0].heat_flow.unwrap();
+    assert!(air > 0. && air < generated);
+
+    let imbalance = report.imbalance.unwrap() / generated;
+    assert!(imbalance.abs() < 0.01, "{imbalance}");
+
+    // hottest in the resistor
+    let [x, y, z] = report.max.index;
+    assert!((3..=5).contains(&x) && (2..=3).contains(&y) && (2..=6).contains(&z));
+}
+
+#[test]
+fn solids_refer_to_materials_by_name() {
+    let geometry = Geometry {
+        solids: vec![solid(cuboid([0.; 3], [0.04; 3]), "copper")],
+        ..Geometry::default()
+    };
+
+    assert!(matches!(
+        geometry.mask(DIVISIONS, params().del()),
+        Err(Error::UnknownMaterial(name)) if name == "copper"
+    ));
+}
@@ -0,0 +1,261 @@
+//! Compares cylindrical solves to the analytic profiles of a heated rod and a pipe wall
+//!
+//! the control volumes conserve heat exactly, so the rod's quadratic profile is reproduced to
+//! the solver's tolerance while the pipe's logarithmic one is only close
+
+mod common;
+
+use common::{adiabatic, K, Q_DOT};
+use finite_difference::conditions::{Condition, Convection, Temperature};
+use finite_difference::cylindrical::{self, Cylinder};
+use finite_difference::dump::{SaveFile, SimulationResult};
+use finite_difference::error::L2Norm;
+use finite_difference::input::Conditions;
+use finite_difference::prelude::{Matrix, Precision, SolverInfo};
+use finite_difference::report::Report;
+use finite_difference::stencil::Sweep;
+use finite_difference::{solver, SolverParams};
+
+const H: f64 = 50.;
+const T_INF: f64 = 300.;
+const RADIUS: f64 = 0.05;
+
+fn temperature(temperature: f64) -> Condition<f64> {
+    Condition::Temperature(Temperature { temperature })
+}
+
+/// A rod generating heat, cooled around its outside and insulated everywhere else
+fn rod() -> Conditions<f64> {
+    Conditions {
+        right_boundary: Condition::Convection(Convection { h: H, t_inf: T_INF }),
+        left_boundary: adiabatic(),
+        top_boundary: adiabatic(),
+        bot_boundary: adiabatic(),
+        front_boundary: adiabatic(),
+        back_boundary: adiabatic(),
+    }
+}
+
+fn rod_temperature(r: f64) -> f64 {
+    T_INF + Q_DOT * RADIUS / (2. * H) + Q_DOT * (RADIUS * RADIUS - r * r) / (4. * K)
+}
+
+fn params(divisions: usize) -> SolverParams<f64> {
+    SolverParams {
+        len: 0.1,
+        divisions,
+        ..common::params()
+    }
+}
+
+fn simulate(
+    cylinder: &Cylinder<f64>,
+    conditions: Conditions<f64>,
+    q_dot: f64,
+    divisions: usize,
+) -> SimulationResult<f64> {
+    let params = params(divisions);
+    let info = SolverInfo::builder()
+        .k(K)
+        .q_dot(q_dot)
+        .del(params.del())
+        .build();
+
+    let coefficients = cylindrical::coefficients(
+        cylinder,
+        &conditions.make_boundaries(),
+        &info,
+        divisions,
+        Sweep::default(),
+    );
+    solver::solve(&coefficients, params, &L2Norm, &[], ())
+}
+
+fn solve(
+    cylinder: &Cylinder<f64>,
+    conditions: Conditions<f64>,
+    q_dot: f64,
+    divisions: usize,
+) -> Matrix<f64> {
+    let result = simulate(cylinder, conditions, q_dot, divisions);
+
+    assert_eq!(result.shape(), cylinder.shape(divisions));
+    result.temperatures()
+}
+
+fn assert_profile(
+    cylinder: &Cylinder<f64>,
+    temps: &Matrix<f64>,
+    tolerance: f64,
+    profile: impl Fn(f64) -> f64,
+) {
+    let divisions = temps.shape()[0];
+
+    for ((i, j, k), &temp) in temps.indexed_iter() {
+        let [r, _, _] = cylinder.position([i, j, k], divisions);
+        let expected = profile(r);
+        assert!(
+            (temp - expected).abs() < tolerance,
+            "{temp} against {expected} at {i} {j} {k}"
+        );
+    }
+}
+
+#[test]
+fn an_axisymmetric_rod_has_a_quadratic_profile() {
+    let cylinder = Cylinder {
+        inner_radius: 0.,
+        outer_radius: RADIUS,
+        length: 0.1,
+        sector: None,
+        axisymmetric: true,
+    };
+
+    let temps = solve(&cylinder, rod(), Q_DOT, 11);
+    assert_eq!(temps.shape(), [11, 1, 11]);
+    assert_profile(&cylinder, &temps, 1e-6, rod_temperature);
+}
+
+#[test]
+fn the_axis_of_a_full_rod_is_a_single_temperature() {
+    let cylinder = Cylinder {
+        inner_radius: 0.,
+        outer_radius: RADIUS,
+        length: 0.1,
+        sector: None,
+        axisymmetric: false,
+    };
+
+    let temps = solve(&cylinder, rod(), Q_DOT, 9);
+    assert_profile(&cylinder, &temps, 1e-6, rod_temperature);
+
+    let axis = temps[[0, 0, 4]];
+    assert!(temps
+        .slice(ndarray::s![0, .., 4])
+        .iter()
+        .all(|&temp| temp == axis));
+}
+
+#[test]
+fn a_quarter_rod_with_insulated_cuts_matches_the_full_one() {
+    let cylinder = Cylinder {
+        inner_radius: 0.,
+        outer_radius: RADIUS,
+        length: 0.1,
+        sector: Some(std::f64::consts::FRAC_PI_2),
+        axisymmetric: false,
+    };
+
+    let temps = solve(&cylinder, rod(), Q_DOT, 9);
+    assert_profile(&cylinder, &temps, 1e-6, rod_temperature);
+}
+
+#[test]
+fn a_pipe_wall_has_a_logarithmic_profile() {
+    let (inner, outer) = (0.02, 0.05);
+    let cylinder = Cylinder {
+        inner_radius: inner,
+        outer_radius: outer,
+        length: 0.1,
+        sector: None,
+        axisymmetric: false,
+    };
+    let conditions = Conditions {
+        left_boundary: temperature(400.),
+        right_boundary: temperature(300.),
+        ..rod()
+    };
+
+    let pipe = |r: f64| 400. - 100. * (r / inner).ln() / (outer / inner).ln();
+
+    let temps = solve(&cylinder, conditions, 0., 17);
+    assert_profile(&cylinder, &temps, 0.1, pipe);
+
+    // the error falls with the square of the spacing
+    let axisymmetric = Cylinder {
+        axisymmetric: true,
+        ..cylinder
+    };
+    let error = |divisions: usize| {
+        let temps = solve(&axisymmetric, conditions, 0., divisions);
+        (0..divisions)
+            .map(|i| {
+                let [r, _, _] = axisymmetric.position([i, 0, 0], divisions);
+                (temps[[i, 0, 0]] - pipe(r)).abs()
+            })
+            .fold(0., f64::max)
+    };
+    let ratio = error(9) / error(17);
+    assert!(ratio > 3.5 && ratio < 4.5, "{ratio}");
+}
+
+#[test]
+fn a_sector_between_two_temperatures_varies_with_angle() {
+    let sector = 2.;
+    let cylinder = Cylinder {
+        inner_radius: 0.02,
+        outer_radius: RADIUS,
+        length: 0.1,
+        sector: Some(sector),
+        axisymmetric: false,
+    };
+    let conditions = Conditions {
+        right_boundary: adiabatic(),
+        bot_boundary: temperature(300.),
+        top_boundary: temperature(400.),
+        ..rod()
+    };
+
+    let temps = solve(&cylinder, conditions, 0., 9);
+
+    for ((i, j, k), &temp) in temps.indexed_iter() {
+        let [_, theta, _] = cylinder.position([i, j, k], 9);
+        let expected = 300. + 100. * theta / sector;
+        assert!((temp - expected).abs() < 1e-6, "{temp} against {expected}");
+    }
+}
+
+#[test]
+fn the_report_of_a_rod_balances_its_heat() {
+    let cylinder = Cylinder {
+        inner_radius: 0.,
+        outer_radius: RADIUS,
+        length: 0.1,
+        sector: Some(std::f64::consts::FRAC_PI_2),
+        axisymmetric: false,
+    };
+    let conditions = rod();
+
+    let results = SaveFile {
+        conditions,
+        simulation: simulate(&cylinder, conditions, Q_DOT, 9),
+        solver_params: params(9),
+        precision: Precision::Double,
+        thermal_conductivity: Some(K),
+        energy_generation: Some(Q_DOT),
+        samples: None,
+        heat_flux: None,
+        geometry: None,
+        cylinder: Some(cylinder),
+        orthotropic: None,
+        mirrored: None,
+    };
+    let report = Report::new(&results);
+
+    // a quarter of the rod
+    let generated: f64 = report.heat_generated.unwrap();
+    let volume = std::f64::consts::FRAC_PI_4 * RADIUS * RADIUS * 0.1;
+    assert!((generated - Q_DOT * volume).abs() < 1e-9, "{generated}");
+
+    // no inner surface on the axis
+    let faces: Vec<_> = report.faces.iter().map(|face| face.face).collect();
+    assert_eq!(faces, ["outer", "θ end", "θ start", "z end", "z start"]);
+
+    let outer: f64 = report.faces[0].heat_flow.unwrap();
+    assert!((outer - generated).abs() / generated < 1e-6, "{outer}");
+    assert!(report.imbalance.unwrap().abs() / generated < 1e-6);
+
+    let [r, _, _] = report.max.position;
+    assert_eq!(r, 0.);
+    assert!((report.max.temperature - rod_temperature(0.)).abs() < 1e-6);
+}
@@ -0,0 +1,139 @@
+//! Solves a wall in 1D and a cross-section in 2D, against the analytic profile of the wall and
+//! a 3D solve of the same cube with its front and back insulated
+
+mod common;
+
+use common::{adiabatic, info, params, DIVISIONS, K, LEN, Q_DOT};
+use finite_difference::conditions::{Condition, Convection, HeatFlux, Temperature};
+use finite_difference::dimensions::{self, Dimensions};
+use finite_difference::dump::{SaveFile, SimulationResult};
+use finite_difference::error::L2Norm;
+use finite_difference::input::Conditions;
+use finite_difference::prelude::Precision;
+use finite_difference::report::Report;
+use finite_difference::solver;
+use finite_difference::stencil::{Coefficients, Sweep};
+
+/// Held at 300 K on the left and cooled on the right, heated through the bottom and
+/// insulated at the top, front and back
+fn conditions() -> Conditions<f64> {
+    Conditions {
+        right_boundary: Condition::Convection(Convection {
+            h: 40.,
+            t_inf: 290.,
+        }),
+        left_boundary: Condition::Temperature(Temperature { temperature: 300. }),
+        top_boundary: adiabatic(),
+        bot_boundary: Condition::HeatFlux(HeatFlux { heat_flux: 2000. }),
+        front_boundary: adiabatic(),
+        back_boundary: adiabatic(),
+    }
+}
+
+fn solve(dimensions: Dimensions, conditions: Conditions<f64>) -> SimulationResult<f64> {
+    let coefficients = dimensions::coefficients(
+        dimensions,
+        &conditions.make_boundaries(),
+        &info(Q_DOT),
+        DIVISIONS,
+        Sweep::default(),
+    );
+    solver::solve(&coefficients, params(), &L2Norm, &[], ())
+}
+
+#[test]
+fn a_wall_has_a_quadratic_profile() {
+    let result = solve(Dimensions::One, conditions());
+    assert_eq!(result.shape(), [DIVISIONS, 1, 1]);
+
+    // the slope at the left that makes the conduction out of the right equal the convection
+    let (h, held) = (40., 300. - 290.);
+    let c = (Q_DOT * LEN - h * (held - Q_DOT * LEN * LEN / (2. * K))) / (K + h * LEN);
+    let wall = |x: f64| 300. + c * x - Q_DOT * x * x / (2. * K);
+
+    for ((x, _, _), &temp) in result.temperatures().indexed_iter() {
+        let expected = wall(params().del() * x as f64);
+        assert!((temp - expected).abs() < 1e-6, "{temp} against {expected}");
+    }
+}
+
+#[test]
+fn a_cross_section_matches_the_cube_with_insulated_ends() {
+    let section = solve(Dimensions::Two, conditions());
+    assert_eq!(section.shape(), [DIVISIONS, DIVISIONS, 1]);
+
+    let coefficients = Coefficients::new(
+        &conditions().make_boundaries(),
+        &info(Q_DOT),
+        DIVISIONS,
+        Sweep::default(),
+    )
+    .unwrap();
+    let cube = solver::solve(&coefficients, params(), &L2Norm, &[], ());
+
+    let comparison = dimensions::compare(&section.temperatures(), &cube.temperatures()).unwrap();
+    assert!(comparison.max < 1e-6, "{comparison:?}");
+
+    // the conditions of the missing faces are not used
+    let convective = Conditions {
+        front_boundary: Condition::Convection(Convection {
+            h: 100.,
+            t_inf: 200.,
+        }),
+        ..conditions()
+    };
+    let ignored = solve(Dimensions::Two, convective);
+    assert_eq!(
+        ignored.temperatures(),
+        section.temperatures(),
+        "front and back are not part of a cross-section"
+    );
+
+    // a wall is not part of the cross-section's cube
+    let wall = solve(Dimensions::One, conditions()).temperatures();
+    assert!(dimensions::compare(&section.temperatures(), &wall).is_none());
+}
+
+#[test]
+fn the_report_of_a_cross_section_balances_its_heat() {
+    let results = SaveFile {
+        conditions: conditions(),
+        simulation: solve(Dimensions::Two, conditions()),
+        solver_params: params(),
+        precision: Precision::Double,
+        thermal_conductivity: Some(K),
+        energy_generation: Some(Q_DOT),
+        samples: None,
+        heat_flux: None,
+        geometry: None,
+        cylinder: None,
+        orthotropic: None,
+        mirrored: None,
+    };
+    let report = Report::new(&results);
+    assert_eq!(report.dimensions, Dimensions::Two);
+
+    // the single layer stands for the whole depth of the cube
+    let generated: f64 = report.heat_generated.unwrap();
+    assert!(
+        (generated - Q_DOT * LEN.powi(3)).abs() < 1e-9,
+        "{generated}"
+    );
+
+    let faces: Vec<_> = report.faces.iter().map(|face| face.face).collect();
+    assert_eq!(faces, ["right", "left", "top", "bot"]);
+
+    let bot: f64 = report.faces[3].heat_flow.unwrap();
+    assert!((bot + 2000. * LEN * LEN).abs() < 1e-9, "{bot}");
+
+    let imbalance = report.imbalance.unwrap() / generated;
+    assert!(imbalance.abs() < 1e-6, "{imbalance}");
+}
+
+#[test]
+fn dimensions_are_written_as_a_number() {
+    let two: Dimensions = serde_json::from_str("2").unwrap();
+    assert_eq!(two, Dimensions::Two);
+    assert_eq!(serde_json::to_string(&Dimensions::One).unwrap(), "1");
+    assert!(serde_json::from_str::<Dimensions>("4").is_err());
+}
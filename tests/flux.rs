@@ -0,0 +1,203 @@
+//! Checks the gradient and heat flux against fields with known derivatives
+
+use finite_difference::anisotropy::Orthotropic;
+use finite_difference::conditions::{Condition, HeatFlux, Temperature};
+use finite_difference::dump::Results;
+use finite_difference::error::InfinityNorm;
+use finite_difference::flux::{self, anisotropic_heat_flux, gradient, heat_flux, FluxField};
+use finite_difference::prelude::{Matrix, SolverInfo};
+use finite_difference::setup::SetupConditions;
+use finite_difference::{solver, SolverParams};
+
+const DEL: f64 = 0.05;
+
+fn close(left: f64, right: f64) -> bool {
+    (left - right).abs() < 1e-6
+}
+
+/// Results of 3 nodes along each side `DEL` apart, rising 1 K per node along x at step 0 and
+/// uniform at step 10, with the fields of `extra`
+fn saved(extra: serde_json::Value) -> Results<f64> {
+    let rising: Vec<f64> = (0..27).map(|node| 300. + (node / 9) as f64).collect();
+    let uniform = [300.; 27];
+    let insulated = serde_json::json!({"type": "HeatFlux", "heat_flux": 0.0});
+
+    let mut results = serde_json::json!({
+        "conditions": {
+            "right_boundary": insulated, "left_boundary": insulated,
+            "top_boundary": insulated, "bot_boundary": insulated,
+            "front_boundary": insulated, "back_boundary": insulated
+        },
+        "simulation": {
+            "step_data": [{"step": 0, "data": rising}, {"step": 10, "data": uniform}],
+            "error_decay": {"error_type": "L2Norm", "data": []},
+            "size": 3,
+            "num_steps": 10
+        },
+        "solver_params": {"len": 2. * DEL, "divisions": 3, "error_epsilon": 1e-7,
+                          "data_steps": 10, "error_steps": 10},
+        "thermal_conductivity": 15.0
+    });
+    results
+        .as_object_mut()
+        .unwrap()
+        .extend(extra.as_object().unwrap().clone());
+
+    serde_json::from_value(results).unwrap()
+}
+
+#[test]
+fn exact_for_quadratic_fields() {
+    // the three point differences are exact for quadratics, on every face, edge and corner
+    let field = |x: f64, y: f64, z: f64| 300. + 20. * x * x - 5. * y + 8. * x * z + 3. * z * z;
+    let derivatives = |x: f64, _: f64, z: f64| [40. * x + 8. * z, -5., 8. * x + 6. * z];
+
+    let temps = Matrix::from_shape_fn((6, 6, 6), |(i, j, k)| {
+        field(i as f64 * DEL, j as f64 * DEL, k as f64 * DEL)
+    });
+
+    let gradient = gradient(&temps, DEL);
+
+    for ((i, j, k), _) in temps.indexed_iter() {
+        let exact = derivatives(i as f64 * DEL, j as f64 * DEL, k as f64 * DEL);
+
+        for axis in 0..3 {
+            assert!(
+                close(gradient[axis][[i, j, k]], exact[axis]),
+                "d/d{axis} at ({i}, {j}, {k})"
+            );
+        }
+    }
+}
+
+#[test]
+fn flux_opposes_the_gradient() {
+    let k = 15.;
+    let temps = Matrix::from_shape_fn((4, 4, 4), |(i, j, k)| (i * 3 + j * 2 + k) as f64);
+
+    let flux = heat_flux(&temps, DEL, k);
+    let field = FluxField::new(&temps, DEL, k);
+
+    let expected = [3., 2., 1.].map(|slope: f64| -k * slope / DEL);
+    let magnitude = (expected.iter().map(|q| q * q).sum::<f64>()).sqrt();
+
+    for axis in 0..3 {
+        assert!(flux[axis].iter().all(|&q| close(q, expected[axis])));
+        assert!(field.heat_flux[axis]
+            .iter()
+            .all(|&q| close(q, expected[axis])));
+    }
+
+    assert!(field.magnitude.iter().all(|&q| close(q, magnitude)));
+}
+
+#[test]
+fn turned_conductivity_conducts_across_the_gradient() {
+    // a laminate turned by 30 degrees about z, with a gradient along x only
+    let orthotropic = Orthotropic {
+        k: [20., 0.3, 20.],
+        rotation: Some([0., 0., 30.]),
+    };
+    let k = orthotropic.tensor();
+    let temps = Matrix::from_shape_fn((4, 4, 4), |(i, _, _)| i as f64);
+
+    let flux = anisotropic_heat_flux(&temps, DEL, |_| k);
+    let field = FluxField::with_tensor(&temps, DEL, |_| k);
+
+    let (cos, sin) = (30_f64.to_radians().cos(), 30_f64.to_radians().sin());
+    let expected = [
+        -(20. * cos * cos + 0.3 * sin * sin) / DEL,
+        -(20. - 0.3) * cos * sin / DEL,
+        0.,
+    ];
+
+    for axis in 0..3 {
+        assert!(flux[axis].iter().all(|&q| close(q, expected[axis])));
+        assert!(field.heat_flux[axis]
+            .iter()
+            .all(|&q| close(q, expected[axis])));
+    }
+}
+
+#[test]
+fn flux_of_a_saved_step() {
+    // the last step takes the flux saved with it, uniform temperatures or not
+    let field = FluxField {
+        gradient: [vec![0.; 27], vec![0.; 27], vec![0.; 27]],
+        heat_flux: [vec![1.; 27], vec![2.; 27], vec![3.; 27]],
+        magnitude: vec![0.; 27],
+    };
+    let results = saved(serde_json::json!({ "heat_flux": field }));
+    let flux = flux::of_step(&results, &results.simulation.step_data[1]);
+    assert!(flux[1].iter().all(|&q| q == 2.));
+
+    // earlier steps conduct with the saved tensor, across the gradient of a turned laminate
+    let orthotropic = Orthotropic {
+        k: [20., 0.3, 20.],
+        rotation: Some([0., 0., 30.]),
+    };
+    let results = saved(serde_json::json!({ "orthotropic": orthotropic }));
+    let flux = flux::of_step(&results, &results.simulation.step_data[0]);
+    let k = orthotropic.tensor();
+    assert!(flux[1].iter().all(|&q| close(q, -k[1][0] / DEL)));
+    assert!(flux[1][[1, 1, 1]].abs() > 50.);
+
+    // along r in a cylinder, at the radial spacing rather than the cube's
+    let cylinder = serde_json::json!({"cylinder": {"outer_radius": 0.4, "length": 1.0}});
+    let results = saved(cylinder);
+    let flux = flux::of_step(&results, &results.simulation.step_data[0]);
+    assert!(flux[0].iter().all(|&q| close(q, -15. / 0.2)));
+    assert!(flux[1].iter().all(|&q| q == 0.));
+}
+
+#[test]
+fn generated_heat_leaves_through_the_cooled_face() {
+    // a slab insulated everywhere but the bottom, all the heat generated in it has to be
+    // conducted out through the bottom
+    let (k, q_dot, divisions) = (20., 1e5, 9);
+
+    let params = SolverParams {
+        len: 0.1,
+        divisions,
+        error_epsilon: 1e-11,
+        data_steps: usize::MAX,
+        error_steps: usize::MAX,
+        sweep: Default::default(),
+        threads: None,
+    };
+
+    let adiabatic = Condition::HeatFlux(HeatFlux { heat_flux: 0. });
+    let conditions = SetupConditions {
+        right_boundary: adiabatic,
+        left_boundary: adiabatic,
+        top_boundary: adiabatic,
+        bot_boundary: Condition::Temperature(Temperature { temperature: 300. }),
+        front_boundary: adiabatic,
+        back_boundary: adiabatic,
+    };
+
+    let s = SolverInfo::builder()
+        .k(k)
+        .q_dot(q_dot)
+        .del(params.del())
+        .build();
+
+    let result = solver::solver(
+        s,
+        params,
+        conditions.make_boundaries(),
+        InfinityNorm,
+        &[],
+        (),
+    )
+    .unwrap();
+    let flux = heat_flux(&result.temperatures(), params.del(), k);
+
+    for x in 0..divisions {
+        for z in 0..divisions {
+            // downwards through the bottom face, nothing through the insulated top
+            assert!(close(flux[1][[x, 0, z]], -q_dot * params.len), "bottom");
+            assert!(flux[1][[x, divisions - 1, z]].abs() < 1e-4, "top");
+        }
+    }
+}
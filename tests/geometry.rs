@@ -0,0 +1,203 @@
+//! Checks masked geometries against the full cube, a bar with a known profile and the heat
+//! balance of an L bracket
+
+mod common;
+
+use common::{adiabatic, convection, info, params, DIVISIONS, K, LEN};
+use finite_difference::conditions::{Condition, HeatFlux, Temperature};
+use finite_difference::dump::{Results, SaveFile};
+use finite_difference::error::L2Norm;
+use finite_difference::geometry::{Block, Error, Geometry, Mask};
+use finite_difference::input::Conditions;
+use finite_difference::prelude::{Matrix, Precision};
+use finite_difference::report::Report;
+use finite_difference::solver;
+use finite_difference::stencil::{Coefficients, Sweep};
+
+use std::collections::BTreeMap;
+
+fn temperature(temperature: f64) -> Condition<f64> {
+    Condition::Temperature(Temperature { temperature })
+}
+
+fn solve(coefficients: &Coefficients<f64>) -> Matrix<f64> {
+    solver::solve(coefficients, params(), &L2Norm, &[], ()).temperatures()
+}
+
+#[test]
+fn a_solid_mask_is_the_cube() {
+    let conditions = Conditions {
+        right_boundary: convection(20.),
+        left_boundary: Condition::HeatFlux(HeatFlux { heat_flux: 500. }),
+        top_boundary: convection(60.),
+        bot_boundary: temperature(300.),
+        front_boundary: convection(5.),
+        back_boundary: convection(40.),
+    }
+    .make_boundaries();
+    let s = info(4e4);
+
+    let cube = Coefficients::new(&conditions, &s, DIVISIONS, Sweep::default()).unwrap();
+    let mask = Mask::solid(DIVISIONS);
+    let masked = Coefficients::masked(&conditions, &s, &mask, Sweep::default()).unwrap();
+
+    // nodes with the same cells around them share a stencil, one per wall, edge and corner
+    assert_eq!(masked.nodes.as_ref().unwrap().stencils.len(), 27);
+
+    let difference = (&solve(&cube) - &solve(&masked))
+        .iter()
+        .fold(0., |largest: f64, d| largest.max(d.abs()));
+    assert!(difference < 1e-8, "{difference}");
+}
+
+#[test]
+fn an_insulated_cut_leaves_a_linear_bar() {
+    // left at 300 K and right at 400 K with everything else insulated, the top half of the
+    // cube removed
+    let conditions = Conditions {
+        right_boundary: temperature(400.),
+        left_boundary: temperature(300.),
+        top_boundary: adiabatic(),
+        bot_boundary: adiabatic(),
+        front_boundary: adiabatic(),
+        back_boundary: adiabatic(),
+    };
+
+    let geometry = Geometry {
+        voids: vec![Block {
+            min: [-1., 0.04, -1.],
+            max: [1., 1., 1.],
+            surface: None,
+        }],
+        ..Geometry::default()
+    };
+
+    let mask = geometry.mask(DIVISIONS, params().del()).unwrap();
+    let coefficients = Coefficients::masked(
+        &conditions.make_boundaries(),
+        &info(0.),
+        &mask,
+        Sweep::default(),
+    )
+    .unwrap();
+    let temps = solve(&coefficients);
+
+    for ((x, y, _), &temp) in temps.indexed_iter() {
+        if y > 4 {
+            assert!(temp.is_nan(), "node above the cut at {y}");
+        } else {
+            let expected = 300. + 100. * x as f64 / (DIVISIONS - 1) as f64;
+            assert!((temp - expected).abs() < 1e-6, "{temp} against {expected}");
+        }
+    }
+}
+
+#[test]
+fn voids_refer_to_surfaces_by_name() {
+    let mut surfaces = BTreeMap::new();
+    surfaces.insert("cooled".to_string(), convection(25.));
+
+    let block = |surface: &str| Block {
+        min: [0.02, 0.02, 0.02],
+        max: [0.06, 0.06, 0.06],
+        surface: Some(surface.to_string()),
+    };
+
+    let geometry = Geometry {
+        surfaces,
+        voids: vec![block("cooled")],
+        ..Geometry::default()
+    };
+    let mask = geometry.mask(DIVISIONS, params().del()).unwrap();
+
+    // the adiabatic default and the named surface
+    assert_eq!(mask.surfaces.len(), 2);
+    // a hole of 4^3 cells in the middle leaves the 3^3 nodes inside it without material
+    let inactive = (0..DIVISIONS)
+        .flat_map(|x| (0..DIVISIONS).flat_map(move |y| (0..DIVISIONS).map(move |z| [x, y, z])))
+        .filter(|&node| !mask.is_active(node))
+        .count();
+    assert_eq!(inactive, 27);
+
+    let geometry = Geometry {
+        voids: vec![block("heated")],
+        ..geometry
+    };
+    assert!(matches!(
+        geometry.mask(DIVISIONS, params().del()),
+        Err(Error::UnknownSurface(name)) if name == "heated"
+    ));
+}
+
+#[test]
+fn an_l_bracket_balances_its_heat() {
+    let conditions = Conditions {
+        right_boundary: convection(10.),
+        left_boundary: temperature(300.),
+        top_boundary: convection(10.),
+        bot_boundary: adiabatic(),
+        front_boundary: adiabatic(),
+        back_boundary: adiabatic(),
+    };
+
+    let mut surfaces = BTreeMap::new();
+    surfaces.insert("inner".to_string(), convection(40.));
+
+    let geometry = Geometry {
+        surfaces,
+        voids: vec![Block {
+            min: [0.03, 0.03, -1.],
+            max: [1., 1., 1.],
+            surface: Some("inner".to_string()),
+        }],
+        ..Geometry::default()
+    };
+
+    let q_dot = 2e5;
+    let mask = geometry.mask(DIVISIONS, params().del()).unwrap();
+    let coefficients = Coefficients::masked(
+        &conditions.make_boundaries(),
+        &info(q_dot),
+        &mask,
+        Sweep::default(),
+    )
+    .unwrap();
+    let simulation = solver::solve(&coefficients, params(), &L2Norm, &[], ());
+
+    let results = SaveFile {
+        conditions,
+        simulation,
+        solver_params: params(),
+        precision: Precision::Double,
+        thermal_conductivity: Some(K),
+        energy_generation: Some(q_dot),
+        samples: None,
+        heat_flux: None,
+        geometry: Some(geometry),
+        cylinder: None,
+        orthotropic: None,
+        mirrored: None,
+    };
+
+    // the nodes cut away are saved as `null` and read back as NaN
+    let json = serde_json::to_string(&results).unwrap();
+    assert!(json.contains("null"));
+    let results: Results<f64> = serde_json::from_str(&json).unwrap();
+
+    let report = Report::new(&results);
+
+    // three cells of the first eight along x and y stay, through the full depth
+    let solid = LEN * LEN * LEN * (1. - (5. * 5.) / (8. * 8.));
+    assert!((report.heat_generated.unwrap() - q_dot * solid).abs() < 1e-6);
+
+    assert_eq!(report.surfaces.len(), 1);
+    assert!(report.surfaces[0].heat_flow.unwrap() > 0.);
+
+    // the constant temperature face is differentiated at second order, so the balance is
+    // not exact on a coarse grid
+    let imbalance = report.imbalance.unwrap() / report.heat_generated.unwrap();
+    assert!(imbalance.abs() < 0.05, "{imbalance}");
+
+    assert!(report.min.temperature >= 300.);
+    assert!(report.max.temperature.is_finite());
+}
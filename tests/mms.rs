@@ -0,0 +1,77 @@
+//! Checks the order of accuracy of every stencil with manufactured solutions
+//!
+//! each configuration gives the faces a different mix of conditions, so between them every
+//! wall, edge and corner calculator is run with each kind of condition on its faces
+
+use finite_difference::mms::{Face, Faces, Manufactured, Part};
+use finite_difference::setup::SetupConditions;
+
+const LEN: f64 = 0.1;
+
+const GRIDS: [usize; 3] = [9, 17, 25];
+
+/// smooth field with no symmetry and no polynomial part the stencils reproduce exactly
+fn field(x: f64, y: f64, z: f64) -> f64 {
+    let (x, y, z) = (x / LEN, y / LEN, z / LEN);
+
+    300. + 40. * (3. * x + 0.4).sin() * (2. * y - 0.3).cos() * (0.8 * z).exp() + 15. * x * y * z
+}
+
+fn check(faces: Faces<f64>) {
+    let problem = Manufactured {
+        field,
+        k: 20.,
+        len: LEN,
+        faces,
+        error_epsilon: 1e-11,
+    };
+
+    let convergence = problem.convergence(&GRIDS);
+
+    // the largest interior error sits next to the walls and only approaches second order
+    // from below on these coarse grids, a stencil with an O(1) local error drops to first
+    for part in Part::ALL {
+        if let Some(order) = convergence.min_order(part) {
+            assert!(
+                order > 1.6,
+                "{part} nodes converge at order {order:.3}, expected 2\n{convergence}"
+            );
+        }
+    }
+}
+
+#[test]
+fn convection() {
+    check(SetupConditions {
+        right_boundary: Face::Convection { h: 50. },
+        left_boundary: Face::Convection { h: 100. },
+        top_boundary: Face::Convection { h: 200. },
+        bot_boundary: Face::Convection { h: 400. },
+        front_boundary: Face::Convection { h: 800. },
+        back_boundary: Face::Convection { h: 1600. },
+    });
+}
+
+#[test]
+fn temperature_and_heat_flux() {
+    check(SetupConditions {
+        right_boundary: Face::Temperature,
+        left_boundary: Face::HeatFlux,
+        top_boundary: Face::HeatFlux,
+        bot_boundary: Face::Temperature,
+        front_boundary: Face::Temperature,
+        back_boundary: Face::HeatFlux,
+    });
+}
+
+#[test]
+fn mixed() {
+    check(SetupConditions {
+        right_boundary: Face::HeatFlux,
+        left_boundary: Face::Temperature,
+        top_boundary: Face::Convection { h: 300. },
+        bot_boundary: Face::HeatFlux,
+        front_boundary: Face::Convection { h: 150. },
+        back_boundary: Face::Temperature,
+    });
+}
@@ -0,0 +1,140 @@
+//! Checks observers see every step and can stop a solve, from the solver's thread or another
+
+use finite_difference::conditions::{Condition, Convection, Temperature};
+use finite_difference::dump::SimulationResult;
+use finite_difference::error::L2Norm;
+use finite_difference::observer::{self, Cancel, Control, Observer, Progress};
+use finite_difference::prelude::SolverInfo;
+use finite_difference::setup::SetupConditions;
+use finite_difference::{solver, SolverParams};
+
+use std::time::Duration;
+
+fn solve(error_epsilon: f64, observer: impl Observer<f64>) -> SimulationResult<f64> {
+    let params = SolverParams {
+        len: 0.1,
+        divisions: 7,
+        error_epsilon,
+        data_steps: usize::MAX,
+        error_steps: 10,
+        sweep: Default::default(),
+        threads: None,
+    };
+
+    let convection = Condition::Convection(Convection {
+        h: 20.,
+        t_inf: 290.,
+    });
+    let conditions = SetupConditions {
+        right_boundary: convection,
+        left_boundary: convection,
+        top_boundary: Condition::Temperature(Temperature { temperature: 350. }),
+        bot_boundary: Condition::Temperature(Temperature { temperature: 300. }),
+        front_boundary: convection,
+        back_boundary: convection,
+    };
+
+    let s = SolverInfo::builder()
+        .k(15.)
+        .q_dot(0.)
+        .del(params.del())
+        .build();
+
+    solver::solver(
+        s,
+        params,
+        conditions.make_boundaries(),
+        L2Norm,
+        &[],
+        observer,
+    )
+    .unwrap()
+}
+
+#[test]
+fn observers_see_every_step() {
+    let mut steps = Vec::new();
+    let mut errors = Vec::new();
+
+    let result = solve(
+        1e-6,
+        observer::from_fn(|progress: Progress<f64>| {
+            steps.push(progress.step);
+            errors.push(progress.error);
+            assert_eq!(progress.temperatures.shape(), [7, 7, 7]);
+            Control::Continue
+        }),
+    );
+
+    assert!(!result.stopped);
+    assert_eq!(steps, (0..=result.num_steps).collect::<Vec<_>>());
+    assert_eq!(errors.last().copied(), result.final_error);
+}
+
+#[test]
+fn every_thins_out_the_calls() {
+    let mut steps = Vec::new();
+
+    let result = solve(
+        1e-6,
+        observer::every(
+            25,
+            observer::from_fn(|progress: Progress<f64>| {
+                steps.push(progress.step);
+                Control::Continue
+            }),
+        ),
+    );
+
+    let expected: Vec<usize> = (0..=result.num_steps).step_by(25).collect();
+    assert_eq!(steps, expected);
+}
+
+#[test]
+fn an_observer_can_stop_the_solve() {
+    let result = solve(
+        1e-6,
+        observer::from_fn(|progress: Progress<f64>| {
+            if progress.step == 40 {
+                Control::Stop
+            } else {
+                Control::Continue
+            }
+        }),
+    );
+
+    assert!(result.stopped);
+    assert_eq!(result.num_steps, 40);
+    assert!(result.final_error.unwrap() > 1e-6);
+
+    // the temperatures reached are kept
+    let temps = result.temperatures();
+    assert!(temps.iter().all(|t| t.is_finite()));
+}
+
+#[test]
+fn cancelled_from_another_thread() {
+    let cancel = Cancel::new();
+
+    let canceller = {
+        let cancel = cancel.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            cancel.cancel();
+        })
+    };
+
+    // an epsilon no solve reaches, only the token can end it
+    let mut seen = 0;
+    let counter = observer::from_fn(|_: Progress<f64>| {
+        seen += 1;
+        Control::Continue
+    });
+    let result = solve(0., (counter, cancel.clone()));
+
+    canceller.join().unwrap();
+
+    assert!(cancel.is_cancelled());
+    assert!(result.stopped);
+    assert_eq!(seen, result.num_steps + 1);
+}
@@ -0,0 +1,115 @@
+//! Checks sampling along lines and planes, and probes tracked while solving
+
+use finite_difference::conditions::{Condition, HeatFlux, Temperature};
+use finite_difference::error::InfinityNorm;
+use finite_difference::prelude::{Matrix, SolverInfo};
+use finite_difference::probe::{line, plane, Axis, Probe};
+use finite_difference::setup::SetupConditions;
+use finite_difference::{solver, SolverParams};
+
+const DEL: f64 = 0.1;
+
+fn field(x: f64, y: f64, z: f64) -> f64 {
+    300. + 10. * x - 4. * y + 2. * z + 30. * x * y
+}
+
+fn temps() -> Matrix<f64> {
+    Matrix::from_shape_fn((5, 5, 5), |(i, j, k)| {
+        field(i as f64 * DEL, j as f64 * DEL, k as f64 * DEL)
+    })
+}
+
+fn close(left: f64, right: f64) -> bool {
+    (left - right).abs() < 1e-9
+}
+
+#[test]
+fn line_includes_both_ends() {
+    let samples = line(&temps(), DEL, [0., 0., 0.], [0.4, 0.2, 0.1], 9).unwrap();
+
+    assert_eq!(samples.len(), 9);
+    assert_eq!(samples[0].point, [0., 0., 0.]);
+    assert!(close(samples[8].point[0], 0.4));
+
+    for sample in &samples {
+        let [x, y, z] = sample.point;
+        assert!(close(sample.temperature, field(x, y, z)));
+    }
+
+    assert!(line(&temps(), DEL, [0., 0., 0.], [0.5, 0., 0.], 3).is_none());
+}
+
+#[test]
+fn plane_covers_the_cross_section() {
+    let section = plane(&temps(), DEL, Axis::Y, 0.25, [3, 5]).unwrap();
+
+    assert_eq!(section.u, [0., 0.2, 0.4]);
+    assert_eq!(section.v.len(), 5);
+
+    for (i, &x) in section.u.iter().enumerate() {
+        for (j, &z) in section.v.iter().enumerate() {
+            assert!(close(section.temperatures[i][j], field(x, 0.25, z)));
+        }
+    }
+
+    assert!(plane(&temps(), DEL, Axis::X, 0.41, [2, 2]).is_none());
+}
+
+#[test]
+fn probes_are_tracked_every_step() {
+    let divisions = 9;
+    let params = SolverParams {
+        len: 0.1,
+        divisions,
+        error_epsilon: 1e-6,
+        data_steps: usize::MAX,
+        error_steps: 1,
+        sweep: Default::default(),
+        threads: None,
+    };
+
+    let adiabatic = Condition::HeatFlux(HeatFlux { heat_flux: 0. });
+    let conditions = SetupConditions {
+        right_boundary: adiabatic,
+        left_boundary: adiabatic,
+        top_boundary: Condition::Temperature(Temperature { temperature: 400. }),
+        bot_boundary: Condition::Temperature(Temperature { temperature: 300. }),
+        front_boundary: adiabatic,
+        back_boundary: adiabatic,
+    };
+
+    let s = SolverInfo::builder()
+        .k(10.)
+        .q_dot(0.)
+        .del(params.del())
+        .build();
+
+    let shape = [divisions; 3];
+    let probes = [
+        Probe::new([0.05, 0.05, 0.05], params.del(), shape).unwrap(),
+        Probe::new([0.02, 0.09, 0.1], params.del(), shape).unwrap(),
+    ];
+
+    let result = solver::solver(
+        s,
+        params,
+        conditions.make_boundaries(),
+        InfinityNorm,
+        &probes,
+        (),
+    )
+    .unwrap();
+    let temps = result.temperatures();
+
+    assert_eq!(result.probes.len(), 2);
+
+    for (probe, history) in probes.iter().zip(&result.probes) {
+        assert_eq!(history.point, probe.point);
+        assert_eq!(history.temperatures.len(), result.num_steps + 1);
+        assert_eq!(*history.temperatures.last().unwrap(), probe.sample(&temps));
+    }
+
+    // the linear steady state between the two fixed temperatures
+    let last: f64 = *result.probes[0].temperatures.last().unwrap();
+    assert!((last - 350.).abs() < 0.1, "{last}");
+}
@@ -0,0 +1,174 @@
+//! Checks slices are drawn the right way up, in the right colours, in both formats
+
+use finite_difference::cylindrical::Cylinder;
+use finite_difference::flux::heat_flux;
+use finite_difference::prelude::Matrix;
+use finite_difference::probe::Axis;
+use finite_difference::render::{Colormap, Coordinates, Figure, Style};
+
+const DEL: f64 = 0.01;
+const K: f64 = 15.;
+
+/// temperature rising along y only, 300 K at the bottom to 310 K at the top
+fn field() -> Matrix<f64> {
+    Matrix::from_shape_fn((6, 11, 8), |(_, j, _)| 300. + j as f64)
+}
+
+/// colour at the centre of node `(i, j)` of the slice
+fn node_color(figure: &Figure, i: usize, j: usize, nodes: [usize; 2]) -> [u8; 3] {
+    let [x, y, width, height] = figure.plot();
+    let cell = [width / nodes[0] as f64, height / nodes[1] as f64];
+
+    let px = x + (i as f64 + 0.5) * cell[0];
+    // the second in plane axis points up the image
+    let py = y + height - (j as f64 + 0.5) * cell[1];
+
+    figure.rasterise().pixel(px as usize, py as usize)
+}
+
+#[test]
+fn colour_maps_are_clamped() {
+    for map in [
+        Colormap::Coolwarm,
+        Colormap::Viridis,
+        Colormap::Inferno,
+        Colormap::Greys,
+    ] {
+        assert_eq!(map.color(-1.), map.color(0.));
+        assert_eq!(map.color(2.), map.color(1.));
+    }
+
+    assert_eq!(Colormap::Greys.color(0.), [0, 0, 0]);
+    assert_eq!(Colormap::Greys.color(1.), [255, 255, 255]);
+    assert_eq!(Colormap::Greys.color(0.5), [128, 128, 128]);
+    assert_eq!("viridis".parse::<Colormap>(), Ok(Colormap::Viridis));
+}
+
+#[test]
+fn slices_are_drawn_the_right_way_up() {
+    let temps = field();
+    let style = Style::builder().colormap(Colormap::Greys).build();
+
+    // normal to x the slice is (y, z), y to the right
+    let figure = Figure::slice(&temps, Coordinates::Cube(DEL), None, Axis::X, 2, &style).unwrap();
+    assert_eq!(node_color(&figure, 0, 3, [11, 8]), [0, 0, 0]);
+    assert_eq!(node_color(&figure, 10, 3, [11, 8]), [255, 255, 255]);
+
+    // normal to z the slice is (x, y), y upwards
+    let figure = Figure::slice(&temps, Coordinates::Cube(DEL), None, Axis::Z, 7, &style).unwrap();
+    assert_eq!(node_color(&figure, 2, 0, [6, 11]), [0, 0, 0]);
+    assert_eq!(node_color(&figure, 2, 10, [6, 11]), [255, 255, 255]);
+
+    // a fixed range maps the whole slice to the top of the map
+    let style = Style::builder()
+        .colormap(Colormap::Greys)
+        .range([250., 260.])
+        .build();
+    let figure = Figure::slice(&temps, Coordinates::Cube(DEL), None, Axis::Z, 0, &style).unwrap();
+    assert_eq!(node_color(&figure, 3, 0, [6, 11]), [255, 255, 255]);
+
+    assert!(Figure::slice(&temps, Coordinates::Cube(DEL), None, Axis::Y, 11, &style).is_none());
+}
+
+#[test]
+fn png_and_svg_output() {
+    let temps = field();
+    let flux = heat_flux(&temps, DEL, K);
+    let style = Style::builder().isotherms(4).arrows(true).build();
+    let figure = Figure::slice(
+        &temps,
+        Coordinates::Cube(DEL),
+        Some(&flux),
+        Axis::Z,
+        3,
+        &style,
+    )
+    .unwrap();
+
+    let mut png = Vec::new();
+    figure.rasterise().write_png(&mut png).unwrap();
+
+    let decoder = png::Decoder::new(png.as_slice());
+    let reader = decoder.read_info().unwrap();
+    let info = reader.info();
+    assert_eq!(info.width as usize, figure.width());
+    assert_eq!(info.height as usize, figure.height());
+
+    let svg = figure.to_svg();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert!(svg.contains("z = 0.0300 m"));
+    assert!(svg.contains("[K]"));
+
+    // one horizontal isotherm segment per cell they cross, and arrows
+    let lines = svg.matches("<line").count();
+    assert!(lines > 4 * 5, "{lines} lines");
+}
+
+#[test]
+fn cylinder_slices_are_placed_by_r_theta_z() {
+    let temps = field();
+    let cylinder = Cylinder {
+        inner_radius: 0.1,
+        outer_radius: 0.6,
+        length: 1.4,
+        sector: Some(std::f64::consts::PI),
+        axisymmetric: false,
+    };
+    // spaced for 6 nodes along each side, 36° apart around the half circle
+    let coordinates = Coordinates::Cylinder(cylinder, 6);
+    let style = Style::default();
+
+    let title = |axis, index| {
+        let figure = Figure::slice(&temps, coordinates, None, axis, index, &style).unwrap();
+        figure.to_svg()
+    };
+
+    assert!(title(Axis::X, 2).contains("r = 0.3000 m"));
+    assert!(title(Axis::Y, 3).contains("θ = 108.0°"));
+    assert!(title(Axis::Z, 5).contains("z = 1.4000 m"));
+}
+
+#[test]
+fn arrows_stay_on_the_slice() {
+    // conducting along x with the steepest gradient at the last nodes, which the spacing of
+    // the arrows puts next to the edge
+    let temps = Matrix::from_shape_fn((6, 6, 3), |(i, _, _)| 300. + (i * i * i) as f64);
+    let flux = heat_flux(&temps, DEL, K);
+    let style = Style::builder().arrows(true).arrow_spacing(2).build();
+    let figure = Figure::slice(
+        &temps,
+        Coordinates::Cube(DEL),
+        Some(&flux),
+        Axis::Z,
+        1,
+        &style,
+    )
+    .unwrap();
+    let [x, y, width, height] = figure.plot();
+
+    let svg = figure.to_svg();
+    let coordinate = |line: &str, name: &str| -> f64 {
+        let start = line.find(&format!(r#"{name}=""#)).unwrap() + name.len() + 2;
+        line[start..].split('"').next().unwrap().parse().unwrap()
+    };
+
+    // the arrows are the only lines drawn 1.5 wide, the isotherms and ticks are 1 wide
+    let lines: Vec<&str> = svg
+        .split("<line")
+        .skip(1)
+        .filter(|line| line.contains(r#"stroke-width="1.5""#))
+        .collect();
+    assert!(!lines.is_empty());
+    for line in lines {
+        for (u, v) in [("x1", "y1"), ("x2", "y2")] {
+            let (u, v) = (coordinate(line, u), coordinate(line, v));
+            assert!(u >= x && u <= x + width, "{u} outside {x} to {}", x + width);
+            assert!(
+                v >= y && v <= y + height,
+                "{v} outside {y} to {}",
+                y + height
+            );
+        }
+    }
+}
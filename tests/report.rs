@@ -0,0 +1,167 @@
+//! Checks the report of a slab with a known solution, and of one read back from its JSON
+
+use finite_difference::conditions::{Condition, HeatFlux, Temperature};
+use finite_difference::dump::{Results, SaveFile};
+use finite_difference::error::L2Norm;
+use finite_difference::prelude::{Precision, SolverInfo};
+use finite_difference::report::Report;
+use finite_difference::setup::SetupConditions;
+use finite_difference::{solver, SolverParams};
+
+const K: f64 = 20.;
+const Q_DOT: f64 = 1e5;
+const WALL: f64 = 300.;
+
+/// A slab insulated everywhere but the bottom, all the heat generated in it is conducted out
+/// through the bottom and `T(y) = WALL + q (2 L y - y^2) / 2k`
+fn slab(divisions: usize) -> Results<f64> {
+    let params = SolverParams {
+        len: 0.1,
+        divisions,
+        error_epsilon: 1e-11,
+        data_steps: usize::MAX,
+        error_steps: 10,
+        sweep: Default::default(),
+        threads: None,
+    };
+
+    let adiabatic = Condition::HeatFlux(HeatFlux { heat_flux: 0. });
+    let conditions = SetupConditions {
+        right_boundary: adiabatic,
+        left_boundary: adiabatic,
+        top_boundary: adiabatic,
+        bot_boundary: Condition::Temperature(Temperature { temperature: WALL }),
+        front_boundary: adiabatic,
+        back_boundary: adiabatic,
+    };
+
+    let s = SolverInfo::builder()
+        .k(K)
+        .q_dot(Q_DOT)
+        .del(params.del())
+        .build();
+
+    let simulation =
+        solver::solver(s, params, conditions.make_boundaries(), L2Norm, &[], ()).unwrap();
+
+    SaveFile {
+        conditions,
+        simulation,
+        solver_params: params,
+        precision: Precision::Double,
+        thermal_conductivity: Some(K),
+        energy_generation: Some(Q_DOT),
+        samples: None,
+        heat_flux: None,
+        geometry: None,
+        cylinder: None,
+        orthotropic: None,
+        mirrored: None,
+    }
+}
+
+#[test]
+fn slab_statistics_and_energy_balance() {
+    let divisions = 11;
+    let report = Report::new(&slab(divisions));
+    let len = 0.1;
+
+    assert!((report.min.temperature - WALL).abs() < 1e-6);
+    assert_eq!(report.min.index[1], 0, "coldest on the cooled face");
+    assert_eq!(
+        report.max.index[1],
+        divisions - 1,
+        "hottest on the insulated face"
+    );
+
+    let peak = WALL + Q_DOT * len * len / (2. * K);
+    assert!((report.max.temperature - peak).abs() < 1e-3);
+
+    // the trapezoidal mean of the quadratic profile is second order accurate
+    let mean = WALL + Q_DOT * len * len / (3. * K);
+    assert!((report.mean - mean).abs() < 0.1, "mean {}", report.mean);
+
+    let generated = Q_DOT * len * len * len;
+    assert!((report.heat_generated.unwrap() - generated).abs() < 1e-9);
+
+    for face in &report.faces {
+        let flow = face.heat_flow.unwrap();
+
+        if face.face == "bot" {
+            assert!((face.mean_temperature - WALL).abs() < 1e-6);
+            assert!((flow - generated).abs() < 1e-4 * generated, "bottom {flow}");
+        } else {
+            assert_eq!(flow, 0., "{}", face.face);
+        }
+    }
+
+    assert!(report.imbalance.unwrap().abs() < 1e-4 * generated);
+}
+
+#[test]
+fn report_of_a_saved_file() {
+    let results = slab(7);
+    let report = Report::new(&results);
+
+    let json = serde_json::to_string(&results).unwrap();
+    let read: Results<f64> = serde_json::from_str(&json).unwrap();
+    let reread = Report::new(&read);
+
+    assert_eq!(report.steps, reread.steps);
+    assert_eq!(report.mean, reread.mean);
+    assert_eq!(report.final_error, reread.final_error);
+    assert_eq!(report.imbalance, reread.imbalance);
+
+    let rate = reread.convergence_rate.unwrap();
+    assert!(rate > 0. && rate < 1., "the error falls every step, {rate}");
+
+    let summary = serde_json::to_value(&reread).unwrap();
+    assert_eq!(summary["faces"].as_array().unwrap().len(), 6);
+    assert!(reread.to_string().contains("imbalance"));
+}
+
+#[test]
+fn report_of_a_file_saved_before_the_conditions_were_tagged() {
+    let json = r#"{
+        "conditions": {
+            "right_boundary": {"h": 10.0, "t_inf": 298.0},
+            "left_boundary": {"heat_flux": 0.0},
+            "top_boundary": {"heat_flux": 0.0},
+            "bot_boundary": {"temperature": 350.0},
+            "front_boundary": {"heat_flux": 0.0},
+            "back_boundary": {"heat_flux": 0.0}
+        },
+        "simulation": {
+            "step_data": [{"step": 0, "data": [300.0, 300.0, 300.0, 300.0,
+                                               300.0, 300.0, 300.0, 300.0]}],
+            "error_decay": {"error_type": "L2Norm", "data": []},
+            "size": 2,
+            "num_steps": 0
+        },
+        "solver_params": {"len": 0.5, "divisions": 2, "error_epsilon": 1e-7,
+                          "data_steps": 50000, "error_steps": 1000}
+    }"#;
+
+    let read: Results<f64> = serde_json::from_str(json).unwrap();
+    assert_eq!(read.precision, Precision::Double);
+    assert!(matches!(read.conditions.right_boundary, Condition::Convection(c) if c.h == 10.));
+    assert!(matches!(
+        read.conditions.left_boundary,
+        Condition::HeatFlux(_)
+    ));
+    assert!(matches!(
+        read.conditions.bot_boundary,
+        Condition::Temperature(_)
+    ));
+
+    // convecting 2 K over the half cell of each of the four nodes of the face
+    let report = Report::new(&read);
+    assert_eq!(report.faces[0].heat_flow, Some(5.));
+
+    // the tagged conditions still say what is wrong with them
+    let unknown = r#"{"type": "Radiation", "emissivity": 0.9}"#;
+    let error = serde_json::from_str::<Condition<f64>>(unknown)
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("Radiation"), "{error}");
+}
@@ -0,0 +1,221 @@
+//! Reads boxes written as ASCII and binary STL, voxelizes them and solves a part whose surface
+//! is split into named solids
+
+mod common;
+
+use common::{adiabatic, info, params, K};
+use finite_difference::conditions::{Condition, Convection};
+use finite_difference::dump::SaveFile;
+use finite_difference::error::L2Norm;
+use finite_difference::geometry::{Cell, Geometry, StlFile};
+use finite_difference::input::Conditions;
+use finite_difference::prelude::Precision;
+use finite_difference::report::Report;
+use finite_difference::solver;
+use finite_difference::stencil::{Coefficients, Sweep};
+use finite_difference::stl::Mesh;
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+type Triangle = [[f64; 3]; 3];
+
+/// The two triangles of each face of the box between `min` and `max`, by outward direction
+/// in the order -x, +x, -y, +y, -z, +z
+fn cuboid(min: [f64; 3], max: [f64; 3]) -> [[Triangle; 2]; 6] {
+    let corner =
+        |high: [bool; 3]| [0, 1, 2].map(|axis| if high[axis] { max[axis] } else { min[axis] });
+
+    [0, 1, 2, 3, 4, 5].map(|face| {
+        let axis = face / 2;
+        let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+        let at = |a: bool, b: bool| {
+            let mut high = [false; 3];
+            high[axis] = face % 2 == 1;
+            high[u] = a;
+            high[v] = b;
+            corner(high)
+        };
+
+        [
+            [at(false, false), at(true, false), at(true, true)],
+            [at(false, false), at(true, true), at(false, true)],
+        ]
+    })
+}
+
+fn ascii(solids: &[(&str, Vec<Triangle>)]) -> String {
+    let mut text = String::new();
+    for (name, triangles) in solids {
+        text += &format!("solid {name}\n");
+        for triangle in triangles {
+            text += "  facet normal 0 0 0\n    outer loop\n";
+            for [x, y, z] in triangle {
+                text += &format!("      vertex {x:e} {y:e} {z:e}\n");
+            }
+            text += "    endloop\n  endfacet\n";
+        }
+        text += &format!("endsolid {name}\n");
+    }
+    text
+}
+
+fn binary(triangles: &[Triangle]) -> Vec<u8> {
+    let mut bytes = vec![0; 80];
+    bytes.extend((triangles.len() as u32).to_le_bytes());
+    for triangle in triangles {
+        let normal = [0f32; 3];
+        let vertices = triangle.iter().flatten().map(|&x| x as f32);
+        for x in normal.into_iter().chain(vertices) {
+            bytes.extend(x.to_le_bytes());
+        }
+        bytes.extend(0u16.to_le_bytes());
+    }
+    bytes
+}
+
+fn temp_file(name: &str, contents: impl AsRef<[u8]>) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("{}-{name}", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn ascii_and_binary_files_hold_the_same_triangles() {
+    let triangles: Vec<Triangle> = cuboid([0.25, 0.5, 0.], [1., 0.75, 2.])
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let from_ascii = Mesh::parse(ascii(&[("part", triangles.clone())]).as_bytes(), "box").unwrap();
+    let from_binary = Mesh::parse(&binary(&triangles), "box").unwrap();
+
+    assert_eq!(from_ascii.groups, ["part"]);
+    assert_eq!(from_binary.groups, ["box"]);
+    assert_eq!(from_ascii.triangles, from_binary.triangles);
+    assert_eq!(from_ascii.bounds(), [[0.25, 0.5, 0.], [1., 0.75, 2.]]);
+
+    // a binary file is named after itself
+    let path = temp_file("named.stl", binary(&triangles));
+    let read = Mesh::read(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    assert_eq!(read.groups, [format!("{}-named", std::process::id())]);
+
+    assert!(Mesh::parse(&binary(&triangles)[..200], "box").is_err());
+    assert!(Mesh::parse(
+        b"solid part\n  facet normal 0 0 0\n    outer loop\n      vertex 1 2\n",
+        "box"
+    )
+    .is_err());
+}
+
+#[test]
+fn a_box_voxelizes_to_the_cells_inside_it() {
+    let mut mesh =
+        Mesh::parse(&binary(&cuboid([1., 1., 1.], [5., 3., 7.]).concat()), "box").unwrap();
+    // millimetres to the grid's metres
+    mesh.transform(0.1, [0.; 3]);
+
+    let inside = mesh.voxelize(8, 0.1);
+    for ((x, y, z), &solid) in inside.indexed_iter() {
+        let expected = (1..5).contains(&x) && (1..3).contains(&y) && (1..7).contains(&z);
+        assert_eq!(solid, expected, "cell {x} {y} {z}");
+    }
+}
+
+#[test]
+fn named_solids_set_the_conditions_of_their_faces() {
+    // a box inside the cube, cooled through its top face and insulated elsewhere
+    let faces = cuboid([0.01, 0.01, 0.01], [0.07, 0.05, 0.07]);
+    let sides: Vec<Triangle> = [0, 1, 2, 4, 5]
+        .iter()
+        .flat_map(|&face| faces[face])
+        .collect();
+    let path = temp_file(
+        "cooled.stl",
+        ascii(&[("part", sides), ("cooled", faces[3].to_vec())]),
+    );
+
+    let mut surfaces = BTreeMap::new();
+    surfaces.insert(
+        "cooled".to_string(),
+        Condition::Convection(Convection {
+            h: 50.,
+            t_inf: 290.,
+        }),
+    );
+    let geometry = Geometry {
+        surfaces,
+        meshes: vec![StlFile {
+            path: path.clone(),
+            scale: 1.,
+            offset: [0.; 3],
+            surface: None,
+            material: None,
+        }],
+        ..Geometry::default()
+    };
+
+    let params = params();
+    let del = params.del();
+    let mask = geometry.mask(params.divisions, del).unwrap();
+
+    assert_eq!(mask.cells[[3, 3, 3]], Cell::Solid(0));
+    assert_eq!(mask.cells[[3, 5, 3]], Cell::Void(1));
+    assert_eq!(mask.cells[[0, 3, 3]], Cell::Void(0));
+    assert_eq!(mask.cells[[3, 0, 3]], Cell::Void(0));
+    let solid = mask
+        .cells
+        .iter()
+        .filter(|&&cell| cell == Cell::Solid(0))
+        .count();
+    assert_eq!(solid, 6 * 4 * 6);
+
+    // the walls of the cube touch no material
+    let insulated = adiabatic();
+    let conditions = Conditions {
+        right_boundary: insulated,
+        left_boundary: insulated,
+        top_boundary: insulated,
+        bot_boundary: insulated,
+        front_boundary: insulated,
+        back_boundary: insulated,
+    };
+    let q_dot = 1e5;
+    let info = info(q_dot);
+    let coefficients = Coefficients::masked(
+        &conditions.make_boundaries(),
+        &info,
+        &mask,
+        Sweep::default(),
+    )
+    .unwrap();
+    let simulation = solver::solve(&coefficients, params, &L2Norm, &[], ());
+
+    let results = SaveFile {
+        conditions,
+        simulation,
+        solver_params: params,
+        precision: Precision::Double,
+        thermal_conductivity: Some(K),
+        energy_generation: Some(q_dot),
+        samples: None,
+        heat_flux: None,
+        geometry: Some(geometry),
+        cylinder: None,
+        orthotropic: None,
+        mirrored: None,
+    };
+    let report = Report::new(&results);
+    std::fs::remove_file(path).unwrap();
+
+    // everything generated leaves through the cooled face
+    let generated: f64 = report.heat_generated.unwrap();
+    assert!((generated - q_dot * 0.06 * 0.04 * 0.06).abs() < 1e-9);
+    assert_eq!(report.surfaces.len(), 1);
+    let cooled: f64 = report.surfaces[0].heat_flow.unwrap();
+    assert!(
+        (cooled - generated).abs() / generated < 0.01,
+        "{cooled} of {generated}"
+    );
+}
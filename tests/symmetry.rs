@@ -0,0 +1,237 @@
+//! Checks an eighth of a symmetric cube against the whole, mirrored back and in place, and a
+//! periodic axis against a source shifted along it
+
+mod common;
+
+use common::{convection, DIVISIONS, K, LEN, Q_DOT};
+use finite_difference::conditions::{Condition, Periodic, Symmetry, Temperature};
+use finite_difference::dump::SaveFile;
+use finite_difference::error::L2Norm;
+use finite_difference::geometry::{Error, Geometry, Mask};
+use finite_difference::input::Conditions;
+use finite_difference::prelude::{Matrix, Precision, SolverInfo};
+use finite_difference::report::Report;
+use finite_difference::stencil::{self, Coefficients, Sweep};
+use finite_difference::symmetry::{self, Mirrored};
+use finite_difference::{solver, SolverParams};
+
+use std::f64::consts::PI;
+
+fn params(len: f64, divisions: usize) -> SolverParams<f64> {
+    SolverParams {
+        len,
+        divisions,
+        ..common::params()
+    }
+}
+
+fn info(params: SolverParams<f64>) -> SolverInfo<f64> {
+    SolverInfo::builder()
+        .k(K)
+        .q_dot(Q_DOT)
+        .del(params.del())
+        .build()
+}
+
+fn largest_difference(a: &Matrix<f64>, b: &Matrix<f64>) -> f64 {
+    assert_eq!(a.shape(), b.shape());
+    a.iter()
+        .zip(b)
+        .fold(0., |largest: f64, (a, b)| largest.max((a - b).abs()))
+}
+
+#[test]
+fn an_eighth_of_a_symmetric_cube_is_the_whole() {
+    let solve = |conditions: Conditions<f64>, params: SolverParams<f64>| {
+        let coefficients = Coefficients::new(
+            &conditions.make_boundaries(),
+            &info(params),
+            params.divisions,
+            Sweep::default(),
+        )
+        .unwrap();
+        solver::solve(&coefficients, params, &L2Norm, &[], ()).temperatures()
+    };
+
+    let whole = Conditions {
+        right_boundary: convection(40.),
+        left_boundary: convection(40.),
+        top_boundary: convection(40.),
+        bot_boundary: convection(40.),
+        front_boundary: convection(40.),
+        back_boundary: convection(40.),
+    };
+    let eighth = Conditions {
+        left_boundary: Condition::Symmetry(Symmetry),
+        bot_boundary: Condition::Symmetry(Symmetry),
+        back_boundary: Condition::Symmetry(Symmetry),
+        ..whole
+    };
+
+    // the same spacing over half of the length
+    let half = DIVISIONS / 2;
+    let whole_temps = solve(whole, params(LEN, DIVISIONS));
+    let eighth_temps = solve(eighth, params(LEN / 2., half + 1));
+
+    let centred = whole_temps
+        .slice(ndarray::s![half.., half.., half..])
+        .to_owned();
+    let difference = largest_difference(&eighth_temps, &centred);
+    assert!(difference < 1e-6, "{difference}");
+
+    let mirrored = Mirrored::new(&eighth_temps, &eighth).unwrap();
+    assert_eq!(mirrored.shape, [DIVISIONS; 3]);
+    assert_eq!(mirrored.origin, [half; 3]);
+    let difference = largest_difference(&mirrored.temperatures(), &whole_temps);
+    assert!(difference < 1e-6, "{difference}");
+
+    // nothing to mirror without a symmetry face
+    assert!(Mirrored::new(&whole_temps, &whole).is_none());
+
+    let parsed: Condition<f64> = serde_json::from_str(r#"{"type": "Symmetry"}"#).unwrap();
+    assert!(matches!(parsed, Condition::Symmetry(_)));
+}
+
+#[test]
+fn a_periodic_axis_wraps_around() {
+    let params = params(LEN, DIVISIONS);
+    let del = params.del();
+
+    // periodic along x, held at the bottom and cooled at the top
+    let conditions = |along_x: Condition<f64>| Conditions {
+        right_boundary: along_x,
+        left_boundary: along_x,
+        top_boundary: convection(40.),
+        bot_boundary: Condition::Temperature(Temperature { temperature: 300. }),
+        front_boundary: Condition::Symmetry(Symmetry),
+        back_boundary: Condition::Symmetry(Symmetry),
+    };
+    let periodic = conditions(Condition::Periodic(Periodic));
+    assert_eq!(symmetry::periodic(&periodic), Some([true, false, false]));
+
+    // a source varying over a single period along x
+    let solve = |along_x: Condition<f64>, phase: f64| {
+        let bcs = conditions(along_x).make_boundaries();
+        let s = info(params);
+        let coefficients = Coefficients::new(&bcs, &s, DIVISIONS, Sweep::default())
+            .unwrap()
+            .with_constants(DIVISIONS, |[x, _, _]| {
+                let angle = 2. * PI * del * x as f64 / LEN - phase;
+                let s = SolverInfo {
+                    q_dot: Q_DOT * (1. + angle.cos()),
+                    ..s.clone()
+                };
+                (conditions(along_x).make_boundaries(), s)
+            });
+        solver::solve(&coefficients, params, &L2Norm, &[], ())
+    };
+
+    let cosine = solve(Condition::Periodic(Periodic), 0.).temperatures();
+
+    // symmetric about both faces, so the same as mirroring across them
+    let mirrored = solve(Condition::Symmetry(Symmetry), 0.).temperatures();
+    let difference = largest_difference(&cosine, &mirrored);
+    assert!(difference < 1e-6, "{difference}");
+
+    // a quarter period further along, which is two cells, wrapping past the faces
+    let simulation = solve(Condition::Periodic(Periodic), PI / 2.);
+    let shifted = simulation.temperatures();
+    let cells = DIVISIONS - 1;
+    let expected = Matrix::from_shape_fn(shifted.raw_dim(), |(x, y, z)| {
+        cosine[[(x + cells - 2) % cells, y, z]]
+    });
+    let difference = largest_difference(&shifted, &expected);
+    assert!(difference < 1e-6, "{difference}");
+    assert!(largest_difference(&shifted, &cosine) > 1.);
+
+    // what leaves through one face enters through the other
+    let results = SaveFile {
+        conditions: periodic,
+        simulation,
+        solver_params: params,
+        precision: Precision::Double,
+        thermal_conductivity: Some(K),
+        energy_generation: Some(Q_DOT),
+        samples: None,
+        heat_flux: None,
+        geometry: None,
+        cylinder: None,
+        orthotropic: None,
+        mirrored: None,
+    };
+    let report = Report::new(&results);
+
+    let right: f64 = report.faces[0].heat_flow.unwrap();
+    let left: f64 = report.faces[1].heat_flow.unwrap();
+    assert!(right.abs() > 1., "{right}");
+    assert!((right + left).abs() < 1e-9, "{right} against {left}");
+}
+
+#[test]
+fn a_periodic_face_needs_one_opposite_it() {
+    let params = params(LEN, DIVISIONS);
+    let unpaired = Conditions {
+        right_boundary: Condition::Periodic(Periodic),
+        left_boundary: convection(40.),
+        top_boundary: convection(40.),
+        bot_boundary: convection(40.),
+        front_boundary: convection(40.),
+        back_boundary: convection(40.),
+    };
+    let coefficients = Coefficients::new(
+        &unpaired.make_boundaries(),
+        &info(params),
+        DIVISIONS,
+        Sweep::default(),
+    );
+    assert!(matches!(
+        coefficients,
+        Err(stencil::Error::UnpairedPeriodic(0))
+    ));
+
+    // the voids of a geometry have no opposite face
+    let mut geometry = Geometry::default();
+    geometry
+        .surfaces
+        .insert("wrapped".to_string(), Condition::Periodic(Periodic));
+    assert!(matches!(
+        geometry.mask(DIVISIONS, params.del()),
+        Err(Error::UnsupportedSurface(name)) if name == "wrapped"
+    ));
+}
+
+#[test]
+fn a_masked_geometry_has_no_periodic_faces() {
+    let params = params(LEN, DIVISIONS);
+    let periodic = |paired| Conditions {
+        right_boundary: convection(40.),
+        left_boundary: convection(40.),
+        top_boundary: paired,
+        bot_boundary: paired,
+        front_boundary: convection(40.),
+        back_boundary: convection(40.),
+    };
+    let mask = Mask::solid(DIVISIONS);
+    let masked = |conditions: Conditions<f64>| {
+        Coefficients::masked(
+            &conditions.make_boundaries(),
+            &info(params),
+            &mask,
+            Sweep::default(),
+        )
+    };
+
+    assert!(matches!(
+        masked(periodic(Condition::Periodic(Periodic))),
+        Err(stencil::Error::MaskedPeriodic(1))
+    ));
+    assert!(masked(periodic(convection(40.))).is_ok());
+
+    // an unpaired face is reported as such, as on the full cube
+    let mut unpaired = periodic(convection(40.));
+    unpaired.front_boundary = Condition::Periodic(Periodic);
+    assert!(matches!(
+        masked(unpaired),
+        Err(stencil::Error::UnpairedPeriodic(2))
+    ));
+}
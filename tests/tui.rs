@@ -0,0 +1,52 @@
+//! Checks the slice of the terminal view stays on grids which are not a cube of `divisions`
+#![cfg(feature = "tui")]
+
+use finite_difference::probe::Axis;
+use finite_difference::tui::Selection;
+
+#[test]
+fn selection_steps_along_a_cylinder() {
+    // 5 radii, 12 angles and 7 heights
+    let shape = [5, 12, 7];
+    let mut selection = Selection::middle(Axis::X, shape);
+    assert_eq!(selection.index, 2);
+
+    selection.select(Axis::Y, shape);
+    assert_eq!(selection, Selection::middle(Axis::Y, shape));
+    assert_eq!(selection.index, 6);
+
+    for _ in 0..20 {
+        selection.step(1, shape);
+    }
+    assert_eq!(selection.index, 11);
+
+    selection.select(Axis::Z, shape);
+    assert_eq!(selection.index, 3);
+
+    for _ in 0..20 {
+        selection.step(-1, shape);
+    }
+    assert_eq!(selection.index, 0);
+}
+
+#[test]
+fn selection_keeps_off_single_node_axes() {
+    let cross_section = [9, 9, 1];
+    let mut selection = Selection::middle(Axis::X, cross_section);
+
+    selection.select(Axis::Z, cross_section);
+    assert_eq!((selection.axis, selection.index), (Axis::X, 4));
+
+    selection.select(Axis::Y, cross_section);
+    selection.step(10, cross_section);
+    assert_eq!((selection.axis, selection.index), (Axis::Y, 8));
+
+    let wall = [9, 1, 1];
+    selection.select(Axis::Y, wall);
+    selection.select(Axis::Z, wall);
+    assert_eq!(selection.axis, Axis::Y);
+    assert_eq!(selection.within(wall).index, 0);
+
+    selection.step(1, wall);
+    assert_eq!(selection.index, 0);
+}